@@ -1,4 +1,6 @@
 use rust_assistant::axum::AuthInfo;
+use rust_assistant::forge::ForgeConfig;
+use rust_assistant::GithubAccess;
 use std::net::{Ipv4Addr, SocketAddr};
 use tokio::net::TcpListener;
 
@@ -10,13 +12,18 @@ async fn main() -> anyhow::Result<()> {
     let Some(password) = dotenv::var("API_PASSWORD").ok() else {
         return Err(anyhow::anyhow!("'API_PASSWORD' must be provided",));
     };
-    let Some(github_token) = dotenv::var("GITHUB_ACCESS_TOKEN").ok() else {
-        return Err(anyhow::anyhow!("'GITHUB_ACCESS_TOKEN' must be provided",));
+    // A GitHub App installation, or a comma-separated pool of tokens in `GITHUB_ACCESS_TOKEN`.
+    // Without either, GitHub is disabled unless `GITHUB_ANONYMOUS` allows anonymous access.
+    let github = GithubAccess::from_vars(|name| dotenv::var(name).ok())?;
+    // Additional forges, e.g. `gitea:git.example.com=token,github:github.example.com=token`.
+    let forges = match dotenv::var("FORGES") {
+        Ok(forges) => ForgeConfig::parse_list(&forges)?,
+        Err(_) => Vec::new(),
     };
     let listener = TcpListener::bind(SocketAddr::from((Ipv4Addr::UNSPECIFIED, 3000))).await?;
     Ok(axum::serve(
         listener,
-        rust_assistant::axum::router(AuthInfo::from((username, password)), &github_token)?
+        rust_assistant::axum::router(AuthInfo::from((username, password)), &github, &forges)?
             .into_make_service(),
     )
     .await?)
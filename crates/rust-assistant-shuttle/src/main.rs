@@ -1,4 +1,6 @@
 use rust_assistant::axum::AuthInfo;
+use rust_assistant::forge::ForgeConfig;
+use rust_assistant::GithubAccess;
 use shuttle_runtime::CustomError;
 
 #[shuttle_runtime::main]
@@ -15,10 +17,16 @@ async fn main(
             "'API_PASSWORD' must be provided",
         )));
     };
-    let Some(github_token) = secret_store.get("GITHUB_ACCESS_TOKEN") else {
-        return Err(shuttle_runtime::Error::Custom(CustomError::msg(
-            "'GITHUB_ACCESS_TOKEN' must be provided",
-        )));
+    // A GitHub App installation, or a comma-separated pool of tokens in `GITHUB_ACCESS_TOKEN`.
+    // Without either, GitHub is disabled unless `GITHUB_ANONYMOUS` allows anonymous access.
+    let github = GithubAccess::from_vars(|name| secret_store.get(name))?;
+    // Additional forges, e.g. `gitea:git.example.com=token,github:github.example.com=token`.
+    let forges = match secret_store.get("FORGES") {
+        Some(forges) => ForgeConfig::parse_list(&forges)?,
+        None => Vec::new(),
     };
-    Ok(rust_assistant::axum::router(AuthInfo::from((username, password)), &github_token)?.into())
+    Ok(
+        rust_assistant::axum::router(AuthInfo::from((username, password)), &github, &forges)?
+            .into(),
+    )
 }
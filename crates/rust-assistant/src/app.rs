@@ -6,11 +6,38 @@
 //! between other modules.
 //!
 use crate::cache::{Crate, CrateCache, CrateTar, FileContent};
-use crate::download::CrateDownloader;
-use crate::github::{GithubClient, Issue, IssueEvent, Repository};
+use crate::diff::{ApiDiff, FileDiff};
+use crate::download::{CrateDownloader, DependencyKind};
+use crate::forge::{Forge, ForgeRegistry};
+use crate::github::{
+    ChangedFile, CodeMatch, CodeQuery, Commit, CommitQuery, CommitSummary, Comparison, DiffPage,
+    GithubClient, GithubDisabled, GithubStatus, Issue, IssueComment, IssueEvent, IssueQuery,
+    PageQuery, PullRequest, PullRequestQuery, PullRequestSummary, Release, Repository,
+    RepositoryCommit, RepositoryFile, ReviewComment, Tag, TimelineQuery, UnifiedDiff,
+};
+use crate::manifest::Manifest;
+use crate::outline::OutlineItem;
+use crate::overview::CrateOverview;
+use crate::resolve::{DependencyGraph, ResolveOptions};
+use crate::vcs::{CrateSource, SourceRevision, SourceUnavailable};
+use crate::xref::{Definitions, ParsedCrate, References, Resolution, UsageTarget};
 use crate::{
-    CrateVersion, CrateVersionPath, Directory, FileLineRange, Item, ItemQuery, Line, LineQuery,
+    CrateItem, CrateLine, CrateSearchError, CrateSet, CrateUsage, CrateVersion, CrateVersionPair,
+    CrateVersionPairPath, CrateVersionPath, DiffOptions, Directory, FileLineRange, ImplQuery,
+    ImplSearchResult, ItemQuery, ItemSearchResult, Line, LineQuery, MultiCrateSearchResult,
+    ParseError, Position, SearchBudget,
 };
+use fnv::FnvHashMap;
+use parking_lot::Mutex;
+use semver::VersionReq;
+use std::collections::BTreeSet;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::task::JoinSet;
+
+/// The maximum number of crates covered by a single multi-crate search.
+const MAX_SEARCH_CRATES: usize = 64;
 
 /// The `RustAssistant` struct, providing functionalities to interact with crates and their contents.
 ///
@@ -20,21 +47,52 @@ use crate::{
 pub struct RustAssistant {
     downloader: CrateDownloader,
     cache: CrateCache,
-    github: GithubClient,
+    github: Option<GithubClient>,
+    forges: ForgeRegistry,
+    repository_downloads: Arc<Mutex<FnvHashMap<RepositoryCommit, Arc<tokio::sync::Mutex<()>>>>>,
 }
 
-impl From<(CrateDownloader, CrateCache, GithubClient)> for RustAssistant {
+impl<G: Into<Option<GithubClient>>> From<(CrateDownloader, CrateCache, G)> for RustAssistant {
     /// Creates a new `RustAssistant` instance from a tuple of dependencies.
-    fn from((downloader, cache, github): (CrateDownloader, CrateCache, GithubClient)) -> Self {
+    ///
+    /// The GitHub client, if any, is also registered as the forge of `github.com`. Without one,
+    /// the GitHub methods fail with [`GithubDisabled`].
+    fn from((downloader, cache, github): (CrateDownloader, CrateCache, G)) -> Self {
+        let github = github.into();
+        let mut forges = ForgeRegistry::default();
+        if let Some(github) = &github {
+            forges.insert("github.com", Forge::Github(github.clone()));
+        }
         Self {
             downloader,
             cache,
             github,
+            forges,
+            repository_downloads: Default::default(),
         }
     }
 }
 
 impl RustAssistant {
+    /// Replaces the forges available through the `forge` methods.
+    ///
+    pub fn with_forges(mut self, forges: ForgeRegistry) -> Self {
+        self.forges = forges;
+        self
+    }
+
+    /// Returns the GitHub client, or a [`GithubDisabled`] error if GitHub access is disabled.
+    ///
+    fn github(&self) -> anyhow::Result<&GithubClient> {
+        self.github.as_ref().ok_or_else(|| GithubDisabled.into())
+    }
+
+    /// Reports how the GitHub API is accessed and its last known rate limit.
+    ///
+    pub fn github_status(&self) -> GithubStatus {
+        GithubStatus::of(self.github.as_ref())
+    }
+
     /// Retrieves a crate from the cache or downloads it if not already cached.
     ///
     /// # Arguments
@@ -80,6 +138,22 @@ impl RustAssistant {
         .await?
     }
 
+    /// Builds the outline of a Rust source file within a specified crate.
+    ///
+    /// # Arguments
+    /// * `crate_version_path` - A reference to `CrateVersionPath` specifying the crate and file path.
+    ///
+    /// # Returns
+    /// A `Result` wrapping an `Option<Vec<OutlineItem>>`, or an error if the file is not valid Rust.
+    pub async fn get_file_outline(
+        &self,
+        crate_version_path: &CrateVersionPath,
+    ) -> anyhow::Result<Option<Vec<OutlineItem>>> {
+        let krate = self.get_crate(&crate_version_path.crate_version).await?;
+        let path = crate_version_path.path.clone();
+        tokio::task::spawn_blocking(move || krate.outline(path.as_ref())).await?
+    }
+
     /// Reads the content of a directory within a specified crate.
     ///
     /// # Arguments
@@ -97,6 +171,213 @@ impl RustAssistant {
             .cloned())
     }
 
+    /// Retrieves the Rust source files of a crate that failed to parse when it was indexed.
+    ///
+    /// # Arguments
+    /// * `crate_version` - A reference to `CrateVersion` specifying the crate.
+    ///
+    /// # Returns
+    /// A `Result` wrapping a `Vec<ParseError>`, or an error if the operation fails.
+    pub async fn get_parse_errors(
+        &self,
+        crate_version: &CrateVersion,
+    ) -> anyhow::Result<Vec<ParseError>> {
+        let krate = self.get_crate(crate_version).await?;
+        Ok(krate.parse_errors().to_vec())
+    }
+
+    /// Retrieves the parsed `Cargo.toml` manifest of a crate.
+    ///
+    /// # Arguments
+    /// * `crate_version` - A reference to `CrateVersion` specifying the crate.
+    ///
+    /// # Returns
+    /// A `Result` wrapping an `Option<Arc<Manifest>>`, `None` if the crate has no `Cargo.toml`, or
+    /// an [`InvalidManifest`](crate::manifest::InvalidManifest) error if it cannot be parsed.
+    pub async fn get_manifest(
+        &self,
+        crate_version: &CrateVersion,
+    ) -> anyhow::Result<Option<Arc<Manifest>>> {
+        let krate = self.get_crate(crate_version).await?;
+        if let Some(error) = krate.manifest_error() {
+            return Err(error.clone().into());
+        }
+        Ok(krate.manifest().cloned())
+    }
+
+    /// Builds the overview of a crate.
+    ///
+    /// # Arguments
+    /// * `crate_version` - A reference to `CrateVersion` specifying the crate.
+    ///
+    /// # Returns
+    /// A `Result` wrapping a `CrateOverview`, or an error if the operation fails.
+    pub async fn get_crate_overview(
+        &self,
+        crate_version: &CrateVersion,
+    ) -> anyhow::Result<CrateOverview> {
+        let krate = self.get_crate(crate_version).await?;
+        Ok(tokio::task::spawn_blocking(move || krate.overview()).await?)
+    }
+
+    /// Maps a crate version to the repository, commit and directory it was published from.
+    ///
+    /// # Arguments
+    /// * `crate_version` - A reference to `CrateVersion` specifying the crate.
+    ///
+    /// # Returns
+    /// A `Result` wrapping a `CrateSource`, or an error if the crate cannot be loaded.
+    pub async fn get_crate_source(
+        &self,
+        crate_version: &CrateVersion,
+    ) -> anyhow::Result<CrateSource> {
+        let krate = self.get_crate(crate_version).await?;
+        Ok(CrateSource::of(&krate))
+    }
+
+    /// Reads a file of a crate from its GitHub repository, at the commit the version was
+    /// published from or at the head of the default branch.
+    ///
+    /// # Arguments
+    /// * `crate_version_path` - A reference to `CrateVersionPath` specifying the crate and file path.
+    /// * `revision` - A `SourceRevision` specifying the revision of the repository to read.
+    /// * `file_line_range` - A `FileLineRange` specifying the range of lines to retrieve.
+    ///
+    /// # Returns
+    /// A `Result` wrapping an `Option<RepositoryFile>`, or a `SourceUnavailable` error if the
+    /// crate is not hosted on GitHub or does not record the commit it was published from.
+    pub async fn read_crate_source_file(
+        &self,
+        crate_version_path: &CrateVersionPath,
+        revision: SourceRevision,
+        file_line_range: FileLineRange,
+    ) -> anyhow::Result<Option<RepositoryFile>> {
+        let crate_version = &crate_version_path.crate_version;
+        let source = self.get_crate_source(crate_version).await?;
+        let Some(repo) = source.github.as_ref() else {
+            return Err(SourceUnavailable(format!(
+                "The repository of {crate_version} is not on GitHub."
+            ))
+            .into());
+        };
+        let reference = match revision {
+            SourceRevision::Commit => match source.commit_sha.as_deref() {
+                Some(sha) => Some(sha),
+                None => {
+                    return Err(SourceUnavailable(format!(
+                        "{crate_version} does not record the commit it was published from."
+                    ))
+                    .into())
+                }
+            },
+            SourceRevision::Head => None,
+        };
+        let path = source.repository_path(&crate_version_path.path);
+        self.read_github_repository_file(repo, &path, reference, file_line_range)
+            .await
+    }
+
+    /// Compares the public APIs of two versions of a crate.
+    ///
+    /// # Arguments
+    /// * `versions` - A reference to `CrateVersionPair` specifying the crate and the two versions.
+    ///
+    /// # Returns
+    /// A `Result` wrapping an `ApiDiff` classifying each change against the semver rules,
+    /// or an error if either version cannot be loaded.
+    pub async fn diff_api(&self, versions: &CrateVersionPair) -> anyhow::Result<ApiDiff> {
+        let (old, new) = self.get_crate_pair(versions).await?;
+        let old_version = semver::Version::parse(&versions.old).ok();
+        let new_version = semver::Version::parse(&versions.new).ok();
+        tokio::task::spawn_blocking(move || {
+            crate::diff::diff_api(
+                old.item_index(),
+                new.item_index(),
+                old_version.as_ref().zip(new_version.as_ref()),
+            )
+        })
+        .await
+        .map_err(Into::into)
+    }
+
+    /// Compares the files of two versions of a crate.
+    ///
+    /// # Arguments
+    /// * `versions` - A reference to `CrateVersionPair` specifying the crate and the two versions.
+    /// * `directory` - The directory to compare, or the whole crate if `None`.
+    ///
+    /// # Returns
+    /// A `Result` wrapping an `Option<FileDiff>`, which is `None` if the directory exists in neither version.
+    pub async fn diff_files(
+        &self,
+        versions: &CrateVersionPair,
+        directory: Option<&str>,
+    ) -> anyhow::Result<Option<FileDiff>> {
+        let (old, new) = self.get_crate_pair(versions).await?;
+        let directory = directory.map(std::path::PathBuf::from);
+        if let Some(directory) = &directory {
+            if old.read_directory(directory).is_none() && new.read_directory(directory).is_none() {
+                return Ok(None);
+            }
+        }
+        Ok(Some(
+            tokio::task::spawn_blocking(move || {
+                crate::diff::diff_files(&old, &new, directory.as_deref())
+            })
+            .await?,
+        ))
+    }
+
+    /// Produces a unified diff of a file between two versions of a crate.
+    ///
+    /// # Arguments
+    /// * `path` - A reference to `CrateVersionPairPath` specifying the crate, the two versions and the file.
+    /// * `options` - The `DiffOptions` of the unified diff.
+    ///
+    /// # Returns
+    /// A `Result` wrapping an `Option<String>`, which is `None` if the file exists in neither version,
+    /// and empty if the file is unchanged.
+    pub async fn diff_file(
+        &self,
+        path: &CrateVersionPairPath,
+        options: DiffOptions,
+    ) -> anyhow::Result<Option<String>> {
+        let (old, new) = self.get_crate_pair(&path.versions).await?;
+        let file = std::path::PathBuf::from(path.path.as_ref());
+        let context = options.context.unwrap_or(3);
+        Ok(
+            tokio::task::spawn_blocking(move || crate::diff::diff_file(&old, &new, &file, context))
+                .await?,
+        )
+    }
+
+    /// Loads both versions of a crate concurrently.
+    async fn get_crate_pair(&self, versions: &CrateVersionPair) -> anyhow::Result<(Crate, Crate)> {
+        let (old, new) = (versions.old_version(), versions.new_version());
+        tokio::try_join!(self.get_crate(&old), self.get_crate(&new))
+    }
+
+    /// Resolves the transitive dependency graph of a crate.
+    ///
+    /// # Arguments
+    /// * `crate_version` - A reference to `CrateVersion` specifying the root crate.
+    /// * `options` - The `ResolveOptions` specifying features, target and dependency kinds.
+    ///
+    /// # Returns
+    /// A `Result` wrapping a `DependencyGraph`, or an error if the root crate cannot be resolved.
+    pub async fn resolve_dependencies(
+        &self,
+        crate_version: &CrateVersion,
+        options: impl Into<ResolveOptions>,
+    ) -> anyhow::Result<DependencyGraph> {
+        crate::resolve::resolve_dependencies(
+            &self.downloader,
+            crate_version.clone(),
+            options.into(),
+        )
+        .await
+    }
+
     /// Searches for items in a crate based on a query.
     ///
     /// # Arguments
@@ -104,15 +385,49 @@ impl RustAssistant {
     /// * `query` - An `ItemQuery` specifying the search criteria.
     ///
     /// # Returns
-    /// A `Result` wrapping a `Vec<Item>`, or an error if the operation fails.
+    /// A `Result` wrapping an `ItemSearchResult`, or an error if the operation fails.
     pub async fn search_item(
         &self,
         crate_version: &CrateVersion,
         query: impl Into<ItemQuery>,
-    ) -> anyhow::Result<Vec<Item>> {
+    ) -> anyhow::Result<ItemSearchResult> {
         let krate = self.get_crate(crate_version).await?;
         let query = query.into();
-        Ok(tokio::task::spawn_blocking(move || krate.search_item(&query)).await?)
+        tokio::task::spawn_blocking(move || krate.search_item(&query)).await?
+    }
+
+    /// Finds the implementors of a trait in a crate.
+    ///
+    /// # Arguments
+    /// * `crate_version` - A reference to `CrateVersion` specifying the crate to search in.
+    /// * `query` - An `ImplQuery` naming the trait.
+    ///
+    /// # Returns
+    /// A `Result` wrapping an `ImplSearchResult`, or an error if the operation fails.
+    pub async fn implementors(
+        &self,
+        crate_version: &CrateVersion,
+        query: ImplQuery,
+    ) -> anyhow::Result<ImplSearchResult> {
+        let krate = self.get_crate(crate_version).await?;
+        Ok(tokio::task::spawn_blocking(move || krate.implementors(&query)).await?)
+    }
+
+    /// Finds the traits implemented by a type in a crate.
+    ///
+    /// # Arguments
+    /// * `crate_version` - A reference to `CrateVersion` specifying the crate to search in.
+    /// * `query` - An `ImplQuery` naming the type.
+    ///
+    /// # Returns
+    /// A `Result` wrapping an `ImplSearchResult`, or an error if the operation fails.
+    pub async fn implemented_traits(
+        &self,
+        crate_version: &CrateVersion,
+        query: ImplQuery,
+    ) -> anyhow::Result<ImplSearchResult> {
+        let krate = self.get_crate(crate_version).await?;
+        Ok(tokio::task::spawn_blocking(move || krate.implemented_traits(&query)).await?)
     }
 
     /// Searches for lines in a crate's files based on a query.
@@ -133,23 +448,612 @@ impl RustAssistant {
         tokio::task::spawn_blocking(move || krate.search_line(&query)).await?
     }
 
+    /// Resolves a `CrateSet` into the list of crates it covers.
+    ///
+    /// For `CrateSet::Dependencies`, the normal, non-optional dependencies of each crate are
+    /// resolved to their highest matching version in the registry index, breadth-first, up to
+    /// the specified depth. At most 64 crates are returned.
+    ///
+    /// # Arguments
+    /// * `crate_set` - A reference to `CrateSet` specifying the crates.
+    ///
+    /// # Returns
+    /// A `Result` wrapping the resolved `CrateVersion`s and the dependencies that could not be
+    /// resolved, or an error if the operation fails. The version of a dependency whose requirement
+    /// could not be resolved is the requirement itself.
+    pub async fn resolve_crate_set(
+        &self,
+        crate_set: &CrateSet,
+    ) -> anyhow::Result<(Vec<CrateVersion>, Vec<CrateSearchError>)> {
+        let (root, depth) = match crate_set {
+            CrateSet::Crates(crates) => {
+                let mut seen = BTreeSet::new();
+                let crates = crates
+                    .iter()
+                    .filter(|crate_version| seen.insert((*crate_version).clone()))
+                    .take(MAX_SEARCH_CRATES)
+                    .cloned()
+                    .collect();
+                return Ok((crates, Vec::new()));
+            }
+            CrateSet::Dependencies { root, depth } => (root, *depth),
+        };
+
+        let mut resolved = vec![root.clone()];
+        let mut failed = Vec::new();
+        let mut seen = BTreeSet::from([root.clone()]);
+        let mut current = vec![root.clone()];
+        for _ in 0..depth {
+            let mut tasks = JoinSet::new();
+            for crate_version in current.drain(..) {
+                let downloader = self.downloader.clone();
+                tasks.spawn(async move {
+                    let mut dependencies = Vec::new();
+                    let mut failed = Vec::new();
+                    let entry = match downloader.get_index_entry(&crate_version).await {
+                        Ok(Some(entry)) => entry,
+                        Ok(None) => {
+                            failed.push(CrateSearchError {
+                                error: format!(
+                                    "Crate not found in the registry index: {crate_version}"
+                                ),
+                                crate_version,
+                            });
+                            return (dependencies, failed);
+                        }
+                        Err(error) => {
+                            failed.push(CrateSearchError {
+                                error: format!("Failed to read the dependencies: {error}"),
+                                crate_version,
+                            });
+                            return (dependencies, failed);
+                        }
+                    };
+
+                    for dep in entry.deps.iter().filter(|dep| {
+                        dep.kind == DependencyKind::Normal
+                            && !dep.optional
+                            && dep.registry.is_none()
+                    }) {
+                        let resolved = match VersionReq::parse(&dep.req) {
+                            Ok(req) => downloader.resolve_version(dep.package_name(), &req).await,
+                            Err(error) => Err(error.into()),
+                        };
+                        match resolved {
+                            Ok(Some(version)) => dependencies.push(version),
+                            Ok(None) => {}
+                            Err(error) => failed.push(CrateSearchError {
+                                crate_version: CrateVersion::from((
+                                    dep.package_name(),
+                                    dep.req.as_str(),
+                                )),
+                                error: format!(
+                                    "Failed to resolve the dependency of {crate_version}: {error}"
+                                ),
+                            }),
+                        }
+                    }
+                    (dependencies, failed)
+                });
+            }
+
+            let mut next = BTreeSet::new();
+            while let Some(result) = tasks.join_next().await {
+                let (dependencies, errors) = result?;
+                next.extend(dependencies);
+                failed.extend(errors);
+            }
+
+            for crate_version in next {
+                if resolved.len() >= MAX_SEARCH_CRATES {
+                    return Ok((resolved, failed));
+                }
+                if seen.insert(crate_version.clone()) {
+                    resolved.push(crate_version.clone());
+                    current.push(crate_version);
+                }
+            }
+
+            if current.is_empty() {
+                break;
+            }
+        }
+
+        Ok((resolved, failed))
+    }
+
+    /// Searches for items across multiple crates concurrently.
+    ///
+    /// # Arguments
+    /// * `crate_set` - A reference to `CrateSet` specifying the crates to search in.
+    /// * `query` - An `ItemQuery` specifying the search criteria.
+    /// * `budget` - A `SearchBudget` limiting the total results and time spent.
+    ///
+    /// # Returns
+    /// A `Result` wrapping a `MultiCrateSearchResult<CrateItem>`, or an error if the crates cannot be resolved.
+    pub async fn search_items_in_crates(
+        &self,
+        crate_set: &CrateSet,
+        query: impl Into<ItemQuery>,
+        budget: SearchBudget,
+    ) -> anyhow::Result<MultiCrateSearchResult<CrateItem>> {
+        let query = query.into();
+        self.search_in_crates(crate_set, budget, move |krate, crate_version, _| {
+            let result = krate.search_item(&query)?;
+            let items = result
+                .items
+                .into_iter()
+                .map(|item| CrateItem {
+                    crate_version: crate_version.clone(),
+                    item,
+                })
+                .collect();
+            Ok((items, result.incomplete))
+        })
+        .await
+    }
+
+    /// Searches for lines across multiple crates concurrently.
+    ///
+    /// # Arguments
+    /// * `crate_set` - A reference to `CrateSet` specifying the crates to search in.
+    /// * `query` - A `LineQuery` specifying the search criteria.
+    /// * `budget` - A `SearchBudget` limiting the total results and time spent.
+    ///
+    /// # Returns
+    /// A `Result` wrapping a `MultiCrateSearchResult<CrateLine>`, or an error if the crates cannot be resolved.
+    pub async fn search_lines_in_crates(
+        &self,
+        crate_set: &CrateSet,
+        query: impl Into<LineQuery>,
+        budget: SearchBudget,
+    ) -> anyhow::Result<MultiCrateSearchResult<CrateLine>> {
+        let query = query.into();
+        self.search_in_crates(crate_set, budget, move |krate, crate_version, limit| {
+            let mut query = query.clone();
+            query.max_results = Some(query.max_results.map_or(limit, |max| max.min(limit)));
+            let lines = krate
+                .search_line(&query)?
+                .into_iter()
+                .map(|line| CrateLine {
+                    crate_version: crate_version.clone(),
+                    line,
+                })
+                .collect();
+            Ok((lines, false))
+        })
+        .await
+    }
+
+    /// Finds the usages of an item across the crates depending on the crate defining it.
+    ///
+    /// # Arguments
+    /// * `target` - A reference to `CrateVersion` specifying the crate defining the item.
+    /// * `path` - The path of the item within its crate, e.g. `de::Deserialize` or `Bytes::new`.
+    /// * `crate_set` - A reference to `CrateSet` specifying the dependent crates to scan.
+    /// * `budget` - A `SearchBudget` limiting the total results and time spent.
+    ///
+    /// # Returns
+    /// A `Result` wrapping a `MultiCrateSearchResult<CrateUsage>`, or an error if the crates cannot be resolved.
+    pub async fn find_usages_in_crates(
+        &self,
+        target: &CrateVersion,
+        path: &str,
+        crate_set: &CrateSet,
+        budget: SearchBudget,
+    ) -> anyhow::Result<MultiCrateSearchResult<CrateUsage>> {
+        let krate = self.get_crate(target).await?;
+        let (name, path) = (target.krate.clone(), path.to_string());
+        let target = Arc::new(
+            tokio::task::spawn_blocking(move || UsageTarget::new(krate.item_index(), &name, &path))
+                .await?,
+        );
+        self.search_in_crates(crate_set, budget, move |krate, crate_version, limit| {
+            let (mut usages, incomplete) = crate::xref::find_usages(&krate, &target)?;
+            usages.truncate(limit.get());
+            let usages = usages
+                .into_iter()
+                .map(|usage| CrateUsage {
+                    crate_version: crate_version.clone(),
+                    usage,
+                })
+                .collect();
+            Ok((usages, incomplete))
+        })
+        .await
+    }
+
+    /// Finds the definitions of the symbol at a position in a source file of a crate.
+    ///
+    /// Paths are resolved through the module tree, the `use` declarations in scope and the item
+    /// index. When a path leads to a dependency, it is resolved in the highest version of the
+    /// dependency matching the requirement of the manifest, following re-exports across crates.
+    ///
+    /// # Arguments
+    /// * `crate_version` - A reference to `CrateVersion` specifying the crate.
+    /// * `path` - The path of the source file within the crate.
+    /// * `position` - The `Position` of the symbol in the file.
+    ///
+    /// # Returns
+    /// A `Result` wrapping an `Option<Definitions>`, `None` if there is no path at the position.
+    pub async fn goto_definition(
+        &self,
+        crate_version: &CrateVersion,
+        path: &str,
+        position: Position,
+    ) -> anyhow::Result<Option<Definitions>> {
+        let krate = self.get_crate(crate_version).await?;
+        let file = std::path::PathBuf::from(path);
+        let (symbol, mut resolution) = {
+            let krate = krate.clone();
+            let resolved = tokio::task::spawn_blocking(move || {
+                let parsed = ParsedCrate::with_filter(&krate, |path, _| path == file);
+                let symbol = parsed.symbol_at(&file, position.line.get(), position.column.get())?;
+                let resolution = crate::xref::resolve_symbol(&krate, &symbol);
+                Some((symbol, resolution))
+            })
+            .await?;
+            match resolved {
+                Some(resolved) => resolved,
+                None => return Ok(None),
+            }
+        };
+
+        let mut defining = crate_version.clone();
+        // Re-exports may lead through several crates, e.g. a facade crate re-exporting its parts.
+        for _ in 0..4 {
+            let Resolution::Dependency(dependency, path) = resolution else {
+                break;
+            };
+            let req = VersionReq::parse(dependency.req.as_deref().unwrap_or("*"))?;
+            let Some(version) = self
+                .downloader
+                .resolve_version(dependency.package_name(), &req)
+                .await?
+            else {
+                resolution = Resolution::Unresolved;
+                break;
+            };
+            let krate = self.get_crate(&version).await?;
+            defining = version;
+            resolution = tokio::task::spawn_blocking(move || {
+                let lib_root = krate.item_index().lib_root.clone();
+                crate::xref::resolve_path(&krate, path, &lib_root)
+            })
+            .await?;
+        }
+
+        let (resolved, definitions) = match resolution {
+            Resolution::Local(path, items) => {
+                let ident = defining.krate.replace('-', "_");
+                let resolved = match path.first().map(String::as_str) {
+                    Some("crate") => std::iter::once(ident.as_str())
+                        .chain(path[1..].iter().map(String::as_str))
+                        .collect::<Vec<_>>()
+                        .join("::"),
+                    _ => path.join("::"),
+                };
+                (Some(resolved), items)
+            }
+            _ => (None, Vec::new()),
+        };
+        Ok(Some(Definitions {
+            symbol: symbol.name,
+            path: symbol.path,
+            kind: symbol.kind,
+            resolved,
+            definitions: definitions
+                .into_iter()
+                .map(|item| CrateItem {
+                    crate_version: defining.clone(),
+                    item,
+                })
+                .collect(),
+        }))
+    }
+
+    /// Finds the references to an item of a crate within the crate itself.
+    ///
+    /// # Arguments
+    /// * `crate_version` - A reference to `CrateVersion` specifying the crate.
+    /// * `path` - The path of the item within the crate, e.g. `de::Deserialize` or `Bytes::new`.
+    ///
+    /// # Returns
+    /// A `Result` wrapping the `References` found, or an error if the crate cannot be loaded.
+    pub async fn find_references(
+        &self,
+        crate_version: &CrateVersion,
+        path: &str,
+    ) -> anyhow::Result<References> {
+        let krate = self.get_crate(crate_version).await?;
+        let path = path.to_string();
+        Ok(
+            tokio::task::spawn_blocking(move || crate::xref::find_references(&krate, &path))
+                .await?,
+        )
+    }
+
+    /// Runs a search function on every crate of a `CrateSet` concurrently and merges the results.
+    ///
+    /// The search function returns the results of a crate and whether they may be incomplete.
+    ///
+    async fn search_in_crates<T, F>(
+        &self,
+        crate_set: &CrateSet,
+        budget: SearchBudget,
+        search: F,
+    ) -> anyhow::Result<MultiCrateSearchResult<T>>
+    where
+        T: Send + 'static,
+        F: Fn(Crate, &CrateVersion, NonZeroUsize) -> anyhow::Result<(Vec<T>, bool)>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let deadline = tokio::time::Instant::now() + budget.timeout;
+        let (crates, failed) = tokio::time::timeout_at(deadline, self.resolve_crate_set(crate_set))
+            .await
+            .map_err(|_| anyhow::anyhow!("Timed out while resolving the crates to search."))??;
+
+        let search = Arc::new(search);
+        let mut tasks = JoinSet::new();
+        for (index, crate_version) in crates.iter().cloned().enumerate() {
+            let this = self.clone();
+            let search = search.clone();
+            tasks.spawn(async move {
+                let result = async {
+                    let krate = this.get_crate(&crate_version).await?;
+                    let crate_version = crate_version.clone();
+                    tokio::task::spawn_blocking(move || {
+                        search(krate, &crate_version, budget.max_results)
+                    })
+                    .await?
+                }
+                .await;
+                (index, result)
+            });
+        }
+
+        let mut per_crate = crates.iter().map(|_| None).collect::<Vec<_>>();
+        let mut timed_out = false;
+        loop {
+            match tokio::time::timeout_at(deadline, tasks.join_next()).await {
+                Ok(Some(joined)) => {
+                    let (index, result) = joined?;
+                    per_crate[index] = Some(result);
+                }
+                Ok(None) => break,
+                Err(_) => {
+                    timed_out = true;
+                    tasks.abort_all();
+                    break;
+                }
+            }
+        }
+
+        let mut merged = MultiCrateSearchResult {
+            results: Vec::new(),
+            searched: Vec::new(),
+            failed,
+            incomplete: Vec::new(),
+            truncated: false,
+            timed_out,
+        };
+        for (crate_version, result) in crates.into_iter().zip(per_crate) {
+            match result {
+                Some(Ok((results, incomplete))) => {
+                    if incomplete {
+                        merged.incomplete.push(crate_version.clone());
+                    }
+                    merged.searched.push(crate_version);
+                    let remaining = budget.max_results.get() - merged.results.len();
+                    if results.len() > remaining {
+                        merged.truncated = true;
+                    }
+                    merged.results.extend(results.into_iter().take(remaining));
+                }
+                Some(Err(error)) => merged.failed.push(CrateSearchError {
+                    crate_version,
+                    error: error.to_string(),
+                }),
+                None => merged.failed.push(CrateSearchError {
+                    crate_version,
+                    error: "The time budget was exhausted before the search completed.".to_string(),
+                }),
+            }
+        }
+
+        Ok(merged)
+    }
+
+    /// Retrieves the snapshot of a GitHub repository at a branch, tag or commit.
+    ///
+    /// The reference is resolved to a commit first, and the repository tarball at that commit is
+    /// downloaded and indexed like a crate unless it is already cached.
+    ///
+    /// # Arguments
+    /// * `repo` - A reference to `Repository` specifying the GitHub repository.
+    /// * `branch` - An optional `&str` specifying the branch, tag or commit, the default branch if `None`.
+    ///
+    /// # Returns
+    /// A `Result` wrapping an `Option<Crate>`, `None` if the repository or the reference does not exist.
+    ///
+    pub async fn get_github_repository_crate(
+        &self,
+        repo: &Repository,
+        branch: impl Into<Option<&str>>,
+    ) -> anyhow::Result<Option<Crate>> {
+        let Some(commit) = self.github()?.get_commit_sha(repo, branch).await? else {
+            return Ok(None);
+        };
+        if let Some(krate) = self.cache.get_repository(&commit) {
+            return Ok(Some(krate));
+        }
+
+        // Concurrent requests for the same commit wait for a single download.
+        let download = self
+            .repository_downloads
+            .lock()
+            .entry(commit.clone())
+            .or_default()
+            .clone();
+        let result: anyhow::Result<Option<Crate>> = async {
+            let _guard = download.lock().await;
+            if let Some(krate) = self.cache.get_repository(&commit) {
+                return Ok(Some(krate));
+            }
+            let Some(data) = self.github()?.download_tarball(&commit).await? else {
+                return Ok(None);
+            };
+            let krate =
+                tokio::task::spawn_blocking(move || Crate::from_repository_tar(&data)).await??;
+            self.cache.set_repository(commit.clone(), krate.clone());
+            Ok(Some(krate))
+        }
+        .await;
+
+        let mut downloads = self.repository_downloads.lock();
+        // The last request for the commit removes its entry.
+        if Arc::strong_count(&download) == 2 {
+            downloads.remove(&commit);
+        }
+        result
+    }
+
+    /// Searches for items in a GitHub repository, indexed like a crate.
+    ///
+    /// # Arguments
+    /// * `repo` - A reference to `Repository` specifying the GitHub repository.
+    /// * `branch` - An optional `&str` specifying the branch, tag or commit.
+    /// * `query` - An `ItemQuery` for the search.
+    ///
+    /// # Returns
+    /// A `Result` wrapping an `Option<ItemSearchResult>`, `None` if the repository or the reference does not exist.
+    ///
+    pub async fn search_github_repository_for_items(
+        &self,
+        repo: &Repository,
+        branch: impl Into<Option<&str>>,
+        query: impl Into<ItemQuery>,
+    ) -> anyhow::Result<Option<ItemSearchResult>> {
+        let Some(krate) = self.get_github_repository_crate(repo, branch).await? else {
+            return Ok(None);
+        };
+        let query = query.into();
+        tokio::task::spawn_blocking(move || krate.search_item(&query).map(Some)).await?
+    }
+
+    /// Searches for lines in the files of a GitHub repository.
+    ///
+    /// # Arguments
+    /// * `repo` - A reference to `Repository` specifying the GitHub repository.
+    /// * `branch` - An optional `&str` specifying the branch, tag or commit.
+    /// * `query` - A `LineQuery` for the search.
+    ///
+    /// # Returns
+    /// A `Result` wrapping an `Option<Vec<Line>>`, `None` if the repository or the reference does not exist.
+    ///
+    pub async fn search_github_repository_for_lines(
+        &self,
+        repo: &Repository,
+        branch: impl Into<Option<&str>>,
+        query: impl Into<LineQuery>,
+    ) -> anyhow::Result<Option<Vec<Line>>> {
+        let Some(krate) = self.get_github_repository_crate(repo, branch).await? else {
+            return Ok(None);
+        };
+        let query = query.into();
+        tokio::task::spawn_blocking(move || krate.search_line(&query).map(Some)).await?
+    }
+
+    /// Builds the outline of a Rust source file in a GitHub repository.
+    ///
+    /// # Arguments
+    /// * `repo` - A reference to `Repository` specifying the GitHub repository.
+    /// * `path` - A `&str` specifying the file path.
+    /// * `branch` - An optional `&str` specifying the branch, tag or commit.
+    ///
+    /// # Returns
+    /// A `Result` wrapping an `Option<Vec<OutlineItem>>`, or an error if the file is not valid Rust.
+    ///
+    pub async fn get_github_repository_file_outline(
+        &self,
+        repo: &Repository,
+        path: &str,
+        branch: impl Into<Option<&str>>,
+    ) -> anyhow::Result<Option<Vec<OutlineItem>>> {
+        let Some(krate) = self.get_github_repository_crate(repo, branch).await? else {
+            return Ok(None);
+        };
+        let path = PathBuf::from(path);
+        tokio::task::spawn_blocking(move || krate.outline(path)).await?
+    }
+
+    /// Reads a directory of a GitHub repository from its tarball rather than the contents API.
+    ///
+    /// # Arguments
+    /// * `repo` - A reference to `Repository` specifying the GitHub repository.
+    /// * `path` - A `&str` specifying the directory path.
+    /// * `branch` - An optional `&str` specifying the branch, tag or commit.
+    ///
+    /// # Returns
+    /// A `Result` wrapping an `Option<Directory>`, or an error if the operation fails.
+    ///
+    pub async fn read_github_repository_tree(
+        &self,
+        repo: &Repository,
+        path: &str,
+        branch: impl Into<Option<&str>>,
+    ) -> anyhow::Result<Option<Directory>> {
+        let Some(krate) = self.get_github_repository_crate(repo, branch).await? else {
+            return Ok(None);
+        };
+        Ok(krate.read_directory(path).cloned())
+    }
+
     /// Reads the content of a file within a specified GitHub repository.
     ///
     /// # Arguments
     /// * `repo` - A reference to `Repository` specifying the GitHub repository.
     /// * `path` - A `&str` specifying the file path.
     /// * `branch` - An optional `&str` specifying the branch name.
+    /// * `file_line_range` - A `FileLineRange` specifying the range of lines to retrieve.
     ///
     /// # Returns
-    /// A `Result` wrapping a `FileContent`, or an error if the operation fails.
+    /// A `Result` wrapping a `RepositoryFile` with the requested lines and the blob SHA and size of
+    /// the whole file, or an error if the operation fails.
     ///
     pub async fn read_github_repository_file(
         &self,
         repo: &Repository,
         path: &str,
         branch: impl Into<Option<&str>>,
-    ) -> anyhow::Result<Option<FileContent>> {
-        self.github.get_file(repo, path, branch).await
+        file_line_range: FileLineRange,
+    ) -> anyhow::Result<Option<RepositoryFile>> {
+        let Some(file) = self.github()?.get_file(repo, path, branch).await? else {
+            return Ok(None);
+        };
+        let Some(content) = file.content.get_file_line_range(file_line_range)? else {
+            return Ok(None);
+        };
+        Ok(Some(RepositoryFile { content, ..file }))
+    }
+
+    /// Searches the code of a GitHub repository with the GitHub code search API.
+    ///
+    /// # Arguments
+    /// * `repo` - A reference to `Repository` specifying the GitHub repository.
+    /// * `query` - A reference to `CodeQuery` specifying the search terms and qualifiers.
+    ///
+    /// # Returns
+    /// A `Result` wrapping a `Vec<CodeMatch>`, or an error if the operation fails.
+    ///
+    pub async fn search_github_repository_code(
+        &self,
+        repo: &Repository,
+        query: &CodeQuery,
+    ) -> anyhow::Result<Vec<CodeMatch>> {
+        self.github()?.search_code(repo, query).await
     }
 
     /// Reads the content of a directory within a specified GitHub repository.
@@ -168,14 +1072,14 @@ impl RustAssistant {
         path: &str,
         branch: impl Into<Option<&str>>,
     ) -> anyhow::Result<Option<Directory>> {
-        self.github.read_dir(repo, path, branch).await
+        self.github()?.read_dir(repo, path, branch).await
     }
 
     /// Searches for issues in a specified GitHub repository based on a query.
     ///
     /// # Arguments
     /// * `repo` - A reference to `Repository` specifying the GitHub repository.
-    /// * `query` - A `&str` specifying the query string.
+    /// * `query` - A reference to `IssueQuery` with the free text, the filters and the limit.
     ///
     /// # Returns
     /// A `Result` wrapping a `Vec<Issue>`, or an error if the operation fails.
@@ -183,9 +1087,9 @@ impl RustAssistant {
     pub async fn search_github_repository_for_issues(
         &self,
         repo: &Repository,
-        query: &str,
+        query: &IssueQuery,
     ) -> anyhow::Result<Vec<Issue>> {
-        self.github.search_for_issues(repo, query).await
+        self.github()?.search_for_issues(repo, query).await
     }
 
     /// Retrieves the timeline of an issue in a specified GitHub repository.
@@ -193,6 +1097,7 @@ impl RustAssistant {
     /// # Arguments
     /// * `repo` - A reference to `Repository` specifying the GitHub repository.
     /// * `issue_number` - A `u64` specifying the issue number.
+    /// * `query` - A reference to `TimelineQuery` with the event limit and the substantive filter.
     ///
     /// # Returns
     /// A `Result` wrapping a `Vec<IssueEvent>`, or an error if the operation fails.
@@ -201,8 +1106,194 @@ impl RustAssistant {
         &self,
         repo: &Repository,
         issue_number: u64,
+        query: &TimelineQuery,
     ) -> anyhow::Result<Vec<IssueEvent>> {
-        self.github.get_issue_timeline(repo, issue_number).await
+        self.github()?
+            .get_issue_timeline(repo, issue_number, query)
+            .await
+    }
+
+    /// Lists the comments of an issue or pull request in a GitHub repository.
+    ///
+    /// # Arguments
+    /// * `repo` - A reference to `Repository` specifying the GitHub repository.
+    /// * `issue_number` - A `u64` specifying the issue number.
+    /// * `page` - A reference to `PageQuery` specifying the page of comments.
+    ///
+    /// # Returns
+    /// A `Result` wrapping an `Option<Vec<IssueComment>>`, or an error if the operation fails.
+    ///
+    pub async fn list_github_issue_comments(
+        &self,
+        repo: &Repository,
+        issue_number: u64,
+        page: &PageQuery,
+    ) -> anyhow::Result<Option<Vec<IssueComment>>> {
+        self.github()?
+            .list_issue_comments(repo, issue_number, page)
+            .await
+    }
+
+    /// Lists the pull requests of a GitHub repository.
+    ///
+    /// # Arguments
+    /// * `repo` - A reference to `Repository` specifying the GitHub repository.
+    /// * `query` - A reference to `PullRequestQuery` with the state, label and author filters.
+    ///
+    /// # Returns
+    /// A `Result` wrapping a `Vec<PullRequestSummary>`, or an error if the operation fails.
+    ///
+    pub async fn list_github_pull_requests(
+        &self,
+        repo: &Repository,
+        query: &PullRequestQuery,
+    ) -> anyhow::Result<Vec<PullRequestSummary>> {
+        self.github()?.list_pull_requests(repo, query).await
+    }
+
+    /// Retrieves the details of a pull request in a GitHub repository.
+    ///
+    pub async fn get_github_pull_request(
+        &self,
+        repo: &Repository,
+        number: u64,
+    ) -> anyhow::Result<Option<PullRequest>> {
+        self.github()?.get_pull_request(repo, number).await
+    }
+
+    /// Retrieves the review comments of a pull request in a GitHub repository.
+    ///
+    pub async fn get_github_pull_request_review_comments(
+        &self,
+        repo: &Repository,
+        number: u64,
+    ) -> anyhow::Result<Option<Vec<ReviewComment>>> {
+        self.github()?
+            .get_pull_request_review_comments(repo, number)
+            .await
+    }
+
+    /// Lists the files changed by a pull request in a GitHub repository.
+    ///
+    pub async fn get_github_pull_request_files(
+        &self,
+        repo: &Repository,
+        number: u64,
+    ) -> anyhow::Result<Option<Vec<ChangedFile>>> {
+        self.github()?.get_pull_request_files(repo, number).await
+    }
+
+    /// Retrieves a page of the unified diff of a pull request in a GitHub repository.
+    ///
+    /// # Arguments
+    /// * `repo` - A reference to `Repository` specifying the GitHub repository.
+    /// * `number` - A `u64` specifying the pull request number.
+    /// * `page` - A reference to `DiffPage` specifying the files to include.
+    ///
+    /// # Returns
+    /// A `Result` wrapping an `Option<UnifiedDiff>`, or an error if the operation fails.
+    ///
+    pub async fn get_github_pull_request_diff(
+        &self,
+        repo: &Repository,
+        number: u64,
+        page: &DiffPage,
+    ) -> anyhow::Result<Option<UnifiedDiff>> {
+        self.github()?
+            .get_pull_request_diff(repo, number, page)
+            .await
+    }
+
+    /// Lists the tags of a GitHub repository.
+    ///
+    /// # Arguments
+    /// * `repo` - A reference to `Repository` specifying the GitHub repository.
+    /// * `page` - A reference to `PageQuery` specifying the page of tags.
+    ///
+    /// # Returns
+    /// A `Result` wrapping an `Option<Vec<Tag>>`, or an error if the operation fails.
+    ///
+    pub async fn list_github_tags(
+        &self,
+        repo: &Repository,
+        page: &PageQuery,
+    ) -> anyhow::Result<Option<Vec<Tag>>> {
+        self.github()?.list_tags(repo, page).await
+    }
+
+    /// Lists the releases of a GitHub repository with their notes.
+    ///
+    /// # Arguments
+    /// * `repo` - A reference to `Repository` specifying the GitHub repository.
+    /// * `page` - A reference to `PageQuery` specifying the page of releases.
+    ///
+    /// # Returns
+    /// A `Result` wrapping an `Option<Vec<Release>>`, or an error if the operation fails.
+    ///
+    pub async fn list_github_releases(
+        &self,
+        repo: &Repository,
+        page: &PageQuery,
+    ) -> anyhow::Result<Option<Vec<Release>>> {
+        self.github()?.list_releases(repo, page).await
+    }
+
+    /// Lists the commits of a GitHub repository, optionally only those touching a path.
+    ///
+    /// # Arguments
+    /// * `repo` - A reference to `Repository` specifying the GitHub repository.
+    /// * `query` - A reference to `CommitQuery` specifying the starting ref, the filters and the
+    ///   page of the log.
+    ///
+    /// # Returns
+    /// A `Result` wrapping an `Option<Vec<CommitSummary>>`, or an error if the operation fails.
+    ///
+    pub async fn list_github_commits(
+        &self,
+        repo: &Repository,
+        query: &CommitQuery,
+    ) -> anyhow::Result<Option<Vec<CommitSummary>>> {
+        self.github()?.list_commits(repo, query).await
+    }
+
+    /// Retrieves the details of a commit in a GitHub repository with a page of its diff.
+    ///
+    /// # Arguments
+    /// * `repo` - A reference to `Repository` specifying the GitHub repository.
+    /// * `reference` - A string slice specifying the commit SHA, branch or tag.
+    /// * `page` - A reference to `DiffPage` specifying the files to include.
+    ///
+    /// # Returns
+    /// A `Result` wrapping an `Option<Commit>`, or an error if the operation fails.
+    ///
+    pub async fn get_github_commit(
+        &self,
+        repo: &Repository,
+        reference: &str,
+        page: &DiffPage,
+    ) -> anyhow::Result<Option<Commit>> {
+        self.github()?.get_commit(repo, reference, page).await
+    }
+
+    /// Compares two refs of a GitHub repository.
+    ///
+    /// # Arguments
+    /// * `repo` - A reference to `Repository` specifying the GitHub repository.
+    /// * `base` - A string slice specifying the ref to compare against.
+    /// * `head` - A string slice specifying the ref to compare.
+    /// * `page` - A reference to `DiffPage` specifying the files to include.
+    ///
+    /// # Returns
+    /// A `Result` wrapping an `Option<Comparison>`, or an error if the operation fails.
+    ///
+    pub async fn compare_github_refs(
+        &self,
+        repo: &Repository,
+        base: &str,
+        head: &str,
+        page: &DiffPage,
+    ) -> anyhow::Result<Option<Comparison>> {
+        self.github()?.compare(repo, base, head, page).await
     }
 
     /// Retrieves the branches of a specified GitHub repository.
@@ -210,7 +1301,85 @@ impl RustAssistant {
     pub async fn get_github_repository_branches(
         &self,
         repo: &Repository,
+        limit: Option<NonZeroUsize>,
     ) -> anyhow::Result<Vec<String>> {
-        self.github.get_repo_branches(repo).await
+        self.github()?.get_repo_branches(repo, limit).await
+    }
+
+    /// Reads the content of a file in a repository of a forge.
+    ///
+    /// # Arguments
+    /// * `host` - A `&str` specifying the host of the forge, e.g. `codeberg.org`.
+    /// * `repo` - A reference to `Repository` specifying the repository.
+    /// * `path` - A `&str` specifying the file path.
+    /// * `branch` - An optional `&str` specifying the branch name.
+    ///
+    /// # Returns
+    /// A `Result` wrapping an `Option<FileContent>`, or an `UnknownForge` error if the host is not configured.
+    ///
+    pub async fn read_forge_repository_file(
+        &self,
+        host: &str,
+        repo: &Repository,
+        path: &str,
+        branch: Option<&str>,
+    ) -> anyhow::Result<Option<FileContent>> {
+        self.forges.get(host)?.get_file(repo, path, branch).await
+    }
+
+    /// Reads the content of a directory in a repository of a forge.
+    ///
+    /// # Arguments
+    /// * `host` - A `&str` specifying the host of the forge.
+    /// * `repo` - A reference to `Repository` specifying the repository.
+    /// * `path` - A `&str` specifying the directory path.
+    /// * `branch` - An optional `&str` specifying the branch name.
+    ///
+    /// # Returns
+    /// A `Result` wrapping an `Option<Directory>`, or an `UnknownForge` error if the host is not configured.
+    ///
+    pub async fn read_forge_repository_directory(
+        &self,
+        host: &str,
+        repo: &Repository,
+        path: &str,
+        branch: Option<&str>,
+    ) -> anyhow::Result<Option<Directory>> {
+        self.forges.get(host)?.read_dir(repo, path, branch).await
+    }
+
+    /// Retrieves the branches of a repository of a forge.
+    ///
+    pub async fn get_forge_repository_branches(
+        &self,
+        host: &str,
+        repo: &Repository,
+    ) -> anyhow::Result<Vec<String>> {
+        self.forges.get(host)?.get_repo_branches(repo).await
+    }
+
+    /// Searches for issues in a repository of a forge.
+    ///
+    pub async fn search_forge_repository_for_issues(
+        &self,
+        host: &str,
+        repo: &Repository,
+        query: &str,
+    ) -> anyhow::Result<Vec<Issue>> {
+        self.forges.get(host)?.search_for_issues(repo, query).await
+    }
+
+    /// Retrieves the timeline of an issue in a repository of a forge.
+    ///
+    pub async fn get_forge_repository_issue_timeline(
+        &self,
+        host: &str,
+        repo: &Repository,
+        issue_number: u64,
+    ) -> anyhow::Result<Vec<IssueEvent>> {
+        self.forges
+            .get(host)?
+            .get_issue_timeline(repo, issue_number)
+            .await
     }
 }
@@ -2,11 +2,33 @@
 //!
 use crate::app::RustAssistant;
 use crate::cache::{CrateCache, FileContent, FileDataType};
+use crate::cfg::InvalidTarget;
+use crate::diff::{ApiDiff, FileDiff};
 use crate::download::CrateDownloader;
-use crate::github::{GithubClient, IssueQuery, Repository, RepositoryIssue, RepositoryPath};
+use crate::forge::{
+    ForgeConfig, ForgeRegistry, ForgeRepository, ForgeRepositoryIssue, ForgeRepositoryPath,
+    UnknownForge,
+};
+use crate::github::{
+    ChangedFile, CodeMatch, CodeQuery, Commit, CommitQuery, CommitSummary, CompareRefs, Comparison,
+    DiffPage, GithubAccess, GithubDisabled, GithubStatus, IssueKind, IssueQuery, IssueSort,
+    IssueState, LimitQuery, PageQuery, PullRequest, PullRequestQuery, PullRequestState,
+    PullRequestSummary, RateLimited, Release, Repository, RepositoryFile, RepositoryIssue,
+    RepositoryPath, RepositoryRef, RepositoryTooLarge, ReviewComment, SortOrder, Tag,
+    TimelineQuery, UnifiedDiff,
+};
+use crate::manifest::InvalidManifest;
+use crate::outline::OutlineItem;
+use crate::overview::CrateOverview;
+use crate::resolve::{DependencyGraph, UnknownFeature};
+use crate::vcs::{CrateSource, SourceFileQuery, SourceRevision, SourceUnavailable};
+use crate::xref::{Definitions, References};
 use crate::{
-    Branch, CrateVersion, CrateVersionPath, Directory, FileLineRange, Issue, IssueEvent, Item,
-    ItemQuery, ItemType, Line, LineQuery, SearchMode,
+    Branch, CrateItem, CrateLine, CrateUsage, CrateVersion, CrateVersionPair, CrateVersionPairPath,
+    CrateVersionPath, DependencyQuery, DiffOptions, Directory, FileLineRange, ImplQuery,
+    ImplSearchResult, Issue, IssueComment, IssueEvent, Item, ItemQuery, ItemSearchResult, ItemType,
+    Line, LineQuery, Manifest, MultiCrateQuery, MultiCrateSearchResult, ParseError, Position,
+    SearchMode, UsageQuery,
 };
 use axum::extract::{FromRequestParts, Path, Query, State};
 use axum::http::request::Parts;
@@ -59,11 +81,15 @@ pub async fn search_crate_for_lines(
 /// Search for items in a specific crate.
 ///
 /// This function provides an API endpoint to search for various items like structs, enums,
-/// functions, etc., within a crate. It uses query parameters to filter search results.
+/// functions, etc., within a crate. It uses query parameters to filter search results. The
+/// `X-Incomplete` header is `true` if some files within the search scope failed to parse.
 ///
 #[cfg_attr(feature = "utoipa",
     utoipa::path(get, path = "/api/items/{crate}/{version}", responses(
-        (status = 200, description = "Search the crate for items successfully.", body = [Item]),
+        (status = 200, description = "Search the crate for items successfully.", body = [Item], headers(
+            ("X-Incomplete" = bool, description = "Whether some files within the search scope failed to parse, so items may be missing and line search should be used as a fallback. The files are listed by `/api/parse-errors`."),
+        )),
+        (status = 400, description = "Invalid target triple.", body = String),
         (status = 500, description = "Internal server error.", body = String),
     ),
     params(
@@ -72,6 +98,9 @@ pub async fn search_crate_for_lines(
         ("type" = ItemType, Query, description = "The type of the item."),
         ("query" = String, Query, description = "Query string."),
         ("path" = String, Query, description = "Directory containing the items to search."),
+        ("features" = Option<String>, Query, description = "A comma-separated list of enabled cargo features. Items gated behind other features are filtered out."),
+        ("no_default_features" = Option<bool>, Query, description = "Disable the `default` feature when `features` is specified."),
+        ("target" = Option<String>, Query, description = "The target triple, e.g. `x86_64-unknown-linux-gnu`. Items gated behind other targets are filtered out."),
     ),
     security(
         ("api_auth" = [])
@@ -82,156 +111,1731 @@ pub async fn search_crate_for_items(
     Query(query): Query<ItemQuery>,
     State(state): State<RustAssistant>,
 ) -> impl IntoResponse {
-    match state.search_item(&crate_version, query).await {
-        Ok(items) => Json(items).into_response(),
-        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+    match state.search_item(&crate_version, query).await {
+        Ok(result) => result.into_response(),
+        Err(error) if error.is::<InvalidTarget>() => {
+            (StatusCode::BAD_REQUEST, error.to_string()).into_response()
+        }
+        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+    }
+}
+
+/// Find the implementors of a trait in a specific crate.
+///
+/// This endpoint lists the impls of a trait, matched by name or by trailing path segments, including the
+/// impls generated by `#[derive(...)]`. Blanket impls of the trait are listed separately.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/implementors/{crate}/{version}", responses(
+        (status = 200, description = "Search the impls successfully.", body = ImplSearchResult),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+    params(
+        ("crate" = String, Path, description = "The exact name of the crate."),
+        ("version" = String, Path, description = "The semantic version number of the crate, following the Semantic versioning specification."),
+        ("name" = String, Query, description = "The name or path of the trait, e.g. `AsyncRead` or `io::Read`."),
+        ("path" = Option<String>, Query, description = "Directory containing the impls to search."),
+    ),
+    security(
+        ("api_auth" = [])
+    )
+))]
+pub async fn get_trait_implementors(
+    Path(crate_version): Path<CrateVersion>,
+    Query(query): Query<ImplQuery>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state.implementors(&crate_version, query).await {
+        Ok(result) => Json(result).into_response(),
+        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+    }
+}
+
+/// Find the traits implemented by a type in a specific crate.
+///
+/// This endpoint lists the trait impls of a type, including the ones generated by `#[derive(...)]`, and the
+/// blanket impls whose bounds the type satisfies within the crate.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/implemented-traits/{crate}/{version}", responses(
+        (status = 200, description = "Search the impls successfully.", body = ImplSearchResult),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+    params(
+        ("crate" = String, Path, description = "The exact name of the crate."),
+        ("version" = String, Path, description = "The semantic version number of the crate, following the Semantic versioning specification."),
+        ("name" = String, Query, description = "The name or path of the type, e.g. `Bytes`. Generic arguments are ignored."),
+        ("path" = Option<String>, Query, description = "Directory containing the impls to search."),
+    ),
+    security(
+        ("api_auth" = [])
+    )
+))]
+pub async fn get_implemented_traits(
+    Path(crate_version): Path<CrateVersion>,
+    Query(query): Query<ImplQuery>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state.implemented_traits(&crate_version, query).await {
+        Ok(result) => Json(result).into_response(),
+        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+    }
+}
+
+/// Search for items across multiple crates.
+///
+/// This endpoint searches a list of crates, or a root crate and its dependency tree, for items
+/// concurrently, and returns the merged results tagged by crate.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/multi/items", responses(
+        (status = 200, description = "Search the crates for items successfully.", body = MultiCrateSearchResult<CrateItem>),
+        (status = 400, description = "Invalid crate list.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+    params(
+        ("crates" = Option<String>, Query, description = "A comma-separated list of crates in `name@version` form."),
+        ("root" = Option<String>, Query, description = "The root crate in `name@version` form, whose dependency tree is searched."),
+        ("depth" = Option<usize>, Query, description = "The maximum dependency depth of the root crate, 1 by default."),
+        ("limit" = Option<usize>, Query, description = "The maximum number of results across all crates."),
+        ("timeout_ms" = Option<u64>, Query, description = "The time budget of the search in milliseconds."),
+        ("type" = ItemType, Query, description = "The type of the item."),
+        ("query" = String, Query, description = "Query string."),
+        ("path" = Option<String>, Query, description = "Directory containing the items to search."),
+        ("features" = Option<String>, Query, description = "A comma-separated list of enabled cargo features. Items gated behind other features are filtered out."),
+        ("no_default_features" = Option<bool>, Query, description = "Disable the `default` feature when `features` is specified."),
+        ("target" = Option<String>, Query, description = "The target triple, e.g. `x86_64-unknown-linux-gnu`. Items gated behind other targets are filtered out."),
+    ),
+    security(
+        ("api_auth" = [])
+    )
+))]
+pub async fn search_crates_for_items(
+    Query(multi): Query<MultiCrateQuery>,
+    Query(query): Query<ItemQuery>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    let crate_set = match multi.crate_set() {
+        Ok(crate_set) => crate_set,
+        Err(error) => return (StatusCode::BAD_REQUEST, error.to_string()).into_response(),
+    };
+    match state
+        .search_items_in_crates(&crate_set, query, multi.budget())
+        .await
+    {
+        Ok(result) => Json(result).into_response(),
+        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+    }
+}
+
+/// Search for lines across multiple crates.
+///
+/// This endpoint runs a full-text search on a list of crates, or a root crate and its dependency
+/// tree, concurrently, and returns the merged results tagged by crate.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/multi/lines", responses(
+        (status = 200, description = "Search the crates for lines successfully.", body = MultiCrateSearchResult<CrateLine>),
+        (status = 400, description = "Invalid crate list.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+    params(
+        ("crates" = Option<String>, Query, description = "A comma-separated list of crates in `name@version` form."),
+        ("root" = Option<String>, Query, description = "The root crate in `name@version` form, whose dependency tree is searched."),
+        ("depth" = Option<usize>, Query, description = "The maximum dependency depth of the root crate, 1 by default."),
+        ("limit" = Option<usize>, Query, description = "The maximum number of results across all crates."),
+        ("timeout_ms" = Option<u64>, Query, description = "The time budget of the search in milliseconds."),
+        ("query" = String, Query, description = "Query string."),
+        ("mode" = SearchMode, Query, description = "Search mode."),
+        ("case_sensitive" = Option<bool>, Query, description = "Case sensitive."),
+        ("whole_word" = Option<bool>, Query, description = "Whole word."),
+        ("max_results" = Option<usize>, Query, description = "Max results count per crate."),
+        ("file_ext" = Option<String>, Query, description = "The extensions of files to search."),
+        ("path" = Option<String>, Query, description = "Directory containing the lines to search."),
+    ),
+    security(
+        ("api_auth" = [])
+    )
+))]
+pub async fn search_crates_for_lines(
+    Query(multi): Query<MultiCrateQuery>,
+    Query(query): Query<LineQuery>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    let crate_set = match multi.crate_set() {
+        Ok(crate_set) => crate_set,
+        Err(error) => return (StatusCode::BAD_REQUEST, error.to_string()).into_response(),
+    };
+    match state
+        .search_lines_in_crates(&crate_set, query, multi.budget())
+        .await
+    {
+        Ok(result) => Json(result).into_response(),
+        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+    }
+}
+
+/// Find the usages of an item across dependent crates.
+///
+/// This endpoint scans a list of crates depending on a crate, or a root crate and its dependency tree,
+/// for references to one of its items. `use` declarations are resolved, including renamed and glob
+/// imports, and every call site is returned with its file, line and source snippet.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/usages/{crate}/{version}", responses(
+        (status = 200, description = "Find the usages of the item successfully.", body = MultiCrateSearchResult<CrateUsage>),
+        (status = 400, description = "Invalid crate list.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+    params(
+        ("crate" = String, Path, description = "The exact name of the crate defining the item."),
+        ("version" = String, Path, description = "The semantic version number of the crate, following the Semantic versioning specification."),
+        ("path" = String, Query, description = "The path of the item within its crate, e.g. `de::Deserialize` or `Bytes::new`."),
+        ("crates" = Option<String>, Query, description = "A comma-separated list of dependent crates in `name@version` form."),
+        ("root" = Option<String>, Query, description = "The root crate in `name@version` form, whose dependency tree is searched."),
+        ("depth" = Option<usize>, Query, description = "The maximum dependency depth of the root crate, 1 by default."),
+        ("limit" = Option<usize>, Query, description = "The maximum number of results across all crates."),
+        ("timeout_ms" = Option<u64>, Query, description = "The time budget of the search in milliseconds."),
+    ),
+    security(
+        ("api_auth" = [])
+    )
+))]
+pub async fn find_item_usages(
+    Path(crate_version): Path<CrateVersion>,
+    Query(multi): Query<MultiCrateQuery>,
+    Query(query): Query<UsageQuery>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    let crate_set = match multi.crate_set() {
+        Ok(crate_set) => crate_set,
+        Err(error) => return (StatusCode::BAD_REQUEST, error.to_string()).into_response(),
+    };
+    match state
+        .find_usages_in_crates(&crate_version, &query.path, &crate_set, multi.budget())
+        .await
+    {
+        Ok(result) => Json(result).into_response(),
+        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+    }
+}
+
+/// Get the content of a file in a crate.
+///
+/// This function serves an endpoint to retrieve the content of a specific file from a crate,
+/// potentially within a specified range of lines.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/file/{crate}/{version}/{path}", responses(
+        (status = 200, description = "Read the file successfully.", body = String),
+        (status = 404, description = "The file does not exist."),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+    params(
+        ("crate" = String, Path, description = "The exact name of the crate."),
+        ("version" = String, Path, description = "The semantic version number of the crate, following the Semantic versioning specification."),
+        ("path" = String, Path, description = "Relative path of a file in crate."),
+        ("start" = usize, Path, description = "Start line number of the file (inclusive)."),
+        ("end" = usize, Path, description = "End line number of the file (inclusive)."),
+    ),
+    security(
+    ("api_auth" = [])
+    )
+))]
+pub async fn get_file_content(
+    Path(path): Path<CrateVersionPath>,
+    Query(range): Query<FileLineRange>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state.get_file_content(&path, range).await {
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Some(file)) => file.into_response(),
+        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+    }
+}
+
+/// Get the outline of a Rust source file in a crate.
+///
+/// This endpoint returns the symbol tree of a file: its modules, types, impls with their methods,
+/// traits with their items, functions and macros, each with its line range, visibility and the
+/// first line of its doc comment. The line ranges can be used to read only part of the file.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/outline/{crate}/{version}/{path}", responses(
+        (status = 200, description = "Build the outline successfully.", body = [OutlineItem]),
+        (status = 404, description = "The file does not exist."),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+    params(
+        ("crate" = String, Path, description = "The exact name of the crate."),
+        ("version" = String, Path, description = "The semantic version number of the crate, following the Semantic versioning specification."),
+        ("path" = String, Path, description = "Relative path of a Rust source file in crate."),
+    ),
+    security(
+        ("api_auth" = [])
+    )
+))]
+pub async fn get_file_outline(
+    Path(path): Path<CrateVersionPath>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state.get_file_outline(&path).await {
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Some(outline)) => Json(outline).into_response(),
+        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+    }
+}
+
+/// Go to the definition of the symbol at a position in a file of a crate.
+///
+/// This endpoint resolves the path segment at a line and column through the module tree, the `use`
+/// declarations in scope and the item index, following into dependency crates when the path
+/// leaves the crate.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/definition/{crate}/{version}/{path}", responses(
+        (status = 200, description = "Resolve the symbol successfully.", body = Definitions),
+        (status = 404, description = "There is no symbol at the position."),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+    params(
+        ("crate" = String, Path, description = "The exact name of the crate."),
+        ("version" = String, Path, description = "The semantic version number of the crate, following the Semantic versioning specification."),
+        ("path" = String, Path, description = "Relative path of a file in crate."),
+        ("line" = usize, Query, description = "The line number of the symbol, starting at 1."),
+        ("column" = usize, Query, description = "The column number of the symbol, starting at 1."),
+    ),
+    security(
+        ("api_auth" = [])
+    )
+))]
+pub async fn goto_definition(
+    Path(path): Path<CrateVersionPath>,
+    Query(position): Query<Position>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state
+        .goto_definition(&path.crate_version, &path.path, position)
+        .await
+    {
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Some(definitions)) => Json(definitions).into_response(),
+        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+    }
+}
+
+/// Find the references to an item within its crate.
+///
+/// This endpoint lists every place in the crate referring to an item, including its library,
+/// binaries, tests and examples, with the file, line and source snippet of each reference.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/references/{crate}/{version}", responses(
+        (status = 200, description = "Find the references successfully.", body = References),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+    params(
+        ("crate" = String, Path, description = "The exact name of the crate."),
+        ("version" = String, Path, description = "The semantic version number of the crate, following the Semantic versioning specification."),
+        ("path" = String, Query, description = "The path of the item within the crate, e.g. `de::Deserialize` or `Bytes::new`."),
+    ),
+    security(
+        ("api_auth" = [])
+    )
+))]
+pub async fn find_item_references(
+    Path(crate_version): Path<CrateVersion>,
+    Query(query): Query<UsageQuery>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state.find_references(&crate_version, &query.path).await {
+        Ok(references) => Json(references).into_response(),
+        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+    }
+}
+
+/// Read a subdirectory in a crate.
+///
+/// This endpoint provides access to the contents of a subdirectory within a crate,
+/// including files and other subdirectories.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/directory/{crate}/{version}/{path}", responses(
+        (status = 200, description = "Read the subdirectory successfully.", body = Directory),
+        (status = 404, description = "The directory does not exist."),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+    params(
+        ("crate" = String, Path, description = "The exact name of the crate."),
+        ("version" = String, Path, description = "The semantic version number of the crate, following the Semantic versioning specification."),
+        ("path" = String, Path, description = "Relative path of a directory in crate."),
+    ),
+    security(
+        ("api_auth" = [])
+    )
+))]
+pub async fn read_crate_directory(
+    Path(path): Path<CrateVersionPath>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state.read_directory(path).await {
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Some(directory)) => Json(directory).into_response(),
+        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+    }
+}
+
+/// Read crate root directory.
+#[cfg_attr(feature = "utoipa", 
+    utoipa::path(get, path = "/api/directory/{crate}/{version}", responses(
+        (status = 200, description = "Read crate root directory successfully.", body = Directory),
+        (status = 404, description = "The root directory does not exist."),
+        (status = 500, description = "Internal server error."),
+    ),
+    params(
+        ("crate" = String, Path, description = "The exact name of the crate."),
+        ("version" = String, Path, description = "The semantic version number of the crate, following the Semantic versioning specification."),
+    ),
+    security(
+        ("api_auth" = [])
+    )
+))]
+pub async fn read_crate_root_directory(
+    Path(crate_version): Path<CrateVersion>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state
+        .read_directory(CrateVersionPath {
+            crate_version,
+            path: "".into(),
+        })
+        .await
+    {
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Some(directory)) => Json(directory).into_response(),
+        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+    }
+}
+
+/// Get the parsed `Cargo.toml` manifest of a crate.
+///
+/// This endpoint returns the package metadata, features, dependencies and build targets of a crate
+/// as typed JSON, so the manifest does not need to be read and interpreted as a file.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/manifest/{crate}/{version}", responses(
+        (status = 200, description = "Get the crate manifest successfully.", body = Manifest),
+        (status = 404, description = "The crate has no manifest."),
+        (status = 422, description = "The manifest could not be parsed.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+    params(
+        ("crate" = String, Path, description = "The exact name of the crate."),
+        ("version" = String, Path, description = "The semantic version number of the crate, following the Semantic versioning specification."),
+    ),
+    security(
+        ("api_auth" = [])
+    )
+))]
+pub async fn get_crate_manifest(
+    Path(crate_version): Path<CrateVersion>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state.get_manifest(&crate_version).await {
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Some(manifest)) => Json(manifest).into_response(),
+        Err(error) if error.is::<InvalidManifest>() => {
+            (StatusCode::UNPROCESSABLE_ENTITY, error.to_string()).into_response()
+        }
+        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+    }
+}
+
+/// Get the files of a crate that failed to parse.
+///
+/// This endpoint lists the Rust source files which are missing from the item index because they
+/// failed to parse, with the error message and its span. Item searches covering them report
+/// `X-Incomplete: true`.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/parse-errors/{crate}/{version}", responses(
+        (status = 200, description = "Get the parse errors successfully.", body = [ParseError]),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+    params(
+        ("crate" = String, Path, description = "The exact name of the crate."),
+        ("version" = String, Path, description = "The semantic version number of the crate, following the Semantic versioning specification."),
+    ),
+    security(
+        ("api_auth" = [])
+    )
+))]
+pub async fn get_crate_parse_errors(
+    Path(crate_version): Path<CrateVersion>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state.get_parse_errors(&crate_version).await {
+        Ok(errors) => Json(errors).into_response(),
+        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+    }
+}
+
+/// Get an overview of a crate.
+///
+/// This endpoint summarizes a crate in a single response: the package metadata, features and
+/// dependency counts from the manifest, the crate-level `//!` docs, the public modules and
+/// re-exports of the library root, the number of items of each type, the lines of code per
+/// directory, the number of examples, tests and benches, and whether the crate has a build script
+/// or is a proc-macro.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/overview/{crate}/{version}", responses(
+        (status = 200, description = "Get the crate overview successfully.", body = CrateOverview),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+    params(
+        ("crate" = String, Path, description = "The exact name of the crate."),
+        ("version" = String, Path, description = "The semantic version number of the crate, following the Semantic versioning specification."),
+    ),
+    security(
+        ("api_auth" = [])
+    )
+))]
+pub async fn get_crate_overview(
+    Path(crate_version): Path<CrateVersion>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state.get_crate_overview(&crate_version).await {
+        Ok(overview) => Json(overview).into_response(),
+        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+    }
+}
+
+/// Get the source repository of a crate version.
+///
+/// This endpoint returns the `repository` URL of the manifest, the GitHub repository it points
+/// to, and the commit and directory the version was published from, as recorded by Cargo in
+/// `.cargo_vcs_info.json`. Crates published from a dirty working tree are flagged, as their files
+/// may differ from those of the commit.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/source/{crate}/{version}", responses(
+        (status = 200, description = "Get the crate source successfully.", body = CrateSource),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+    params(
+        ("crate" = String, Path, description = "The exact name of the crate."),
+        ("version" = String, Path, description = "The semantic version number of the crate, following the Semantic versioning specification."),
+    ),
+    security(
+        ("api_auth" = [])
+    )
+))]
+pub async fn get_crate_source(
+    Path(crate_version): Path<CrateVersion>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state.get_crate_source(&crate_version).await {
+        Ok(source) => Json(source).into_response(),
+        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+    }
+}
+
+/// Read a file of a crate from its GitHub repository.
+///
+/// This endpoint maps the path of a file of the published crate to its path in the GitHub
+/// repository, and reads it at the commit the version was published from, or at the head of the
+/// default branch to see what changed since. Compare the result with `/api/file` to tell the
+/// published code from the unpublished one.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/source/{crate}/{version}/{path}", responses(
+        (status = 200, description = "Read the file successfully.", body = String),
+        (status = 404, description = "The file does not exist, the repository is not on GitHub or the version does not record its commit.", body = String),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
+        (status = 503, description = "GitHub access is disabled.", body = String),
+    ),
+    params(
+        ("crate" = String, Path, description = "The exact name of the crate."),
+        ("version" = String, Path, description = "The semantic version number of the crate, following the Semantic versioning specification."),
+        ("path" = String, Path, description = "Relative path of a file in crate."),
+        ("at" = Option<SourceRevision>, Query, description = "The revision to read, `commit` (the default) or `head`."),
+        ("start" = Option<usize>, Query, description = "Start line number of the file (inclusive)."),
+        ("end" = Option<usize>, Query, description = "End line number of the file (inclusive)."),
+    ),
+    security(
+        ("api_auth" = [])
+    )
+))]
+pub async fn read_crate_source_file(
+    Path(path): Path<CrateVersionPath>,
+    Query(query): Query<SourceFileQuery>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state
+        .read_crate_source_file(&path, query.at, query.line_range())
+        .await
+    {
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Some(file)) => file.into_response(),
+        Err(error) if error.is::<SourceUnavailable>() => {
+            (StatusCode::NOT_FOUND, error.to_string()).into_response()
+        }
+        Err(error) => github_error_response(error),
+    }
+}
+
+/// Compare the public APIs of two versions of a crate.
+///
+/// This endpoint lists the items added, removed, changed or moved between the two versions, classifies
+/// each change as breaking or not, and checks whether the version bump satisfies the semver rules.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/api-diff/{crate}/{old}/{new}", responses(
+        (status = 200, description = "Compare the public APIs successfully.", body = ApiDiff),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+    params(
+        ("crate" = String, Path, description = "The exact name of the crate."),
+        ("old" = String, Path, description = "The old version of the crate."),
+        ("new" = String, Path, description = "The new version of the crate."),
+    ),
+    security(
+        ("api_auth" = [])
+    )
+))]
+pub async fn diff_crate_api(
+    Path(versions): Path<CrateVersionPair>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state.diff_api(&versions).await {
+        Ok(diff) => Json(diff).into_response(),
+        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+    }
+}
+
+/// Get the unified diff of a file between two versions of a crate.
+///
+/// A file that only exists in one of the versions is compared against an empty file.
+/// The response is empty if the file is identical in both versions.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/file-diff/{crate}/{old}/{new}/{path}", responses(
+        (status = 200, description = "Diff the file successfully.", body = String),
+        (status = 404, description = "The file exists in neither version."),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+    params(
+        ("crate" = String, Path, description = "The exact name of the crate."),
+        ("old" = String, Path, description = "The old version of the crate."),
+        ("new" = String, Path, description = "The new version of the crate."),
+        ("path" = String, Path, description = "Relative path of a file in crate."),
+        ("context" = Option<usize>, Query, description = "The number of unchanged lines shown around each change, 3 by default."),
+    ),
+    security(
+        ("api_auth" = [])
+    )
+))]
+pub async fn diff_crate_file(
+    Path(path): Path<CrateVersionPairPath>,
+    Query(options): Query<DiffOptions>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state.diff_file(&path, options).await {
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Some(diff)) => diff.into_response(),
+        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+    }
+}
+
+/// List the files changed in a subdirectory between two versions of a crate.
+///
+/// Identical files are detected by their lengths and hashes, so large crates are compared quickly.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/directory-diff/{crate}/{old}/{new}/{path}", responses(
+        (status = 200, description = "Diff the subdirectory successfully.", body = FileDiff),
+        (status = 404, description = "The directory exists in neither version."),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+    params(
+        ("crate" = String, Path, description = "The exact name of the crate."),
+        ("old" = String, Path, description = "The old version of the crate."),
+        ("new" = String, Path, description = "The new version of the crate."),
+        ("path" = String, Path, description = "Relative path of a directory in crate."),
+    ),
+    security(
+        ("api_auth" = [])
+    )
+))]
+pub async fn diff_crate_directory(
+    Path(path): Path<CrateVersionPairPath>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state.diff_files(&path.versions, Some(&path.path)).await {
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Some(diff)) => Json(diff).into_response(),
+        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+    }
+}
+
+/// List the files changed between two versions of a crate.
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/directory-diff/{crate}/{old}/{new}", responses(
+        (status = 200, description = "Diff the crate files successfully.", body = FileDiff),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+    params(
+        ("crate" = String, Path, description = "The exact name of the crate."),
+        ("old" = String, Path, description = "The old version of the crate."),
+        ("new" = String, Path, description = "The new version of the crate."),
+    ),
+    security(
+        ("api_auth" = [])
+    )
+))]
+pub async fn diff_crate_root_directory(
+    Path(versions): Path<CrateVersionPair>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state.diff_files(&versions, None).await {
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Some(diff)) => Json(diff).into_response(),
+        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+    }
+}
+
+/// Resolve the transitive dependency graph of a crate.
+///
+/// This endpoint resolves every crate pulled in by a crate version from the registry index, with features
+/// unified across the graph like Cargo does, and returns the graph as nodes and edges.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/dependencies/{crate}/{version}", responses(
+        (status = 200, description = "Resolve the dependency graph successfully.", body = DependencyGraph),
+        (status = 400, description = "Invalid target triple or unknown feature.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+    params(
+        ("crate" = String, Path, description = "The exact name of the crate."),
+        ("version" = String, Path, description = "The semantic version number of the crate, following the Semantic versioning specification."),
+        ("features" = Option<String>, Query, description = "A comma-separated list of features enabled on the crate."),
+        ("no_default_features" = Option<bool>, Query, description = "Whether the default features are disabled."),
+        ("all_features" = Option<bool>, Query, description = "Whether all features are enabled."),
+        ("target" = Option<String>, Query, description = "The target triple, e.g. \"x86_64-unknown-linux-gnu\". Dependencies of all targets are included if not specified."),
+        ("dev" = Option<bool>, Query, description = "Whether the dev-dependencies of the crate are included."),
+    ),
+    security(
+        ("api_auth" = [])
+    )
+))]
+pub async fn get_crate_dependencies(
+    Path(crate_version): Path<CrateVersion>,
+    Query(query): Query<DependencyQuery>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state.resolve_dependencies(&crate_version, query).await {
+        Ok(graph) => Json(graph).into_response(),
+        Err(error) => dependency_error_response(error),
+    }
+}
+
+/// Converts an error of a dependency resolution into a response, with 400 for an invalid target
+/// triple or an unknown feature.
+fn dependency_error_response(error: anyhow::Error) -> Response {
+    if error.is::<InvalidTarget>() || error.is::<UnknownFeature>() {
+        (StatusCode::BAD_REQUEST, error.to_string()).into_response()
+    } else {
+        (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response()
+    }
+}
+
+/// Render the dependency tree of a crate like `cargo tree`.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/dependencies/{crate}/{version}/tree", responses(
+        (status = 200, description = "Render the dependency tree successfully.", body = String),
+        (status = 400, description = "Invalid target triple or unknown feature.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+    params(
+        ("crate" = String, Path, description = "The exact name of the crate."),
+        ("version" = String, Path, description = "The semantic version number of the crate, following the Semantic versioning specification."),
+        ("features" = Option<String>, Query, description = "A comma-separated list of features enabled on the crate."),
+        ("no_default_features" = Option<bool>, Query, description = "Whether the default features are disabled."),
+        ("all_features" = Option<bool>, Query, description = "Whether all features are enabled."),
+        ("target" = Option<String>, Query, description = "The target triple, e.g. \"x86_64-unknown-linux-gnu\". Dependencies of all targets are included if not specified."),
+        ("dev" = Option<bool>, Query, description = "Whether the dev-dependencies of the crate are included."),
+    ),
+    security(
+        ("api_auth" = [])
+    )
+))]
+pub async fn get_crate_dependency_tree(
+    Path(crate_version): Path<CrateVersion>,
+    Query(query): Query<DependencyQuery>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state.resolve_dependencies(&crate_version, query).await {
+        Ok(graph) => graph.to_tree().into_response(),
+        Err(error) => dependency_error_response(error),
+    }
+}
+
+/// Read the root directory of a GitHub repository.
+///
+/// This endpoint provides access to the contents of the root directory within a GitHub repository,
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/github/directory/{owner}/{repo}", responses(
+        (status = 200, description = "Read repository root directory successfully.", body = Directory),
+        (status = 404, description = "The root directory does not exist."),
+        (status = 500, description = "Internal server error."),
+    ),
+        params(
+            ("owner" = String, Path, description = "The owner of the GitHub repository."),
+            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("branch" = Option<String>, Query, description = "The branch name."),
+        ),
+        security(
+            ("api_auth" = [])
+        )
+    ))]
+pub async fn read_github_repository_root_directory(
+    Path(repository): Path<Repository>,
+    Query(branch): Query<Branch>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state
+        .read_github_repository_directory(&repository, "", branch.as_str())
+        .await
+    {
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Some(directory)) => Json(directory).into_response(),
+        Err(error) => github_error_response(error),
+    }
+}
+
+/// Read a subdirectory in a GitHub repository.
+///
+/// This endpoint provides access to the contents of a subdirectory within a GitHub repository,
+/// including files and other subdirectories.
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/github/directory/{owner}/{repo}/{path}", responses(
+        (status = 200, description = "Read the subdirectory successfully.", body = Directory),
+        (status = 404, description = "The directory does not exist."),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+        params(
+            ("owner" = String, Path, description = "The owner of the GitHub repository."),
+            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("path" = String, Path, description = "Relative path of a directory in repository."),
+            ("branch" = Option<String>, Query, description = "The branch name."),
+        ),
+        security(
+            ("api_auth" = [])
+        )
+    ))]
+pub async fn read_github_repository_directory(
+    Path(repository_path): Path<RepositoryPath>,
+    Query(branch): Query<Branch>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state
+        .read_github_repository_directory(
+            &repository_path.repo,
+            repository_path.path.as_ref(),
+            branch.as_str(),
+        )
+        .await
+    {
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Some(directory)) => Json(directory).into_response(),
+        Err(error) => github_error_response(error),
+    }
+}
+
+/// Read the content of a file in a GitHub repository.
+///
+/// This function serves an endpoint to retrieve the content of a specific file from a GitHub repository,
+/// potentially within a specified range of lines. The SHA of the Git blob of the whole file is
+/// returned in the `X-Blob-Sha` header.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/github/file/{owner}/{repo}/{path}", responses(
+        (status = 200, description = "Read the file successfully.", body = String, headers(
+            ("X-Blob-Sha" = String, description = "The SHA of the Git blob of the file, also sent as the `ETag`."),
+        )),
+        (status = 404, description = "The file does not exist."),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+        params(
+            ("owner" = String, Path, description = "The owner of the GitHub repository."),
+            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("path" = String, Path, description = "Relative path of a file in repository."),
+            ("branch" = Option<String>, Query, description = "The branch name."),
+            ("start" = Option<usize>, Query, description = "Start line number of the file (inclusive)."),
+            ("end" = Option<usize>, Query, description = "End line number of the file (inclusive)."),
+        ),
+        security(
+            ("api_auth" = [])
+        )
+    ))]
+pub async fn read_github_repository_file_content(
+    Path(repository_path): Path<RepositoryPath>,
+    Query(branch): Query<Branch>,
+    Query(range): Query<FileLineRange>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state
+        .read_github_repository_file(
+            &repository_path.repo,
+            repository_path.path.as_ref(),
+            branch.as_str(),
+            range,
+        )
+        .await
+    {
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Some(file)) => file.into_response(),
+        Err(error) => github_error_response(error),
+    }
+}
+
+/// Search for items in a GitHub repository.
+///
+/// This endpoint downloads the repository tarball at a branch, tag or commit and indexes it like
+/// a crate, so unpublished code can be searched for structs, enums, traits and other items. The
+/// index is cached by commit, and the `X-Incomplete` header is `true` if some files within the
+/// search scope failed to parse.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/github/items/{owner}/{repo}", responses(
+        (status = 200, description = "Search the repository for items successfully.", body = [Item], headers(
+            ("X-Incomplete" = bool, description = "Whether some files within the search scope failed to parse, so items may be missing and line search should be used as a fallback."),
+        )),
+        (status = 400, description = "Invalid target triple.", body = String),
+        (status = 404, description = "The repository or the branch does not exist."),
+        (status = 422, description = "The repository is too large to index.", body = String),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+        params(
+            ("owner" = String, Path, description = "The owner of the GitHub repository."),
+            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("branch" = Option<String>, Query, description = "The branch, tag or commit, the default branch if not specified."),
+            ("type" = ItemType, Query, description = "The type of the item."),
+            ("query" = String, Query, description = "Query string."),
+            ("path" = String, Query, description = "Directory containing the items to search."),
+            ("features" = Option<String>, Query, description = "A comma-separated list of enabled cargo features. Items gated behind other features are filtered out."),
+            ("no_default_features" = Option<bool>, Query, description = "Disable the `default` feature when `features` is specified."),
+            ("target" = Option<String>, Query, description = "The target triple, e.g. `x86_64-unknown-linux-gnu`. Items gated behind other targets are filtered out."),
+        ),
+        security(
+            ("api_auth" = [])
+        )
+    ))]
+pub async fn search_github_repository_for_items(
+    Path(repository): Path<Repository>,
+    Query(branch): Query<Branch>,
+    Query(query): Query<ItemQuery>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state
+        .search_github_repository_for_items(&repository, branch.as_str(), query)
+        .await
+    {
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Some(result)) => result.into_response(),
+        Err(error) => github_error_response(error),
+    }
+}
+
+/// Search for lines in a GitHub repository.
+///
+/// This endpoint searches the files of the repository tarball at a branch, tag or commit, with
+/// the same options as the crate line search.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/github/lines/{owner}/{repo}", responses(
+        (status = 200, description = "Search the repository for lines successfully.", body = [Line]),
+        (status = 404, description = "The repository or the branch does not exist."),
+        (status = 422, description = "The repository is too large to index.", body = String),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+        params(
+            ("owner" = String, Path, description = "The owner of the GitHub repository."),
+            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("branch" = Option<String>, Query, description = "The branch, tag or commit, the default branch if not specified."),
+            ("query" = String, Query, description = "Query string."),
+            ("mode" = SearchMode, Query, description = "Search mode."),
+            ("case_sensitive" = Option<bool>, Query, description = "Case sensitive."),
+            ("whole_word" = Option<bool>, Query, description = "Whole word."),
+            ("max_results" = Option<usize>, Query, description = "Max results count."),
+            ("file_ext" = Option<usize>, Query, description = "The extensions of files to search."),
+            ("path" = Option<String>, Query, description = "Directory containing the lines to search."),
+        ),
+        security(
+            ("api_auth" = [])
+        )
+    ))]
+pub async fn search_github_repository_for_lines(
+    Path(repository): Path<Repository>,
+    Query(branch): Query<Branch>,
+    Query(query): Query<LineQuery>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state
+        .search_github_repository_for_lines(&repository, branch.as_str(), query)
+        .await
+    {
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Some(lines)) => Json(lines).into_response(),
+        Err(error) => github_error_response(error),
+    }
+}
+
+/// Get the outline of a Rust source file in a GitHub repository.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/github/outline/{owner}/{repo}/{path}", responses(
+        (status = 200, description = "Build the outline successfully.", body = [OutlineItem]),
+        (status = 404, description = "The repository, the branch or the file does not exist."),
+        (status = 422, description = "The repository is too large to index.", body = String),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+        params(
+            ("owner" = String, Path, description = "The owner of the GitHub repository."),
+            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("path" = String, Path, description = "Relative path of a Rust source file in repository."),
+            ("branch" = Option<String>, Query, description = "The branch, tag or commit, the default branch if not specified."),
+        ),
+        security(
+            ("api_auth" = [])
+        )
+    ))]
+pub async fn get_github_repository_file_outline(
+    Path(repository_path): Path<RepositoryPath>,
+    Query(branch): Query<Branch>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state
+        .get_github_repository_file_outline(
+            &repository_path.repo,
+            repository_path.path.as_ref(),
+            branch.as_str(),
+        )
+        .await
+    {
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Some(outline)) => Json(outline).into_response(),
+        Err(error) => github_error_response(error),
+    }
+}
+
+/// Read the root directory of a GitHub repository from its tarball.
+///
+/// Unlike `/api/github/directory`, this endpoint reads the cached repository tarball, so browsing
+/// a repository after searching it does not cost further API requests.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/github/tree/{owner}/{repo}", responses(
+        (status = 200, description = "Read repository root directory successfully.", body = Directory),
+        (status = 404, description = "The repository or the branch does not exist."),
+        (status = 422, description = "The repository is too large to index.", body = String),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+        params(
+            ("owner" = String, Path, description = "The owner of the GitHub repository."),
+            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("branch" = Option<String>, Query, description = "The branch, tag or commit, the default branch if not specified."),
+        ),
+        security(
+            ("api_auth" = [])
+        )
+    ))]
+pub async fn read_github_repository_root_tree(
+    Path(repository): Path<Repository>,
+    Query(branch): Query<Branch>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state
+        .read_github_repository_tree(&repository, "", branch.as_str())
+        .await
+    {
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Some(directory)) => Json(directory).into_response(),
+        Err(error) => github_error_response(error),
+    }
+}
+
+/// Read a subdirectory of a GitHub repository from its tarball.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/github/tree/{owner}/{repo}/{path}", responses(
+        (status = 200, description = "Read the subdirectory successfully.", body = Directory),
+        (status = 404, description = "The repository, the branch or the directory does not exist."),
+        (status = 422, description = "The repository is too large to index.", body = String),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+        params(
+            ("owner" = String, Path, description = "The owner of the GitHub repository."),
+            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("path" = String, Path, description = "Relative path of a directory in repository."),
+            ("branch" = Option<String>, Query, description = "The branch, tag or commit, the default branch if not specified."),
+        ),
+        security(
+            ("api_auth" = [])
+        )
+    ))]
+pub async fn read_github_repository_tree(
+    Path(repository_path): Path<RepositoryPath>,
+    Query(branch): Query<Branch>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state
+        .read_github_repository_tree(
+            &repository_path.repo,
+            repository_path.path.as_ref(),
+            branch.as_str(),
+        )
+        .await
+    {
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Some(directory)) => Json(directory).into_response(),
+        Err(error) => github_error_response(error),
+    }
+}
+
+/// Search for issues in a GitHub repository.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/github/issue/{owner}/{repo}", responses(
+        (status = 200, description = "Get issue list successfully.", body = [Issue]),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+        params(
+            ("owner" = String, Path, description = "The owner of the GitHub repository."),
+            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("query" = Option<String>, Query, description = "The free text to search for, which may also contain search qualifiers."),
+            ("state" = Option<IssueState>, Query, description = "Only `open` or only `closed` issues."),
+            ("labels" = Option<String>, Query, description = "Comma-separated names of labels the issues must all have."),
+            ("author" = Option<String>, Query, description = "The login of the author of the issues."),
+            ("assignee" = Option<String>, Query, description = "The login of a user assigned to the issues."),
+            ("type" = Option<IssueKind>, Query, description = "Only `issue`s or only `pr`s."),
+            ("created_since" = Option<String>, Query, description = "Only issues created on or after this date, e.g. `2024-01-31`."),
+            ("created_until" = Option<String>, Query, description = "Only issues created on or before this date."),
+            ("updated_since" = Option<String>, Query, description = "Only issues updated on or after this date."),
+            ("updated_until" = Option<String>, Query, description = "Only issues updated on or before this date."),
+            ("sort" = Option<IssueSort>, Query, description = "The sort field, best match by default."),
+            ("order" = Option<SortOrder>, Query, description = "The sort order, `desc` by default."),
+            ("limit" = Option<usize>, Query, description = "The maximum number of issues, 30 by default."),
+        ),
+        security(
+            ("api_auth" = [])
+        )
+    ))]
+pub async fn search_github_repository_for_issues(
+    Path(repository): Path<Repository>,
+    Query(query): Query<IssueQuery>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state
+        .search_github_repository_for_issues(&repository, &query)
+        .await
+    {
+        Ok(issues) => Json(issues).into_response(),
+        Err(error) => github_error_response(error),
+    }
+}
+
+/// Search the code of a GitHub repository.
+///
+/// This endpoint uses the GitHub code search API, which searches the default branch. Each match
+/// has the path of the file and the fragments of it which matched. Unlike `/api/github/lines`,
+/// the repository is not downloaded, so this also works for very large repositories.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/github/code/{owner}/{repo}", responses(
+        (status = 200, description = "Search the code successfully.", body = [CodeMatch]),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+        params(
+            ("owner" = String, Path, description = "The owner of the GitHub repository."),
+            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("query" = String, Query, description = "The terms to search for."),
+            ("path" = Option<String>, Query, description = "Only files under this path."),
+            ("language" = Option<String>, Query, description = "Only files in this language, e.g. `rust`."),
+            ("extension" = Option<String>, Query, description = "Only files with this extension, e.g. `toml`."),
+            ("limit" = Option<usize>, Query, description = "The maximum number of matches, 30 by default."),
+        ),
+        security(
+            ("api_auth" = [])
+        )
+    ))]
+pub async fn search_github_repository_code(
+    Path(repository): Path<Repository>,
+    Query(query): Query<CodeQuery>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state
+        .search_github_repository_code(&repository, &query)
+        .await
+    {
+        Ok(matches) => Json(matches).into_response(),
+        Err(error) => github_error_response(error),
+    }
+}
+
+/// Get the timeline of an issue in a GitHub repository.
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/github/issue/{owner}/{repo}/{number}", responses(
+        (status = 200, description = "Get issue timeline successfully.", body = [IssueEvent]),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+        params(
+            ("owner" = String, Path, description = "The owner of the GitHub repository."),
+            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("number" = u64, Path, description = "The issue number."),
+            ("limit" = Option<usize>, Query, description = "The maximum number of events, 3000 by default."),
+            ("substantive" = Option<bool>, Query, description = "Only include comments, reviews, references and closing events."),
+        ),
+        security(
+            ("api_auth" = [])
+        )
+    ))]
+pub async fn get_github_repository_issue_timeline(
+    Path(repository_issue): Path<RepositoryIssue>,
+    Query(query): Query<TimelineQuery>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state
+        .get_github_repository_issue_timeline(
+            &repository_issue.repo,
+            repository_issue.number,
+            &query,
+        )
+        .await
+    {
+        Ok(timeline) => Json(timeline).into_response(),
+        Err(error) => github_error_response(error),
+    }
+}
+
+/// List the comments of an issue or pull request in a GitHub repository, oldest first.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/github/issue/{owner}/{repo}/{number}/comments", responses(
+        (status = 200, description = "List issue comments successfully.", body = [IssueComment]),
+        (status = 404, description = "The issue does not exist."),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+        params(
+            ("owner" = String, Path, description = "The owner of the GitHub repository."),
+            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("number" = u64, Path, description = "The issue number."),
+            ("page" = Option<usize>, Query, description = "The page of results, starting at 1."),
+            ("per_page" = Option<usize>, Query, description = "The number of results per page, at most 100."),
+        ),
+        security(
+            ("api_auth" = [])
+        )
+    ))]
+pub async fn list_github_issue_comments(
+    Path(repository_issue): Path<RepositoryIssue>,
+    Query(page): Query<PageQuery>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state
+        .list_github_issue_comments(&repository_issue.repo, repository_issue.number, &page)
+        .await
+    {
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Some(comments)) => Json(comments).into_response(),
+        Err(error) => github_error_response(error),
+    }
+}
+
+/// List the pull requests of a GitHub repository.
+///
+/// The pull requests are filtered by state, label and author and sorted by the time they were
+/// last updated, most recent first.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/github/pulls/{owner}/{repo}", responses(
+        (status = 200, description = "List pull requests successfully.", body = [PullRequestSummary]),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+        params(
+            ("owner" = String, Path, description = "The owner of the GitHub repository."),
+            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("state" = Option<PullRequestState>, Query, description = "The state of the pull requests: `open` (default), `closed`, `merged` or `all`."),
+            ("label" = Option<String>, Query, description = "The name of a label the pull requests must have."),
+            ("author" = Option<String>, Query, description = "The login of the author."),
+            ("page" = Option<usize>, Query, description = "The page of results, starting at 1."),
+            ("per_page" = Option<usize>, Query, description = "The number of results per page, at most 100."),
+        ),
+        security(
+            ("api_auth" = [])
+        )
+    ))]
+pub async fn list_github_pull_requests(
+    Path(repository): Path<Repository>,
+    Query(query): Query<PullRequestQuery>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state.list_github_pull_requests(&repository, &query).await {
+        Ok(pull_requests) => Json(pull_requests).into_response(),
+        Err(error) => github_error_response(error),
+    }
+}
+
+/// Get the details of a pull request in a GitHub repository.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/github/pulls/{owner}/{repo}/{number}", responses(
+        (status = 200, description = "Get the pull request successfully.", body = PullRequest),
+        (status = 404, description = "The pull request does not exist."),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+        params(
+            ("owner" = String, Path, description = "The owner of the GitHub repository."),
+            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("number" = u64, Path, description = "The pull request number."),
+        ),
+        security(
+            ("api_auth" = [])
+        )
+    ))]
+pub async fn get_github_pull_request(
+    Path(repository_issue): Path<RepositoryIssue>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state
+        .get_github_pull_request(&repository_issue.repo, repository_issue.number)
+        .await
+    {
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Some(pull_request)) => Json(pull_request).into_response(),
+        Err(error) => github_error_response(error),
+    }
+}
+
+/// Get the review comments of a pull request in a GitHub repository.
+///
+/// Review comments are the comments made on lines of the diff, with the hunk they refer to.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/github/pulls/{owner}/{repo}/{number}/comments", responses(
+        (status = 200, description = "Get the review comments successfully.", body = [ReviewComment]),
+        (status = 404, description = "The pull request does not exist."),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+        params(
+            ("owner" = String, Path, description = "The owner of the GitHub repository."),
+            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("number" = u64, Path, description = "The pull request number."),
+        ),
+        security(
+            ("api_auth" = [])
+        )
+    ))]
+pub async fn get_github_pull_request_review_comments(
+    Path(repository_issue): Path<RepositoryIssue>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state
+        .get_github_pull_request_review_comments(&repository_issue.repo, repository_issue.number)
+        .await
+    {
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Some(comments)) => Json(comments).into_response(),
+        Err(error) => github_error_response(error),
+    }
+}
+
+/// List the files changed by a pull request in a GitHub repository.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/github/pulls/{owner}/{repo}/{number}/files", responses(
+        (status = 200, description = "List the changed files successfully.", body = [ChangedFile]),
+        (status = 404, description = "The pull request does not exist."),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+        params(
+            ("owner" = String, Path, description = "The owner of the GitHub repository."),
+            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("number" = u64, Path, description = "The pull request number."),
+        ),
+        security(
+            ("api_auth" = [])
+        )
+    ))]
+pub async fn get_github_pull_request_files(
+    Path(repository_issue): Path<RepositoryIssue>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state
+        .get_github_pull_request_files(&repository_issue.repo, repository_issue.number)
+        .await
+    {
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Some(files)) => Json(files).into_response(),
+        Err(error) => github_error_response(error),
+    }
+}
+
+/// Get the unified diff of a pull request in a GitHub repository, paginated by file.
+///
+/// Each page contains the complete diff of up to `limit` files, starting at `offset`, so that the
+/// diff of a large pull request can be read in parts. `next_offset` is set if there are more files.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/github/pulls/{owner}/{repo}/{number}/diff", responses(
+        (status = 200, description = "Get the diff successfully.", body = UnifiedDiff),
+        (status = 404, description = "The pull request does not exist."),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+        params(
+            ("owner" = String, Path, description = "The owner of the GitHub repository."),
+            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("number" = u64, Path, description = "The pull request number."),
+            ("offset" = Option<usize>, Query, description = "The index of the first file, 0 by default."),
+            ("limit" = Option<usize>, Query, description = "The maximum number of files, 10 by default."),
+        ),
+        security(
+            ("api_auth" = [])
+        )
+    ))]
+pub async fn get_github_pull_request_diff(
+    Path(repository_issue): Path<RepositoryIssue>,
+    Query(page): Query<DiffPage>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state
+        .get_github_pull_request_diff(&repository_issue.repo, repository_issue.number, &page)
+        .await
+    {
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Some(diff)) => Json(diff).into_response(),
+        Err(error) => github_error_response(error),
+    }
+}
+
+/// List the tags of a GitHub repository, most recently created first.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/github/tags/{owner}/{repo}", responses(
+        (status = 200, description = "List tags successfully.", body = [Tag]),
+        (status = 404, description = "The repository does not exist."),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+        params(
+            ("owner" = String, Path, description = "The owner of the GitHub repository."),
+            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("page" = Option<usize>, Query, description = "The page of results, starting at 1."),
+            ("per_page" = Option<usize>, Query, description = "The number of results per page, at most 100."),
+        ),
+        security(
+            ("api_auth" = [])
+        )
+    ))]
+pub async fn list_github_tags(
+    Path(repository): Path<Repository>,
+    Query(page): Query<PageQuery>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state.list_github_tags(&repository, &page).await {
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Some(tags)) => Json(tags).into_response(),
+        Err(error) => github_error_response(error),
+    }
+}
+
+/// List the releases of a GitHub repository, most recently created first.
+///
+/// Each release includes its notes, which usually summarize what changed since the previous one.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/github/releases/{owner}/{repo}", responses(
+        (status = 200, description = "List releases successfully.", body = [Release]),
+        (status = 404, description = "The repository does not exist."),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+        params(
+            ("owner" = String, Path, description = "The owner of the GitHub repository."),
+            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("page" = Option<usize>, Query, description = "The page of results, starting at 1."),
+            ("per_page" = Option<usize>, Query, description = "The number of results per page, at most 100."),
+        ),
+        security(
+            ("api_auth" = [])
+        )
+    ))]
+pub async fn list_github_releases(
+    Path(repository): Path<Repository>,
+    Query(page): Query<PageQuery>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state.list_github_releases(&repository, &page).await {
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Some(releases)) => Json(releases).into_response(),
+        Err(error) => github_error_response(error),
+    }
+}
+
+/// List the commits of a GitHub repository, most recent first.
+///
+/// Set `path` to find when a file or directory changed, e.g. when a feature landed.
+///
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/github/commits/{owner}/{repo}", responses(
+        (status = 200, description = "List commits successfully.", body = [CommitSummary]),
+        (status = 404, description = "The repository or the starting ref does not exist."),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
+    ),
+        params(
+            ("owner" = String, Path, description = "The owner of the GitHub repository."),
+            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("sha" = Option<String>, Query, description = "The branch, tag or commit to start from, the default branch by default."),
+            ("path" = Option<String>, Query, description = "Only commits touching this file or directory."),
+            ("author" = Option<String>, Query, description = "Only commits by this login or email address."),
+            ("since" = Option<String>, Query, description = "Only commits after this ISO 8601 timestamp."),
+            ("until" = Option<String>, Query, description = "Only commits before this ISO 8601 timestamp."),
+            ("page" = Option<usize>, Query, description = "The page of results, starting at 1."),
+            ("per_page" = Option<usize>, Query, description = "The number of results per page, at most 100."),
+        ),
+        security(
+            ("api_auth" = [])
+        )
+    ))]
+pub async fn list_github_commits(
+    Path(repository): Path<Repository>,
+    Query(query): Query<CommitQuery>,
+    State(state): State<RustAssistant>,
+) -> impl IntoResponse {
+    match state.list_github_commits(&repository, &query).await {
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Some(commits)) => Json(commits).into_response(),
+        Err(error) => github_error_response(error),
     }
 }
 
-/// Get the content of a file in a crate.
-///
-/// This function serves an endpoint to retrieve the content of a specific file from a crate,
-/// potentially within a specified range of lines.
+/// Get the details of a commit in a GitHub repository, with its diff paginated by file.
 ///
 #[cfg_attr(feature = "utoipa",
-    utoipa::path(get, path = "/api/file/{crate}/{version}/{path}", responses(
-        (status = 200, description = "Read the file successfully.", body = String),
-        (status = 404, description = "The file does not exist."),
+    utoipa::path(get, path = "/api/github/commit/{owner}/{repo}/{reference}", responses(
+        (status = 200, description = "Get the commit successfully.", body = Commit),
+        (status = 404, description = "The commit does not exist."),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
         (status = 500, description = "Internal server error.", body = String),
     ),
-    params(
-        ("crate" = String, Path, description = "The exact name of the crate."),
-        ("version" = String, Path, description = "The semantic version number of the crate, following the Semantic versioning specification."),
-        ("path" = String, Path, description = "Relative path of a file in crate."),
-        ("start" = usize, Path, description = "Start line number of the file (inclusive)."),
-        ("end" = usize, Path, description = "End line number of the file (inclusive)."),
-    ),
-    security(
-    ("api_auth" = [])
-    )
-))]
-pub async fn get_file_content(
-    Path(path): Path<CrateVersionPath>,
-    Query(range): Query<FileLineRange>,
+        params(
+            ("owner" = String, Path, description = "The owner of the GitHub repository."),
+            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("reference" = String, Path, description = "The commit SHA, branch or tag."),
+            ("offset" = Option<usize>, Query, description = "The index of the first file, 0 by default."),
+            ("limit" = Option<usize>, Query, description = "The maximum number of files, 10 by default."),
+        ),
+        security(
+            ("api_auth" = [])
+        )
+    ))]
+pub async fn get_github_commit(
+    Path(repository_ref): Path<RepositoryRef>,
+    Query(page): Query<DiffPage>,
     State(state): State<RustAssistant>,
 ) -> impl IntoResponse {
-    match state.get_file_content(&path, range).await {
+    match state
+        .get_github_commit(&repository_ref.repo, &repository_ref.reference, &page)
+        .await
+    {
         Ok(None) => StatusCode::NOT_FOUND.into_response(),
-        Ok(Some(file)) => file.into_response(),
-        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+        Ok(Some(commit)) => Json(commit).into_response(),
+        Err(error) => github_error_response(error),
     }
 }
 
-/// Read a subdirectory in a crate.
+/// Compare two refs of a GitHub repository.
 ///
-/// This endpoint provides access to the contents of a subdirectory within a crate,
-/// including files and other subdirectories.
+/// Returns the commits reachable from `head` but not from `base` and the diff between their merge
+/// base and `head`, paginated by file. Comparing two release tags shows what changed in a release.
 ///
 #[cfg_attr(feature = "utoipa",
-    utoipa::path(get, path = "/api/directory/{crate}/{version}/{path}", responses(
-        (status = 200, description = "Read the subdirectory successfully.", body = Directory),
-        (status = 404, description = "The directory does not exist."),
+    utoipa::path(get, path = "/api/github/compare/{owner}/{repo}", responses(
+        (status = 200, description = "Compare the refs successfully.", body = Comparison),
+        (status = 404, description = "The repository or one of the refs does not exist."),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
         (status = 500, description = "Internal server error.", body = String),
     ),
-    params(
-        ("crate" = String, Path, description = "The exact name of the crate."),
-        ("version" = String, Path, description = "The semantic version number of the crate, following the Semantic versioning specification."),
-        ("path" = String, Path, description = "Relative path of a directory in crate."),
-    ),
-    security(
-        ("api_auth" = [])
-    )
-))]
-pub async fn read_crate_directory(
-    Path(path): Path<CrateVersionPath>,
+        params(
+            ("owner" = String, Path, description = "The owner of the GitHub repository."),
+            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("base" = String, Query, description = "The branch, tag or commit to compare against."),
+            ("head" = String, Query, description = "The branch, tag or commit to compare."),
+            ("offset" = Option<usize>, Query, description = "The index of the first file, 0 by default."),
+            ("limit" = Option<usize>, Query, description = "The maximum number of files, 10 by default."),
+        ),
+        security(
+            ("api_auth" = [])
+        )
+    ))]
+pub async fn compare_github_refs(
+    Path(repository): Path<Repository>,
+    Query(refs): Query<CompareRefs>,
+    Query(page): Query<DiffPage>,
     State(state): State<RustAssistant>,
 ) -> impl IntoResponse {
-    match state.read_directory(path).await {
+    match state
+        .compare_github_refs(&repository, &refs.base, &refs.head, &page)
+        .await
+    {
         Ok(None) => StatusCode::NOT_FOUND.into_response(),
-        Ok(Some(directory)) => Json(directory).into_response(),
-        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+        Ok(Some(comparison)) => Json(comparison).into_response(),
+        Err(error) => github_error_response(error),
     }
 }
 
-/// Read crate root directory.
-#[cfg_attr(feature = "utoipa", 
-    utoipa::path(get, path = "/api/directory/{crate}/{version}", responses(
-        (status = 200, description = "Read crate root directory successfully.", body = Directory),
-        (status = 404, description = "The root directory does not exist."),
-        (status = 500, description = "Internal server error."),
-    ),
-    params(
-        ("crate" = String, Path, description = "The exact name of the crate."),
-        ("version" = String, Path, description = "The semantic version number of the crate, following the Semantic versioning specification."),
+/// Get the branches of a GitHub repository.
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/api/github/branches/{owner}/{repo}", responses(
+        (status = 200, description = "Get repository branches successfully.", body = [String]),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
     ),
-    security(
-        ("api_auth" = [])
-    )
-))]
-pub async fn read_crate_root_directory(
-    Path(crate_version): Path<CrateVersion>,
+        params(
+            ("owner" = String, Path, description = "The owner of the GitHub repository."),
+            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("limit" = Option<usize>, Query, description = "The maximum number of branches, 3000 by default."),
+        ),
+        security(
+            ("api_auth" = [])
+        )
+    ))]
+pub async fn get_github_repository_branches(
+    Path(repository): Path<Repository>,
+    Query(limit): Query<LimitQuery>,
     State(state): State<RustAssistant>,
 ) -> impl IntoResponse {
     match state
-        .read_directory(CrateVersionPath {
-            crate_version,
-            path: "".into(),
-        })
+        .get_github_repository_branches(&repository, limit.limit)
         .await
     {
-        Ok(None) => StatusCode::NOT_FOUND.into_response(),
-        Ok(Some(directory)) => Json(directory).into_response(),
-        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+        Ok(branches) => Json(branches).into_response(),
+        Err(error) => github_error_response(error),
     }
 }
 
-/// Read the root directory of a GitHub repository.
+/// Converts an error of a GitHub request into a response, with 429 and `Retry-After` when the
+/// rate limit is exceeded, 422 when a repository is too large to index, 400 for an invalid target
+/// triple, and 503 when GitHub access is disabled.
+fn github_error_response(error: anyhow::Error) -> Response {
+    if error.is::<GithubDisabled>() {
+        return (StatusCode::SERVICE_UNAVAILABLE, error.to_string()).into_response();
+    }
+    if error.is::<RepositoryTooLarge>() {
+        return (StatusCode::UNPROCESSABLE_ENTITY, error.to_string()).into_response();
+    }
+    if error.is::<InvalidTarget>() {
+        return (StatusCode::BAD_REQUEST, error.to_string()).into_response();
+    }
+    match error.downcast_ref::<RateLimited>() {
+        Some(RateLimited {
+            retry_after: Some(wait),
+        }) => (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(
+                axum::http::header::RETRY_AFTER,
+                wait.as_secs().max(1).to_string(),
+            )],
+            error.to_string(),
+        )
+            .into_response(),
+        Some(RateLimited { retry_after: None }) => {
+            (StatusCode::TOO_MANY_REQUESTS, error.to_string()).into_response()
+        }
+        None => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+    }
+}
+
+/// Converts an error of a forge request into a response, with 404 for unknown forge hosts.
+fn forge_error_response(error: anyhow::Error) -> Response {
+    if error.is::<UnknownForge>() {
+        (StatusCode::NOT_FOUND, error.to_string()).into_response()
+    } else {
+        github_error_response(error)
+    }
+}
+
+/// Read the root directory of a repository on a forge.
 ///
-/// This endpoint provides access to the contents of the root directory within a GitHub repository,
+/// The forge endpoints provide the same operations as the GitHub endpoints for repositories hosted
+/// on GitHub, GitHub Enterprise Server, GitLab and Gitea or Forgejo instances such as Codeberg.
 ///
 #[cfg_attr(feature = "utoipa",
-    utoipa::path(get, path = "/api/github/directory/{owner}/{repo}", responses(
+    utoipa::path(get, path = "/api/forge/{host}/{owner}/{repo}/directory", responses(
         (status = 200, description = "Read repository root directory successfully.", body = Directory),
-        (status = 404, description = "The root directory does not exist."),
-        (status = 500, description = "Internal server error."),
+        (status = 404, description = "The forge is not configured or the repository does not exist."),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
+        (status = 500, description = "Internal server error.", body = String),
     ),
         params(
-            ("owner" = String, Path, description = "The owner of the GitHub repository."),
-            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("host" = String, Path, description = "The host of the forge, e.g. `github.com`, `gitlab.com` or `codeberg.org`."),
+            ("owner" = String, Path, description = "The owner of the repository, or its URL-encoded group path on GitLab."),
+            ("repo" = String, Path, description = "The name of the repository."),
             ("branch" = Option<String>, Query, description = "The branch name."),
         ),
         security(
             ("api_auth" = [])
         )
     ))]
-pub async fn read_github_repository_root_directory(
-    Path(repository): Path<Repository>,
+pub async fn read_forge_repository_root_directory(
+    Path(repository): Path<ForgeRepository>,
     Query(branch): Query<Branch>,
     State(state): State<RustAssistant>,
 ) -> impl IntoResponse {
     match state
-        .read_github_repository_directory(&repository, "", branch.as_str())
+        .read_forge_repository_directory(&repository.host, &repository.repo, "", branch.as_str())
         .await
     {
         Ok(None) => StatusCode::NOT_FOUND.into_response(),
         Ok(Some(directory)) => Json(directory).into_response(),
-        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+        Err(error) => forge_error_response(error),
     }
 }
 
-/// Read a subdirectory in a GitHub repository.
+/// Read a subdirectory of a repository on a forge.
 ///
-/// This endpoint provides access to the contents of a subdirectory within a GitHub repository,
-/// including files and other subdirectories.
 #[cfg_attr(feature = "utoipa",
-    utoipa::path(get, path = "/api/github/directory/{owner}/{repo}/{path}", responses(
+    utoipa::path(get, path = "/api/forge/{host}/{owner}/{repo}/directory/{path}", responses(
         (status = 200, description = "Read the subdirectory successfully.", body = Directory),
-        (status = 404, description = "The directory does not exist."),
+        (status = 404, description = "The forge is not configured or the directory does not exist."),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
         (status = 500, description = "Internal server error.", body = String),
     ),
         params(
-            ("owner" = String, Path, description = "The owner of the GitHub repository."),
-            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("host" = String, Path, description = "The host of the forge, e.g. `github.com`, `gitlab.com` or `codeberg.org`."),
+            ("owner" = String, Path, description = "The owner of the repository, or its URL-encoded group path on GitLab."),
+            ("repo" = String, Path, description = "The name of the repository."),
             ("path" = String, Path, description = "Relative path of a directory in repository."),
             ("branch" = Option<String>, Query, description = "The branch name."),
         ),
@@ -239,13 +1843,14 @@ pub async fn read_github_repository_root_directory(
             ("api_auth" = [])
         )
     ))]
-pub async fn read_github_repository_directory(
-    Path(repository_path): Path<RepositoryPath>,
+pub async fn read_forge_repository_directory(
+    Path(repository_path): Path<ForgeRepositoryPath>,
     Query(branch): Query<Branch>,
     State(state): State<RustAssistant>,
 ) -> impl IntoResponse {
     match state
-        .read_github_repository_directory(
+        .read_forge_repository_directory(
+            &repository_path.host,
             &repository_path.repo,
             repository_path.path.as_ref(),
             branch.as_str(),
@@ -254,23 +1859,23 @@ pub async fn read_github_repository_directory(
     {
         Ok(None) => StatusCode::NOT_FOUND.into_response(),
         Ok(Some(directory)) => Json(directory).into_response(),
-        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+        Err(error) => forge_error_response(error),
     }
 }
 
-/// Read the content of a file in a GitHub repository.
-///
-/// This function serves an endpoint to retrieve the content of a specific file from a GitHub repository.
+/// Read the content of a file in a repository on a forge.
 ///
 #[cfg_attr(feature = "utoipa",
-    utoipa::path(get, path = "/api/github/file/{owner}/{repo}/{path}", responses(
+    utoipa::path(get, path = "/api/forge/{host}/{owner}/{repo}/file/{path}", responses(
         (status = 200, description = "Read the file successfully.", body = String),
-        (status = 404, description = "The file does not exist."),
+        (status = 404, description = "The forge is not configured or the file does not exist."),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
         (status = 500, description = "Internal server error.", body = String),
     ),
         params(
-            ("owner" = String, Path, description = "The owner of the GitHub repository."),
-            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("host" = String, Path, description = "The host of the forge, e.g. `github.com`, `gitlab.com` or `codeberg.org`."),
+            ("owner" = String, Path, description = "The owner of the repository, or its URL-encoded group path on GitLab."),
+            ("repo" = String, Path, description = "The name of the repository."),
             ("path" = String, Path, description = "Relative path of a file in repository."),
             ("branch" = Option<String>, Query, description = "The branch name."),
         ),
@@ -278,13 +1883,14 @@ pub async fn read_github_repository_directory(
             ("api_auth" = [])
         )
     ))]
-pub async fn read_github_repository_file_content(
-    Path(repository_path): Path<RepositoryPath>,
+pub async fn read_forge_repository_file_content(
+    Path(repository_path): Path<ForgeRepositoryPath>,
     Query(branch): Query<Branch>,
     State(state): State<RustAssistant>,
 ) -> impl IntoResponse {
     match state
-        .read_github_repository_file(
+        .read_forge_repository_file(
+            &repository_path.host,
             &repository_path.repo,
             repository_path.path.as_ref(),
             branch.as_str(),
@@ -293,97 +1899,134 @@ pub async fn read_github_repository_file_content(
     {
         Ok(None) => StatusCode::NOT_FOUND.into_response(),
         Ok(Some(file)) => file.into_response(),
-        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+        Err(error) => forge_error_response(error),
     }
 }
 
-/// Search for issues in a GitHub repository.
+/// Search for issues in a repository on a forge.
 ///
 #[cfg_attr(feature = "utoipa",
-    utoipa::path(get, path = "/api/github/issue/{owner}/{repo}", responses(
+    utoipa::path(get, path = "/api/forge/{host}/{owner}/{repo}/issue", responses(
         (status = 200, description = "Get issue list successfully.", body = [Issue]),
+        (status = 404, description = "The forge is not configured."),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
         (status = 500, description = "Internal server error.", body = String),
     ),
         params(
-            ("owner" = String, Path, description = "The owner of the GitHub repository."),
-            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("host" = String, Path, description = "The host of the forge, e.g. `github.com`, `gitlab.com` or `codeberg.org`."),
+            ("owner" = String, Path, description = "The owner of the repository, or its URL-encoded group path on GitLab."),
+            ("repo" = String, Path, description = "The name of the repository."),
             ("query" = String, Query, description = "Query string."),
         ),
         security(
             ("api_auth" = [])
         )
     ))]
-pub async fn search_github_repository_for_issues(
-    Path(repository): Path<Repository>,
+pub async fn search_forge_repository_for_issues(
+    Path(repository): Path<ForgeRepository>,
     Query(query): Query<IssueQuery>,
     State(state): State<RustAssistant>,
 ) -> impl IntoResponse {
     match state
-        .search_github_repository_for_issues(&repository, query.as_ref())
+        .search_forge_repository_for_issues(&repository.host, &repository.repo, query.as_ref())
         .await
     {
         Ok(issues) => Json(issues).into_response(),
-        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+        Err(error) => forge_error_response(error),
     }
 }
 
-/// Get the timeline of an issue in a GitHub repository.
+/// Get the timeline of an issue in a repository on a forge.
+///
 #[cfg_attr(feature = "utoipa",
-    utoipa::path(get, path = "/api/github/issue/{owner}/{repo}/{number}", responses(
+    utoipa::path(get, path = "/api/forge/{host}/{owner}/{repo}/issue/{number}", responses(
         (status = 200, description = "Get issue timeline successfully.", body = [IssueEvent]),
+        (status = 404, description = "The forge is not configured."),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
         (status = 500, description = "Internal server error.", body = String),
     ),
         params(
-            ("owner" = String, Path, description = "The owner of the GitHub repository."),
-            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("host" = String, Path, description = "The host of the forge, e.g. `github.com`, `gitlab.com` or `codeberg.org`."),
+            ("owner" = String, Path, description = "The owner of the repository, or its URL-encoded group path on GitLab."),
+            ("repo" = String, Path, description = "The name of the repository."),
             ("number" = u64, Path, description = "The issue number."),
         ),
         security(
             ("api_auth" = [])
         )
     ))]
-pub async fn get_github_repository_issue_timeline(
-    Path(repository_issue): Path<RepositoryIssue>,
+pub async fn get_forge_repository_issue_timeline(
+    Path(repository_issue): Path<ForgeRepositoryIssue>,
     State(state): State<RustAssistant>,
 ) -> impl IntoResponse {
     match state
-        .get_github_repository_issue_timeline(&repository_issue.repo, repository_issue.number)
+        .get_forge_repository_issue_timeline(
+            &repository_issue.host,
+            &repository_issue.repo,
+            repository_issue.number,
+        )
         .await
     {
         Ok(timeline) => Json(timeline).into_response(),
-        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+        Err(error) => forge_error_response(error),
     }
 }
 
-/// Get the branches of a GitHub repository.
+/// Get the branches of a repository on a forge.
+///
 #[cfg_attr(feature = "utoipa",
-    utoipa::path(get, path = "/api/github/branches/{owner}/{repo}", responses(
+    utoipa::path(get, path = "/api/forge/{host}/{owner}/{repo}/branches", responses(
         (status = 200, description = "Get repository branches successfully.", body = [String]),
+        (status = 404, description = "The forge is not configured."),
+        (status = 429, description = "The GitHub API rate limit is exceeded.", body = String),
         (status = 500, description = "Internal server error.", body = String),
     ),
         params(
-            ("owner" = String, Path, description = "The owner of the GitHub repository."),
-            ("repo" = String, Path, description = "The name of the GitHub repository."),
+            ("host" = String, Path, description = "The host of the forge, e.g. `github.com`, `gitlab.com` or `codeberg.org`."),
+            ("owner" = String, Path, description = "The owner of the repository, or its URL-encoded group path on GitLab."),
+            ("repo" = String, Path, description = "The name of the repository."),
         ),
         security(
             ("api_auth" = [])
         )
     ))]
-pub async fn get_github_repository_branches(
-    Path(repository): Path<Repository>,
+pub async fn get_forge_repository_branches(
+    Path(repository): Path<ForgeRepository>,
     State(state): State<RustAssistant>,
 ) -> impl IntoResponse {
-    match state.get_github_repository_branches(&repository).await {
+    match state
+        .get_forge_repository_branches(&repository.host, &repository.repo)
+        .await
+    {
         Ok(branches) => Json(branches).into_response(),
-        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+        Err(error) => forge_error_response(error),
     }
 }
 
+/// The health of the API.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct Health {
+    /// How the GitHub API is accessed and its last known rate limit.
+    pub github: GithubStatus,
+}
+
 /// Health check endpoint.
 ///
 /// This endpoint is used to perform a health check of the API, ensuring that it is running and responsive.
+/// It also reports whether GitHub access is authenticated, anonymous or disabled.
 ///
-pub async fn health() {}
+#[cfg_attr(feature = "utoipa",
+    utoipa::path(get, path = "/health", responses(
+        (status = 200, description = "The API is healthy.", body = Health),
+    ))
+)]
+pub async fn health(State(state): State<RustAssistant>) -> impl IntoResponse {
+    Json(Health {
+        github: state.github_status(),
+    })
+}
 
 /// Redirect the client to "https://rustassistant.com".
 ///
@@ -404,28 +2047,96 @@ pub async fn privacy_policy() -> impl IntoResponse {
 /// This function sets up the routing for the API, including all the endpoints for searching crates,
 /// reading file contents, and accessing directory information. It also configures any necessary middleware.
 ///
+/// Besides `github.com`, the public `gitlab.com` and `codeberg.org` forges are available for
+/// anonymous access, and further forges, such as self-hosted Gitea or GitHub Enterprise Server
+/// instances, can be added with `forges`.
+///
+/// When GitHub access is disabled, the `/api/github` endpoints are not served and are left out of
+/// the OpenAPI document, and `github.com` is not available as a forge.
+///
 pub fn router(
     auth_info: impl Into<Option<AuthInfo>>,
-    github_token: &str,
+    github: &GithubAccess,
+    forges: &[ForgeConfig],
 ) -> anyhow::Result<Router> {
+    let cache = CrateCache::default();
+    let github = github
+        .client(None)?
+        .map(|github| github.with_memory_budget(cache.memory_budget().clone()));
+    let mut forge_registry = ForgeRegistry::with_public_forges(github.clone())?;
+    forge_registry.configure(forges)?;
+    let github_enabled = github.is_some();
+    let state = RustAssistant::from((CrateDownloader::default(), cache, github))
+        .with_forges(forge_registry);
+
     let main = Router::new()
         .route("/", get(redirect))
         .route("/health", get(health))
-        .route("/privacy-policy", get(privacy_policy));
+        .route("/privacy-policy", get(privacy_policy))
+        .with_state(state.clone());
 
     #[cfg(feature = "utoipa")]
-    let main = {
-        use utoipa::OpenApi;
-        main.merge(
-            utoipa_swagger_ui::SwaggerUi::new("/swagger-ui")
-                .url("/api-docs/openapi.json", swagger_ui::ApiDoc::openapi()),
-        )
-    };
+    let main = main.merge(utoipa_swagger_ui::SwaggerUi::new("/swagger-ui").url(
+        "/api-docs/openapi.json",
+        swagger_ui::openapi(state.github_status().mode),
+    ));
 
     let api = Router::new()
         .route("/lines/{crate}/{version}", get(search_crate_for_lines))
         .route("/items/{crate}/{version}", get(search_crate_for_items))
+        .route(
+            "/implementors/{crate}/{version}",
+            get(get_trait_implementors),
+        )
+        .route(
+            "/implemented-traits/{crate}/{version}",
+            get(get_implemented_traits),
+        )
+        .nest(
+            "/multi",
+            Router::new()
+                .route("/items", get(search_crates_for_items))
+                .route("/lines", get(search_crates_for_lines)),
+        )
+        .route("/usages/{crate}/{version}", get(find_item_usages))
+        .route("/references/{crate}/{version}", get(find_item_references))
+        .route(
+            "/definition/{crate}/{version}/{*path}",
+            get(goto_definition),
+        )
         .route("/file/{crate}/{version}/{*path}", get(get_file_content))
+        .route("/outline/{crate}/{version}/{*path}", get(get_file_outline))
+        .route("/manifest/{crate}/{version}", get(get_crate_manifest))
+        .route("/overview/{crate}/{version}", get(get_crate_overview))
+        .route(
+            "/parse-errors/{crate}/{version}",
+            get(get_crate_parse_errors),
+        )
+        .nest(
+            "/source/{crate}/{version}",
+            Router::new()
+                .route("/", get(get_crate_source))
+                .route("/{*path}", get(read_crate_source_file)),
+        )
+        .route(
+            "/dependencies/{crate}/{version}",
+            get(get_crate_dependencies),
+        )
+        .route(
+            "/dependencies/{crate}/{version}/tree",
+            get(get_crate_dependency_tree),
+        )
+        .route("/api-diff/{crate}/{old}/{new}", get(diff_crate_api))
+        .route(
+            "/file-diff/{crate}/{old}/{new}/{*path}",
+            get(diff_crate_file),
+        )
+        .nest(
+            "/directory-diff/{crate}/{old}/{new}",
+            Router::new()
+                .route("/", get(diff_crate_root_directory))
+                .route("/{*path}", get(diff_crate_directory)),
+        )
         .nest(
             "/directory/{crate}/{version}",
             Router::new()
@@ -433,34 +2144,22 @@ pub fn router(
                 .route("/{*path}", get(read_crate_directory)),
         )
         .nest(
-            "/github",
+            "/forge/{host}/{owner}/{repo}",
             Router::new()
-                .nest(
-                    "/directory/{owner}/{repo}",
-                    Router::new()
-                        .route("/", get(read_github_repository_root_directory))
-                        .route("/{*path}", get(read_github_repository_directory)),
-                )
-                .route(
-                    "/file/{owner}/{repo}/{*path}",
-                    get(read_github_repository_file_content),
-                )
-                .nest(
-                    "/issue/{owner}/{repo}",
-                    Router::new()
-                        .route("/", get(search_github_repository_for_issues))
-                        .route("/{number}", get(get_github_repository_issue_timeline)),
-                )
-                .route(
-                    "/branches/{owner}/{repo}",
-                    get(get_github_repository_branches),
-                ),
+                .route("/file/{*path}", get(read_forge_repository_file_content))
+                .route("/directory", get(read_forge_repository_root_directory))
+                .route("/directory/{*path}", get(read_forge_repository_directory))
+                .route("/branches", get(get_forge_repository_branches))
+                .route("/issue", get(search_forge_repository_for_issues))
+                .route("/issue/{number}", get(get_forge_repository_issue_timeline)),
         )
-        .with_state(RustAssistant::from((
-            CrateDownloader::default(),
-            CrateCache::default(),
-            GithubClient::new(github_token, None)?,
-        )));
+        .with_state(state.clone());
+
+    let api = if github_enabled {
+        api.nest("/github", github_router().with_state(state))
+    } else {
+        api
+    };
 
     let api = if let Some(auth_info) = auth_info.into() {
         api.layer(axum::middleware::from_extractor::<RequireAuth>())
@@ -472,6 +2171,68 @@ pub fn router(
     Ok(main.nest("/api", api))
 }
 
+/// The router of the `/api/github` endpoints.
+fn github_router() -> Router<RustAssistant> {
+    Router::new()
+        .nest(
+            "/directory/{owner}/{repo}",
+            Router::new()
+                .route("/", get(read_github_repository_root_directory))
+                .route("/{*path}", get(read_github_repository_directory)),
+        )
+        .route(
+            "/file/{owner}/{repo}/{*path}",
+            get(read_github_repository_file_content),
+        )
+        .route(
+            "/items/{owner}/{repo}",
+            get(search_github_repository_for_items),
+        )
+        .route(
+            "/lines/{owner}/{repo}",
+            get(search_github_repository_for_lines),
+        )
+        .route(
+            "/outline/{owner}/{repo}/{*path}",
+            get(get_github_repository_file_outline),
+        )
+        .nest(
+            "/tree/{owner}/{repo}",
+            Router::new()
+                .route("/", get(read_github_repository_root_tree))
+                .route("/{*path}", get(read_github_repository_tree)),
+        )
+        .nest(
+            "/issue/{owner}/{repo}",
+            Router::new()
+                .route("/", get(search_github_repository_for_issues))
+                .route("/{number}", get(get_github_repository_issue_timeline))
+                .route("/{number}/comments", get(list_github_issue_comments)),
+        )
+        .route("/code/{owner}/{repo}", get(search_github_repository_code))
+        .route(
+            "/branches/{owner}/{repo}",
+            get(get_github_repository_branches),
+        )
+        .nest(
+            "/pulls/{owner}/{repo}",
+            Router::new()
+                .route("/", get(list_github_pull_requests))
+                .route("/{number}", get(get_github_pull_request))
+                .route(
+                    "/{number}/comments",
+                    get(get_github_pull_request_review_comments),
+                )
+                .route("/{number}/files", get(get_github_pull_request_files))
+                .route("/{number}/diff", get(get_github_pull_request_diff)),
+        )
+        .route("/tags/{owner}/{repo}", get(list_github_tags))
+        .route("/releases/{owner}/{repo}", get(list_github_releases))
+        .route("/commits/{owner}/{repo}", get(list_github_commits))
+        .route("/commit/{owner}/{repo}/{reference}", get(get_github_commit))
+        .route("/compare/{owner}/{repo}", get(compare_github_refs))
+}
+
 impl IntoResponse for FileContent {
     fn into_response(self) -> Response {
         let content_type = match self.data_type {
@@ -488,6 +2249,31 @@ impl IntoResponse for FileContent {
     }
 }
 
+impl IntoResponse for ItemSearchResult {
+    fn into_response(self) -> Response {
+        let mut response = Json(self.items).into_response();
+        response.headers_mut().insert(
+            "x-incomplete",
+            HeaderValue::from_static(if self.incomplete { "true" } else { "false" }),
+        );
+        response
+    }
+}
+
+impl IntoResponse for RepositoryFile {
+    fn into_response(self) -> Response {
+        let mut response = self.content.into_response();
+        if let Ok(sha) = HeaderValue::from_str(&self.sha) {
+            let headers = response.headers_mut();
+            if let Ok(etag) = HeaderValue::from_str(&format!("\"{}\"", self.sha)) {
+                headers.insert(axum::http::header::ETAG, etag);
+            }
+            headers.insert("x-blob-sha", sha);
+        }
+        response
+    }
+}
+
 /// Authentication information structure.
 ///
 /// This struct holds authentication credentials, such as username and password, used for API access.
@@ -549,7 +2335,9 @@ impl FromRequestParts<()> for RequireAuth {
 
 #[cfg(feature = "utoipa")]
 mod swagger_ui {
+    use crate::github::GithubMode;
     use utoipa::openapi::security::{Http, HttpAuthScheme, SecurityScheme};
+    use utoipa::OpenApi;
 
     #[derive(utoipa::OpenApi)]
     #[openapi(
@@ -558,20 +2346,63 @@ mod swagger_ui {
         description = "API that supports source code browsing of crates on crates.io for Rust Assistant."
     ),
     paths(
+        super::health,
         super::get_file_content,
+        super::get_file_outline,
         super::read_crate_directory,
         super::read_crate_root_directory,
+        super::get_crate_manifest,
+        super::get_crate_overview,
+        super::get_crate_parse_errors,
+        super::get_crate_source,
+        super::read_crate_source_file,
+        super::get_crate_dependencies,
+        super::get_crate_dependency_tree,
+        super::diff_crate_api,
+        super::diff_crate_file,
+        super::diff_crate_directory,
+        super::diff_crate_root_directory,
         super::search_crate_for_items,
+        super::get_trait_implementors,
+        super::get_implemented_traits,
         super::search_crate_for_lines,
+        super::search_crates_for_items,
+        super::search_crates_for_lines,
+        super::find_item_usages,
+        super::goto_definition,
+        super::find_item_references,
         super::read_github_repository_root_directory,
         super::read_github_repository_directory,
         super::read_github_repository_file_content,
+        super::search_github_repository_for_items,
+        super::search_github_repository_for_lines,
+        super::get_github_repository_file_outline,
+        super::read_github_repository_root_tree,
+        super::read_github_repository_tree,
         super::search_github_repository_for_issues,
+        super::search_github_repository_code,
         super::get_github_repository_issue_timeline,
+        super::list_github_issue_comments,
         super::get_github_repository_branches,
+        super::list_github_pull_requests,
+        super::get_github_pull_request,
+        super::get_github_pull_request_review_comments,
+        super::get_github_pull_request_files,
+        super::get_github_pull_request_diff,
+        super::list_github_tags,
+        super::list_github_releases,
+        super::list_github_commits,
+        super::get_github_commit,
+        super::compare_github_refs,
+        super::read_forge_repository_root_directory,
+        super::read_forge_repository_directory,
+        super::read_forge_repository_file_content,
+        super::search_forge_repository_for_issues,
+        super::get_forge_repository_issue_timeline,
+        super::get_forge_repository_branches,
     ),
     components(
-        schemas(crate::Directory, crate::Item, crate::ItemType, crate::SearchMode, crate::Line, crate::RangeSchema, crate::Actor, crate::Author, crate::Issue, crate::IssueEvent)
+        schemas(crate::Directory, crate::Item, crate::ImplInfo, crate::ImplSearchResult, crate::ParseError, crate::outline::OutlineItem, crate::outline::OutlineKind, crate::overview::CrateOverview, crate::vcs::CrateSource, super::Health, crate::GithubStatus, crate::GithubMode, crate::RateLimit, crate::vcs::SourceRevision, crate::Repository, crate::overview::DependencyCounts, crate::overview::ModuleSummary, crate::overview::ReExportSummary, crate::overview::DirectoryLines, crate::Manifest, crate::manifest::Package, crate::manifest::Dependencies, crate::manifest::TargetDependencies, crate::manifest::Dependency, crate::manifest::Target, crate::resolve::DependencyGraph, crate::resolve::DependencyNode, crate::resolve::DependencyEdge, crate::resolve::UnresolvedDependency, crate::diff::ApiDiff, crate::diff::ApiChange, crate::diff::ApiItem, crate::diff::ReExportChange, crate::diff::ChangeKind, crate::diff::SemverBump, crate::diff::FileDiff, crate::ItemType, crate::SearchMode, crate::Line, crate::RangeSchema, crate::CrateVersion, crate::CrateItem, crate::CrateLine, crate::CrateUsage, crate::xref::Usage, crate::xref::UsageKind, crate::xref::Definitions, crate::xref::References, crate::Position, crate::CrateSearchError, crate::Actor, crate::Author, crate::Issue, crate::IssueState, crate::IssueKind, crate::IssueSort, crate::SortOrder, crate::IssueEvent, crate::IssueReference, crate::IssueComment, crate::Reactions, crate::PullRequestState, crate::PullRequestSummary, crate::PullRequest, crate::ReviewComment, crate::ChangedFile, crate::UnifiedDiff, crate::CodeMatch, crate::CodeFragment, crate::Tag, crate::Release, crate::CommitSummary, crate::Commit, crate::Comparison)
     ),
     modifiers(&SecurityAddon),
     tags(
@@ -580,6 +2411,21 @@ mod swagger_ui {
     )]
     pub struct ApiDoc;
 
+    /// Builds the OpenAPI document, leaving out the GitHub endpoints when GitHub access is
+    /// disabled and stating how GitHub is accessed in the description.
+    pub fn openapi(github: GithubMode) -> utoipa::openapi::OpenApi {
+        let mut openapi = ApiDoc::openapi();
+        if github == GithubMode::Disabled {
+            openapi
+                .paths
+                .paths
+                .retain(|path, _| !path.starts_with("/api/github/"));
+        }
+        let description = openapi.info.description.take().unwrap_or_default();
+        openapi.info.description = Some(format!("{description} {}", github.description()));
+        openapi
+    }
+
     struct SecurityAddon;
 
     impl utoipa::Modify for SecurityAddon {
@@ -3,24 +3,32 @@
 //! This module provides caching functionalities to optimize performance and reduce
 //! redundant operations, particularly in the context of downloading and storing crate data.
 //! It may include structures like `CrateCache` to store downloaded crates and their metadata
-//! for quick retrieval.
+//! for quick retrieval. A `MemoryBudget` bounds the memory used by these caches together with
+//! the response cache of the GitHub client.
 //!
+use crate::github::RepositoryCommit;
+use crate::manifest::{InvalidManifest, Manifest};
+use crate::outline::{self, OutlineItem};
+use crate::overview::{self, CrateOverview};
 use crate::search::{SearchIndex, SearchIndexBuilder};
+use crate::xref::XrefIndex;
 use crate::{
-    CrateVersion, Directory, DirectoryMut, FileLineRange, Item, ItemQuery, Line, LineQuery,
-    SearchMode,
+    CrateVersion, Directory, DirectoryMut, FileLineRange, ImplQuery, ImplSearchResult, ItemQuery,
+    ItemSearchResult, Line, LineQuery, ParseError, SearchMode,
 };
 use bytes::{Bytes, BytesMut};
-use fnv::FnvHashMap;
+use fnv::{FnvHashMap, FnvHasher};
 use lru::LruCache;
 use parking_lot::Mutex;
 use regex::RegexBuilder;
 use std::collections::BTreeSet;
+use std::hash::{Hash, Hasher};
 use std::io::{BufRead, Cursor, Read};
 use std::num::NonZeroUsize;
 use std::ops::{Bound, Range, RangeBounds};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
 use tar::EntryType;
 
 /// Represents a tarball of a crate, including version information and tar data.
@@ -200,6 +208,8 @@ pub struct CrateFileDataDesc {
     pub data_type: FileDataType,
     /// The byte range of the file content within the crate's data buffer.
     pub range: Range<usize>,
+    /// The FNV-1a hash of the file content, used to detect identical files without comparing them.
+    pub hash: u64,
 }
 
 /// Contains the actual content of a file within a crate.
@@ -226,66 +236,27 @@ impl From<Bytes> for FileContent {
     }
 }
 
-/// Represents a crate with its data and indexes for quick access to its contents.
-///
-/// This struct stores the complete data of a crate and provides indexes for accessing individual files,
-/// directories, and search functionalities within the crate.
-///
-#[derive(Debug, Clone)]
-pub struct Crate {
-    data: Bytes,
-    files_index: Arc<FnvHashMap<PathBuf, CrateFileDataDesc>>,
-    directories_index: Arc<FnvHashMap<PathBuf, Directory>>,
-    item_search_index: SearchIndex,
-}
-
-impl Crate {
-    /// Retrieves the content of a file by specifying a line range.
+impl FileContent {
+    /// Retrieves the lines of the file within a range.
     ///
-    pub fn get_file_by_file_line_range<P: AsRef<Path>>(
-        &self,
-        file: P,
-        FileLineRange { start, end }: FileLineRange,
-    ) -> anyhow::Result<Option<FileContent>> {
-        match (start, end) {
-            (Some(start), Some(end)) => self.get_file_by_line_range(file, start..=end),
-            (Some(start), None) => self.get_file_by_line_range(file, start..),
-            (None, Some(end)) => self.get_file_by_line_range(file, ..=end),
-            (None, None) => self.get_file_by_line_range(file, ..),
-        }
-    }
-
-    /// Retrieves the content of a file by specifying a line range.
+    /// Returns `None` if the range starts past the end of the file.
     ///
-    /// This method is used to extract a specific range of lines from a file in the crate.
-    ///
-    pub fn get_file_by_line_range<P: AsRef<Path>>(
+    pub fn get_line_range(
         &self,
-        file: P,
         line_range: impl RangeBounds<NonZeroUsize>,
     ) -> anyhow::Result<Option<FileContent>> {
-        let file = file.as_ref();
-        let Some(CrateFileDataDesc { range, data_type }) = self.files_index.get(file) else {
-            return Ok(None);
-        };
-
-        let data = self.data.slice(range.clone());
-
         if matches!(
             (line_range.start_bound(), line_range.end_bound()),
             (Bound::Unbounded, Bound::Unbounded)
         ) {
-            return Ok(Some(FileContent {
-                data,
-                data_type: *data_type,
-            }));
+            return Ok(Some(self.clone()));
         }
 
-        if let FileDataType::NonUtf8 = data_type {
+        if let FileDataType::NonUtf8 = self.data_type {
             anyhow::bail!("Non-UTF8 formatted files do not support line-range querying.");
         }
 
-        let s = std::str::from_utf8(data.as_ref())?;
+        let s = std::str::from_utf8(self.data.as_ref())?;
         let start_line = match line_range.start_bound() {
             Bound::Included(n) => n.get() - 1,
             Bound::Excluded(n) => n.get(),
@@ -325,16 +296,142 @@ impl Crate {
         }
 
         if line_start < line_end {
-            let line_bytes_range = range.start + line_start..range.start + line_end;
             return Ok(Some(FileContent {
                 data_type: FileDataType::Utf8,
-                data: self.data.slice(line_bytes_range),
+                data: self.data.slice(line_start..line_end),
             }));
         }
 
         Ok(None)
     }
 
+    /// Retrieves the lines of the file within a `FileLineRange`.
+    ///
+    pub fn get_file_line_range(
+        &self,
+        FileLineRange { start, end }: FileLineRange,
+    ) -> anyhow::Result<Option<FileContent>> {
+        match (start, end) {
+            (Some(start), Some(end)) => self.get_line_range(start..=end),
+            (Some(start), None) => self.get_line_range(start..),
+            (None, Some(end)) => self.get_line_range(..=end),
+            (None, None) => self.get_line_range(..),
+        }
+    }
+}
+
+/// Represents a crate with its data and indexes for quick access to its contents.
+///
+/// This struct stores the complete data of a crate and provides indexes for accessing individual files,
+/// directories, and search functionalities within the crate.
+///
+#[derive(Debug, Clone)]
+pub struct Crate {
+    data: Bytes,
+    files_index: Arc<FnvHashMap<PathBuf, CrateFileDataDesc>>,
+    directories_index: Arc<FnvHashMap<PathBuf, Directory>>,
+    item_search_index: SearchIndex,
+    manifest: Option<Arc<Manifest>>,
+    manifest_error: Option<InvalidManifest>,
+    xref_index: Arc<OnceLock<XrefIndex>>,
+}
+
+impl Crate {
+    /// Returns the approximate memory used by the crate, dominated by the contents of its files.
+    ///
+    pub fn memory_usage(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Retrieves the content of a file by specifying a line range.
+    ///
+    pub fn get_file_by_file_line_range<P: AsRef<Path>>(
+        &self,
+        file: P,
+        FileLineRange { start, end }: FileLineRange,
+    ) -> anyhow::Result<Option<FileContent>> {
+        match (start, end) {
+            (Some(start), Some(end)) => self.get_file_by_line_range(file, start..=end),
+            (Some(start), None) => self.get_file_by_line_range(file, start..),
+            (None, Some(end)) => self.get_file_by_line_range(file, ..=end),
+            (None, None) => self.get_file_by_line_range(file, ..),
+        }
+    }
+
+    /// Retrieves the content of a file by specifying a line range.
+    ///
+    /// This method is used to extract a specific range of lines from a file in the crate.
+    ///
+    pub fn get_file_by_line_range<P: AsRef<Path>>(
+        &self,
+        file: P,
+        line_range: impl RangeBounds<NonZeroUsize>,
+    ) -> anyhow::Result<Option<FileContent>> {
+        let file = file.as_ref();
+        let Some(CrateFileDataDesc {
+            range, data_type, ..
+        }) = self.files_index.get(file)
+        else {
+            return Ok(None);
+        };
+
+        FileContent {
+            data: self.data.slice(range.clone()),
+            data_type: *data_type,
+        }
+        .get_line_range(line_range)
+    }
+
+    /// Returns the parsed `Cargo.toml` manifest of the crate.
+    ///
+    /// Returns `None` if the crate has no `Cargo.toml` or it could not be parsed, see
+    /// [`Crate::manifest_error`].
+    ///
+    pub fn manifest(&self) -> Option<&Arc<Manifest>> {
+        self.manifest.as_ref()
+    }
+
+    /// Returns the error of parsing the `Cargo.toml` of the crate, if it exists but is invalid.
+    ///
+    pub fn manifest_error(&self) -> Option<&InvalidManifest> {
+        self.manifest_error.as_ref()
+    }
+
+    /// Iterates over all files in the crate and their descriptions.
+    ///
+    pub fn files(&self) -> impl Iterator<Item = (&Path, &CrateFileDataDesc)> {
+        self.files_index
+            .iter()
+            .map(|(path, desc)| (path.as_path(), desc))
+    }
+
+    /// Returns the description of a file in the crate.
+    ///
+    pub fn get_file_desc<P: AsRef<Path>>(&self, file: P) -> Option<&CrateFileDataDesc> {
+        self.files_index.get(file.as_ref())
+    }
+
+    /// Returns the content of a file described by `desc`.
+    ///
+    pub fn file_data(&self, desc: &CrateFileDataDesc) -> Bytes {
+        self.data.slice(desc.range.clone())
+    }
+
+    /// Checks whether a file has the same content as a file of another crate.
+    ///
+    /// Files with different lengths or hashes are known to differ without comparing their contents.
+    ///
+    pub fn is_same_file(
+        &self,
+        desc: &CrateFileDataDesc,
+        other: &Crate,
+        other_desc: &CrateFileDataDesc,
+    ) -> bool {
+        desc.range.len() == other_desc.range.len()
+            && desc.hash == other_desc.hash
+            && self.data[desc.range.clone()] == other.data[other_desc.range.clone()]
+    }
+
     /// Reads the content of a specified directory within the crate.
     ///
     pub fn read_directory<P: AsRef<Path>>(&self, path: P) -> Option<&Directory> {
@@ -343,8 +440,79 @@ impl Crate {
 
     /// Searches for items in the crate based on a given query.
     ///
-    pub fn search_item(&self, query: &ItemQuery) -> Vec<Item> {
-        self.item_search_index.search(query)
+    /// The result is marked as incomplete if any Rust source file within the query path failed to parse.
+    /// If the query specifies features or a target, items whose `cfg` predicate is false under that
+    /// configuration are filtered out.
+    ///
+    pub fn search_item(&self, query: &ItemQuery) -> anyhow::Result<ItemSearchResult> {
+        let config = query.cfg_config(self.manifest.as_deref())?;
+        let mut items = self.item_search_index.search(query);
+        if !config.is_unknown() {
+            items.retain(|item| {
+                item.cfg
+                    .as_ref()
+                    .is_none_or(|cfg| cfg.eval(&config) != Some(false))
+            });
+        }
+
+        let parse_errors = self.item_search_index.parse_errors(query.path.as_deref());
+        Ok(ItemSearchResult {
+            items,
+            incomplete: !parse_errors.is_empty(),
+            parse_errors,
+        })
+    }
+
+    /// Finds the impls of a trait in the crate, including those generated by `#[derive(...)]`.
+    ///
+    pub fn implementors(&self, query: &ImplQuery) -> ImplSearchResult {
+        self.item_search_index
+            .implementors(&query.name, query.path.as_deref())
+    }
+
+    /// Finds the traits implemented by a type in the crate, including derived ones.
+    ///
+    pub fn implemented_traits(&self, query: &ImplQuery) -> ImplSearchResult {
+        self.item_search_index
+            .implemented_traits(&query.name, query.path.as_deref())
+    }
+
+    /// Builds the outline of a Rust source file of the crate.
+    ///
+    /// Returns `None` if the file does not exist, or an error if it is not valid Rust.
+    ///
+    pub fn outline<P: AsRef<Path>>(&self, file: P) -> anyhow::Result<Option<Vec<OutlineItem>>> {
+        let Some(desc) = self.get_file_desc(file) else {
+            return Ok(None);
+        };
+        let data = self.file_data(desc);
+        Ok(Some(outline::outline(std::str::from_utf8(&data)?)?))
+    }
+
+    /// Builds the overview of the crate: its manifest summary, crate-level docs, top-level public
+    /// modules and re-exports, item counts, lines of code and targets.
+    ///
+    pub fn overview(&self) -> CrateOverview {
+        overview::overview(self)
+    }
+
+    /// Returns the item index of the crate.
+    ///
+    pub fn item_index(&self) -> &SearchIndex {
+        &self.item_search_index
+    }
+
+    /// Returns the cross-reference index of the crate, which is built on first use by parsing its
+    /// Rust source files.
+    ///
+    pub fn xref_index(&self) -> &XrefIndex {
+        self.xref_index.get_or_init(|| XrefIndex::new(self))
+    }
+
+    /// Returns the Rust source files that failed to parse when the crate was indexed.
+    ///
+    pub fn parse_errors(&self) -> &[ParseError] {
+        &self.item_search_index.parse_errors
     }
 
     /// Searches for lines in the crate's files based on a given query.
@@ -439,8 +607,34 @@ impl Crate {
 impl TryFrom<CrateTar> for Crate {
     type Error = std::io::Error;
     fn try_from(crate_tar: CrateTar) -> std::io::Result<Self> {
-        let mut archive = tar::Archive::new(crate_tar.tar_data.as_slice());
         let root_dir = crate_tar.crate_version.root_dir();
+        Crate::from_tar(&crate_tar.tar_data, |path| {
+            path.strip_prefix(&root_dir).ok()
+        })
+    }
+}
+
+impl Crate {
+    /// Builds a crate from the tarball of a GitHub repository.
+    ///
+    /// GitHub tarballs contain a single top-level directory named after the repository and the
+    /// commit, e.g. `owner-repo-0123abc/`, which is stripped from the paths of the files.
+    ///
+    pub fn from_repository_tar(tar_data: &[u8]) -> std::io::Result<Self> {
+        Crate::from_tar(tar_data, |path| {
+            let mut components = path.components();
+            components.next()?;
+            Some(components.as_path())
+        })
+    }
+
+    /// Builds a crate from a tarball, indexing the files whose paths are mapped by `strip_root`
+    /// to a path relative to the root of the crate.
+    fn from_tar(
+        tar_data: &[u8],
+        strip_root: impl Fn(&Path) -> Option<&Path>,
+    ) -> std::io::Result<Self> {
+        let mut archive = tar::Archive::new(tar_data);
 
         let mut data = BytesMut::new();
         let mut files_index = FnvHashMap::default();
@@ -458,11 +652,11 @@ impl TryFrom<CrateTar> for Crate {
                 continue;
             };
 
-            let Ok(path) = path.strip_prefix(&root_dir) else {
+            let Some(path) = strip_root(&path) else {
                 continue;
             };
 
-            let Some(last) = path.components().last() else {
+            let Some(last) = path.components().next_back() else {
                 continue;
             };
 
@@ -486,9 +680,18 @@ impl TryFrom<CrateTar> for Crate {
                 };
 
                 let range = data.len()..data.len() + buffer.len();
+                let mut hasher = FnvHasher::default();
+                hasher.write(&buffer);
 
                 data.extend_from_slice(buffer.as_slice());
-                files_index.insert(path.clone(), CrateFileDataDesc { data_type, range });
+                files_index.insert(
+                    path.clone(),
+                    CrateFileDataDesc {
+                        data_type,
+                        range,
+                        hash: hasher.finish(),
+                    },
+                );
                 let parent = path.parent().map(|p| p.to_path_buf()).unwrap_or_default();
                 directories_index
                     .entry(parent)
@@ -508,7 +711,7 @@ impl TryFrom<CrateTar> for Crate {
 
         let mut subdirectories_index = FnvHashMap::default();
         for key in directories_index.keys() {
-            let Some(last) = key.components().last() else {
+            let Some(last) = key.components().next_back() else {
                 continue;
             };
 
@@ -543,23 +746,200 @@ impl TryFrom<CrateTar> for Crate {
             .map(|(k, v)| (k, v.freeze()))
             .collect();
 
+        let data = data.freeze();
+        let (manifest, manifest_error) = match files_index.get(Path::new("Cargo.toml")) {
+            None => (None, None),
+            Some(desc) => match std::str::from_utf8(&data[desc.range.clone()])
+                .map_err(anyhow::Error::from)
+                .and_then(|content| {
+                    Manifest::parse(content, files_index.keys().map(PathBuf::as_path))
+                }) {
+                Ok(manifest) => (Some(Arc::new(manifest)), None),
+                Err(error) => (None, Some(InvalidManifest(error.to_string()))),
+            },
+        };
+        if let Some(lib_root) = manifest
+            .as_ref()
+            .and_then(|manifest| manifest.lib.as_ref())
+            .and_then(|lib| lib.path.as_deref())
+        {
+            search_index_builder.set_lib_root(lib_root);
+        }
+
         Ok(Self {
-            data: data.freeze(),
+            data,
             files_index: Arc::new(files_index),
             directories_index: Arc::new(directories_index),
             item_search_index: search_index_builder.finish(),
+            manifest,
+            manifest_error,
+            xref_index: Default::default(),
         })
     }
 }
 
+/// The default memory budget of the caches, 1 GiB.
+pub const DEFAULT_MEMORY_BUDGET: usize = 1 << 30;
+
+/// A memory budget shared by several caches.
+///
+/// Each cache is given a share of the limit, charges the size of its entries to the budget and
+/// evicts its own least recently used entries while they exceed its share. Crates, repositories
+/// and GitHub responses thus cannot push each other out, and the budget reports their total.
+#[derive(Debug, Clone)]
+pub struct MemoryBudget {
+    limit: usize,
+    used: Arc<AtomicUsize>,
+}
+
+impl Default for MemoryBudget {
+    fn default() -> Self {
+        Self::new(DEFAULT_MEMORY_BUDGET)
+    }
+}
+
+impl MemoryBudget {
+    /// Creates a budget of `limit` bytes.
+    ///
+    pub fn new(limit: usize) -> Self {
+        MemoryBudget {
+            limit,
+            used: Default::default(),
+        }
+    }
+
+    /// Returns the limit of the budget in bytes.
+    ///
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Returns the number of bytes charged to the budget.
+    ///
+    pub fn used(&self) -> usize {
+        self.used.load(Ordering::Relaxed)
+    }
+
+    fn charge(&self, bytes: usize) {
+        self.used.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    fn release(&self, bytes: usize) {
+        self.used.fetch_sub(bytes, Ordering::Relaxed);
+    }
+
+    /// Returns the bytes of a share of the limit, in percent.
+    pub(crate) fn share(&self, percent: usize) -> usize {
+        self.limit / 100 * percent
+    }
+}
+
+/// A value whose memory usage can be charged to a [`MemoryBudget`].
+pub(crate) trait MemoryUsage {
+    /// Returns the approximate memory used by the value in bytes.
+    fn memory_usage(&self) -> usize;
+}
+
+impl MemoryUsage for Crate {
+    fn memory_usage(&self) -> usize {
+        Crate::memory_usage(self)
+    }
+}
+
+/// An LRU cache whose entries are charged to a share of a [`MemoryBudget`].
+#[derive(Debug)]
+pub(crate) struct BudgetedLru<K: Hash + Eq, V: MemoryUsage> {
+    lru: LruCache<K, V, fnv::FnvBuildHasher>,
+    budget: MemoryBudget,
+    limit: usize,
+    used: usize,
+}
+
+impl<K: Hash + Eq, V: MemoryUsage> BudgetedLru<K, V> {
+    /// Creates a cache of at most `capacity` entries charged to `budget`, using at most `share`
+    /// percent of it.
+    pub(crate) fn new(capacity: NonZeroUsize, budget: MemoryBudget, share: usize) -> Self {
+        BudgetedLru {
+            lru: LruCache::with_hasher(capacity, fnv::FnvBuildHasher::default()),
+            limit: budget.share(share),
+            budget,
+            used: 0,
+        }
+    }
+
+    /// Returns the number of bytes charged by the entries of the cache.
+    #[cfg(test)]
+    pub(crate) fn used(&self) -> usize {
+        self.used
+    }
+
+    /// Returns an entry, marking it as recently used.
+    pub(crate) fn get(&mut self, key: &K) -> Option<&V> {
+        self.lru.get(key)
+    }
+
+    /// Inserts an entry, returning the previous value of the key.
+    ///
+    /// The least recently used entry is evicted when the cache is full, and further ones while the
+    /// share of the budget is exceeded. The new entry itself is kept, so a single entry larger
+    /// than the share still serves repeated requests.
+    pub(crate) fn put(&mut self, key: K, value: V) -> Option<V> {
+        let size = value.memory_usage();
+        self.used += size;
+        self.budget.charge(size);
+        let mut previous = None;
+        if let Some((removed_key, removed)) = self.lru.push(key, value) {
+            self.release(removed.memory_usage());
+            // `push` returns either the replaced value of the key or the evicted entry.
+            if self.lru.contains(&removed_key) {
+                previous = Some(removed);
+            }
+        }
+        while self.used > self.limit && self.lru.len() > 1 {
+            let Some((_, evicted)) = self.lru.pop_lru() else {
+                break;
+            };
+            self.release(evicted.memory_usage());
+        }
+        previous
+    }
+
+    fn release(&mut self, size: usize) {
+        self.used -= size;
+        self.budget.release(size);
+    }
+}
+
+impl<K: Hash + Eq, V: MemoryUsage> Drop for BudgetedLru<K, V> {
+    fn drop(&mut self) {
+        self.budget.release(self.used);
+    }
+}
+
 /// A cache for storing and retrieving `Crate` instances to minimize redundant operations.
 ///
-/// This cache uses a least-recently-used (LRU) strategy and is thread-safe.
+/// This cache uses a least-recently-used (LRU) strategy and is thread-safe. Crates are charged to
+/// a [`MemoryBudget`], which may be shared with the response cache of a
+/// [`GithubClient`](crate::github::GithubClient).
 #[derive(Clone)]
 pub struct CrateCache {
-    lru: Arc<Mutex<LruCache<CrateVersion, Crate, fnv::FnvBuildHasher>>>,
+    lru: Arc<Mutex<BudgetedLru<CrateVersion, Crate>>>,
+    repositories: Arc<Mutex<BudgetedLru<RepositoryCommit, Crate>>>,
+    budget: MemoryBudget,
 }
 
+/// The number of repository snapshots kept in the cache. Repositories are usually much larger
+/// than published crates, so far fewer of them are kept.
+const REPOSITORY_CACHE_CAPACITY: NonZeroUsize = match NonZeroUsize::new(32) {
+    Some(capacity) => capacity,
+    None => unreachable!(),
+};
+
+/// The shares of the memory budget, in percent, of published crates and of repository
+/// snapshots. The rest is left to the GitHub response cache.
+const CRATE_CACHE_SHARE: usize = 60;
+const REPOSITORY_CACHE_SHARE: usize = 30;
+
 impl Default for CrateCache {
     fn default() -> Self {
         Self::new(unsafe { NonZeroUsize::new_unchecked(2048) })
@@ -570,14 +950,34 @@ impl CrateCache {
     /// Creates a new `CrateCache` with a specified capacity.
     ///
     pub fn new(capacity: NonZeroUsize) -> Self {
+        Self::with_memory_budget(capacity, MemoryBudget::default())
+    }
+
+    /// Creates a new `CrateCache` with a specified capacity, charging its crates to a memory
+    /// budget.
+    ///
+    pub fn with_memory_budget(capacity: NonZeroUsize, budget: MemoryBudget) -> Self {
         CrateCache {
-            lru: Arc::new(Mutex::new(LruCache::with_hasher(
+            lru: Arc::new(Mutex::new(BudgetedLru::new(
                 capacity,
-                fnv::FnvBuildHasher::default(),
+                budget.clone(),
+                CRATE_CACHE_SHARE,
             ))),
+            repositories: Arc::new(Mutex::new(BudgetedLru::new(
+                REPOSITORY_CACHE_CAPACITY.min(capacity),
+                budget.clone(),
+                REPOSITORY_CACHE_SHARE,
+            ))),
+            budget,
         }
     }
 
+    /// Returns the memory budget of the cache.
+    ///
+    pub fn memory_budget(&self) -> &MemoryBudget {
+        &self.budget
+    }
+
     /// Retrieves a crate from the cache if it exists.
     ///
     pub fn get_crate(&self, crate_version: &CrateVersion) -> Option<Crate> {
@@ -593,4 +993,16 @@ impl CrateCache {
     ) -> Option<Crate> {
         self.lru.lock().put(crate_version.into(), krate.into())
     }
+
+    /// Retrieves the snapshot of a GitHub repository at a commit from the cache if it exists.
+    ///
+    pub fn get_repository(&self, commit: &RepositoryCommit) -> Option<Crate> {
+        self.repositories.lock().get(commit).cloned()
+    }
+
+    /// Inserts or updates the snapshot of a GitHub repository at a commit in the cache.
+    ///
+    pub fn set_repository(&self, commit: RepositoryCommit, krate: Crate) -> Option<Crate> {
+        self.repositories.lock().put(commit, krate)
+    }
 }
@@ -0,0 +1,436 @@
+//! The `cfg` module.
+//!
+//! Provides a representation of `#[cfg(...)]` predicates and their evaluation against a set of
+//! enabled cargo features and a target triple, so that items which do not exist under a given
+//! configuration can be filtered out of search results.
+//!
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Expr, Lit, Meta, Token};
+
+/// A `cfg` predicate, such as `all(unix, feature = "net")`.
+///
+/// Serialized as its source form.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum Cfg {
+    /// `all(...)`, true if all of the predicates are true.
+    All(Vec<Cfg>),
+    /// `any(...)`, true if any of the predicates is true.
+    Any(Vec<Cfg>),
+    /// `not(...)`, true if the predicate is false.
+    Not(Box<Cfg>),
+    /// A configuration name, such as `unix` or `docsrs`.
+    Name(String),
+    /// A configuration key-value pair, such as `feature = "net"`.
+    KeyValue(String, String),
+}
+
+impl Cfg {
+    /// Parses a predicate from the meta inside `cfg(...)`.
+    ///
+    pub fn from_meta(meta: &Meta) -> syn::Result<Self> {
+        let name = meta
+            .path()
+            .get_ident()
+            .map(|ident| ident.to_string())
+            .ok_or_else(|| syn::Error::new_spanned(meta.path(), "expected an identifier"))?;
+        match meta {
+            Meta::Path(_) => Ok(Cfg::Name(name)),
+            Meta::NameValue(name_value) => match &name_value.value {
+                Expr::Lit(expr) => match &expr.lit {
+                    Lit::Str(value) => Ok(Cfg::KeyValue(name, value.value())),
+                    lit => Err(syn::Error::new_spanned(lit, "expected a string literal")),
+                },
+                expr => Err(syn::Error::new_spanned(expr, "expected a string literal")),
+            },
+            Meta::List(list) => {
+                let nested = list
+                    .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?
+                    .iter()
+                    .map(Cfg::from_meta)
+                    .collect::<syn::Result<Vec<_>>>()?;
+                match name.as_str() {
+                    "all" => Ok(Cfg::All(nested)),
+                    "any" => Ok(Cfg::Any(nested)),
+                    "not" if nested.len() == 1 => {
+                        Ok(Cfg::Not(Box::new(nested.into_iter().next().unwrap())))
+                    }
+                    _ => Err(syn::Error::new_spanned(list, "unknown cfg operator")),
+                }
+            }
+        }
+    }
+
+    /// Collects the predicates an item is gated by from its attributes.
+    ///
+    /// Both `#[cfg(...)]` and the documented requirements of `#[doc(cfg(...))]`, including
+    /// `#[cfg_attr(docsrs, doc(cfg(...)))]`, are taken into account. Returns `None` if the item
+    /// is unconditional.
+    ///
+    pub fn from_attributes(attrs: &[Attribute]) -> Option<Self> {
+        let mut predicates = BTreeSet::new();
+        for attr in attrs {
+            if attr.path().is_ident("cfg") {
+                if let Ok(cfg) = attr
+                    .parse_args::<Meta>()
+                    .and_then(|meta| Cfg::from_meta(&meta))
+                {
+                    predicates.insert(cfg);
+                }
+            } else if attr.path().is_ident("doc") {
+                if let Ok(meta) = attr.parse_args::<Meta>() {
+                    predicates.extend(doc_cfg(&meta));
+                }
+            } else if attr.path().is_ident("cfg_attr") {
+                let Ok(args) =
+                    attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                else {
+                    continue;
+                };
+                for meta in args.iter().skip(1) {
+                    if let Meta::List(list) = meta {
+                        if list.path.is_ident("doc") {
+                            if let Ok(meta) = list.parse_args::<Meta>() {
+                                predicates.extend(doc_cfg(&meta));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Cfg::all(predicates)
+    }
+
+    /// Combines predicates into one, flattening nested `all(...)`.
+    ///
+    /// Returns `None` if there are no predicates.
+    ///
+    pub fn all(predicates: impl IntoIterator<Item = Cfg>) -> Option<Self> {
+        let mut flattened = BTreeSet::new();
+        for predicate in predicates {
+            match predicate {
+                Cfg::All(nested) => flattened.extend(nested),
+                predicate => {
+                    flattened.insert(predicate);
+                }
+            }
+        }
+        let mut flattened = flattened.into_iter().collect::<Vec<_>>();
+        match flattened.len() {
+            0 => None,
+            1 => flattened.pop(),
+            _ => Some(Cfg::All(flattened)),
+        }
+    }
+
+    /// Evaluates the predicate under a configuration.
+    ///
+    /// Returns `None` if the result cannot be determined, for example when the predicate depends
+    /// on the target but no target was specified.
+    ///
+    pub fn eval(&self, config: &CfgConfig) -> Option<bool> {
+        match self {
+            Cfg::All(predicates) => {
+                let mut result = Some(true);
+                for predicate in predicates {
+                    match predicate.eval(config) {
+                        Some(false) => return Some(false),
+                        None => result = None,
+                        Some(true) => {}
+                    }
+                }
+                result
+            }
+            Cfg::Any(predicates) => {
+                let mut result = Some(false);
+                for predicate in predicates {
+                    match predicate.eval(config) {
+                        Some(true) => return Some(true),
+                        None => result = None,
+                        Some(false) => {}
+                    }
+                }
+                result
+            }
+            Cfg::Not(predicate) => predicate.eval(config).map(|value| !value),
+            Cfg::Name(name) => config.name(name),
+            Cfg::KeyValue(key, value) => config.key_value(key, value),
+        }
+    }
+}
+
+/// Extracts the predicate of `doc(cfg(...))`.
+fn doc_cfg(meta: &Meta) -> Option<Cfg> {
+    let Meta::List(list) = meta else {
+        return None;
+    };
+    if !list.path.is_ident("cfg") {
+        return None;
+    }
+    let meta = list.parse_args::<Meta>().ok()?;
+    Cfg::from_meta(&meta).ok()
+}
+
+impl Display for Cfg {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fn list(f: &mut Formatter<'_>, op: &str, predicates: &[Cfg]) -> std::fmt::Result {
+            write!(f, "{op}(")?;
+            for (i, predicate) in predicates.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{predicate}")?;
+            }
+            write!(f, ")")
+        }
+
+        match self {
+            Cfg::All(predicates) => list(f, "all", predicates),
+            Cfg::Any(predicates) => list(f, "any", predicates),
+            Cfg::Not(predicate) => write!(f, "not({predicate})"),
+            Cfg::Name(name) => write!(f, "{name}"),
+            Cfg::KeyValue(key, value) => write!(f, "{key} = {value:?}"),
+        }
+    }
+}
+
+impl FromStr for Cfg {
+    type Err = syn::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Cfg::from_meta(&syn::parse_str::<Meta>(s)?)
+    }
+}
+
+impl Serialize for Cfg {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Cfg {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// The configuration `cfg` predicates are evaluated against.
+///
+/// Unset parts of the configuration are unknown, so predicates depending on them do not
+/// filter anything out.
+#[derive(Debug, Clone, Default)]
+pub struct CfgConfig {
+    /// The enabled cargo features, or `None` if unknown.
+    pub features: Option<BTreeSet<String>>,
+    /// The target, or `None` if unknown.
+    pub target: Option<TargetCfg>,
+}
+
+impl CfgConfig {
+    /// Returns whether the configuration is entirely unknown.
+    ///
+    pub fn is_unknown(&self) -> bool {
+        self.features.is_none() && self.target.is_none()
+    }
+
+    fn name(&self, name: &str) -> Option<bool> {
+        match name {
+            "unix" | "windows" => self
+                .target
+                .as_ref()
+                .map(|target| target.family.iter().any(|family| family == name)),
+            "debug_assertions" | "panic" | "overflow_checks" => None,
+            // Custom cfgs such as `docsrs`, `test` or `tokio_unstable` are not set by a normal build.
+            _ => Some(false),
+        }
+    }
+
+    fn key_value(&self, key: &str, value: &str) -> Option<bool> {
+        if key == "feature" {
+            return self
+                .features
+                .as_ref()
+                .map(|features| features.contains(value));
+        }
+
+        let target = self.target.as_ref();
+        match key {
+            "target_arch" => target.map(|t| t.arch == value),
+            "target_os" => target.map(|t| t.os == value),
+            "target_family" => target.map(|t| t.family.iter().any(|f| f == value)),
+            "target_env" => target.map(|t| t.env == value),
+            "target_vendor" => target.map(|t| t.vendor == value),
+            "target_pointer_width" => target.map(|t| t.pointer_width == value),
+            "target_endian" => target.map(|t| t.endian == value),
+            "target_has_atomic" | "target_feature" | "panic" => None,
+            _ => Some(false),
+        }
+    }
+}
+
+/// The `cfg` values derived from a target triple, e.g. `x86_64-unknown-linux-gnu`.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TargetCfg {
+    /// The value of `target_arch`.
+    pub arch: String,
+    /// The value of `target_vendor`.
+    pub vendor: String,
+    /// The value of `target_os`.
+    pub os: String,
+    /// The value of `target_env`.
+    pub env: String,
+    /// The values of `target_family`.
+    pub family: Vec<String>,
+    /// The value of `target_pointer_width`.
+    pub pointer_width: String,
+    /// The value of `target_endian`.
+    pub endian: String,
+}
+
+/// The error of parsing a malformed target triple.
+///
+#[derive(Debug)]
+pub struct InvalidTarget(pub String);
+
+impl Display for InvalidTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid target triple: '{}'", self.0)
+    }
+}
+
+impl std::error::Error for InvalidTarget {}
+
+impl FromStr for TargetCfg {
+    type Err = InvalidTarget;
+
+    fn from_str(triple: &str) -> Result<Self, Self::Err> {
+        let parts = triple.trim().split('-').collect::<Vec<_>>();
+        if parts.len() < 2 || parts.iter().any(|part| part.is_empty()) {
+            return Err(InvalidTarget(triple.to_string()));
+        }
+
+        let arch = match parts[0] {
+            "i386" | "i586" | "i686" => "x86",
+            arch if arch.starts_with("armv") || arch.starts_with("thumbv") => "arm",
+            arch if arch.starts_with("riscv64") => "riscv64",
+            arch if arch.starts_with("riscv32") => "riscv32",
+            "arm64" | "arm64e" => "aarch64",
+            arch => arch,
+        };
+
+        // The triple is `arch-vendor-os[-env]`, except for a few well-known `arch-os-env` triples.
+        let (vendor, os, env) = match &parts[1..] {
+            [os, env] if *os == "linux" => ("unknown", *os, *env),
+            [os, _] if *os == "none" => ("unknown", *os, ""),
+            [vendor, os] => (*vendor, *os, ""),
+            [vendor, os, env, ..] => (*vendor, *os, *env),
+            [os] => ("unknown", *os, ""),
+            [] => unreachable!(),
+        };
+        let (os, env) = match (os, env) {
+            ("darwin", env) => ("macos", env),
+            ("linux", "android" | "androideabi") => ("android", ""),
+            ("wasip1" | "wasip2", env) => ("wasi", env),
+            ("unknown", _) if arch.starts_with("wasm") => ("unknown", ""),
+            (os, env) => (os, env),
+        };
+
+        let family = match os {
+            "windows" => vec!["windows"],
+            "wasi" | "unknown" if arch.starts_with("wasm") => vec!["wasm"],
+            "emscripten" => vec!["unix", "wasm"],
+            "none" | "uefi" | "unknown" | "cuda" => vec![],
+            _ => vec!["unix"],
+        };
+
+        let pointer_width = match arch {
+            "x86_64" | "aarch64" | "riscv64" | "powerpc64" | "s390x" | "mips64" | "sparc64"
+            | "loongarch64" | "wasm64" | "nvptx64" => "64",
+            "avr" | "msp430" => "16",
+            _ => "32",
+        };
+        let endian = match parts[0] {
+            "powerpc" | "powerpc64" | "s390x" | "mips" | "mips64" | "sparc64" | "aarch64_be" => {
+                "big"
+            }
+            _ => "little",
+        };
+
+        Ok(Self {
+            arch: arch.to_string(),
+            vendor: vendor.to_string(),
+            os: os.to_string(),
+            env: env.to_string(),
+            family: family.into_iter().map(String::from).collect(),
+            pointer_width: pointer_width.to_string(),
+            endian: endian.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eval_cfg() -> anyhow::Result<()> {
+        let cfg: Cfg =
+            r#"all(feature = "net", any(unix, target_os = "wasi"), not(docsrs))"#.parse()?;
+        assert_eq!(
+            cfg.to_string(),
+            r#"all(feature = "net", any(unix, target_os = "wasi"), not(docsrs))"#
+        );
+
+        let linux = CfgConfig {
+            features: Some(BTreeSet::from(["net".to_string()])),
+            target: Some("x86_64-unknown-linux-gnu".parse()?),
+        };
+        assert_eq!(cfg.eval(&linux), Some(true));
+
+        let windows = CfgConfig {
+            target: Some("x86_64-pc-windows-msvc".parse()?),
+            ..linux.clone()
+        };
+        assert_eq!(cfg.eval(&windows), Some(false));
+
+        let unknown_target = CfgConfig {
+            target: None,
+            ..linux
+        };
+        assert_eq!(cfg.eval(&unknown_target), None);
+
+        let no_features = CfgConfig {
+            features: Some(BTreeSet::new()),
+            target: None,
+        };
+        assert_eq!(cfg.eval(&no_features), Some(false));
+        Ok(())
+    }
+
+    #[test]
+    fn parse_target_triples() -> anyhow::Result<()> {
+        let macos: TargetCfg = "aarch64-apple-darwin".parse()?;
+        assert_eq!(
+            (macos.arch.as_str(), macos.os.as_str()),
+            ("aarch64", "macos")
+        );
+        assert_eq!(macos.family, vec!["unix"]);
+
+        let android: TargetCfg = "armv7-linux-androideabi".parse()?;
+        assert_eq!(
+            (android.arch.as_str(), android.os.as_str()),
+            ("arm", "android")
+        );
+        assert_eq!(android.pointer_width, "32");
+
+        let wasm: TargetCfg = "wasm32-unknown-unknown".parse()?;
+        assert_eq!(wasm.family, vec!["wasm"]);
+        assert!("x86_64".parse::<TargetCfg>().is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,541 @@
+//! Comparison of two versions of a crate.
+//!
+//! The public API of each version is extracted from its item index: every exported item, keyed by
+//! its path from the library root, plus the items made reachable by `pub use` re-exports. The two
+//! APIs are compared item by item and each change is classified against the Cargo semver rules.
+//!
+//! The files of the two versions can also be compared directly, listing the added, removed and
+//! modified files and producing unified diffs of single files.
+//!
+use crate::cache::{Crate, FileDataType};
+use crate::cfg::Cfg;
+use crate::search::{PublicApi, SearchIndex};
+use crate::{Item, ItemType};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::num::NonZeroUsize;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+#[cfg(feature = "utoipa")]
+use utoipa::ToSchema;
+
+/// The differences between the public APIs of two versions of a crate.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct ApiDiff {
+    /// The changed items, sorted by path.
+    pub changes: Vec<ApiChange>,
+    /// The changed re-exports of names that are not items of the crate, e.g. items of dependencies.
+    pub reexports: Vec<ReExportChange>,
+    /// The smallest version bump that is compatible with the changes.
+    pub required_bump: SemverBump,
+    /// The version bump between the two compared versions, if both are valid semver versions.
+    pub actual_bump: Option<SemverBump>,
+    /// Whether the actual version bump is smaller than the required one.
+    pub semver_violation: bool,
+}
+
+/// A change to an item of the public API.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct ApiChange {
+    /// The kind of change.
+    pub kind: ChangeKind,
+    /// The type of the item.
+    #[serde(rename = "type")]
+    pub type_: ItemType,
+    /// The path of the item in the old version, or in the new version for added items.
+    ///
+    /// Impl blocks are identified by the trait as written and the canonical path of their self
+    /// type, e.g. `impl fmt::Display for crate::error::Error`.
+    pub path: String,
+    /// The item in the old version. There may be several if the item has `cfg`-gated variants.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub old: Vec<ApiItem>,
+    /// The item in the new version. There may be several if the item has `cfg`-gated variants.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub new: Vec<ApiItem>,
+    /// Whether the change is breaking, requiring a major version bump.
+    pub breaking: bool,
+    /// The reasons for the classification, one per changed member.
+    pub reasons: Vec<String>,
+}
+
+/// An item of the public API in one version of a crate.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct ApiItem {
+    /// The path of the item.
+    pub path: String,
+    /// The normalized signature of the item.
+    pub signature: String,
+    /// The file containing the item.
+    #[cfg_attr(feature = "utoipa", schema(value_type = String))]
+    pub file: Arc<Path>,
+    /// The range of lines of the item.
+    #[cfg_attr(feature = "utoipa", schema(value_type = crate::RangeSchema))]
+    pub line_range: RangeInclusive<NonZeroUsize>,
+    /// The `cfg` predicate of the item, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Option<String>))]
+    pub cfg: Option<Cfg>,
+}
+
+/// A change to a re-export of a name that is not an item of the crate.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct ReExportChange {
+    /// The kind of change.
+    pub kind: ChangeKind,
+    /// The path the name is exported as.
+    pub path: String,
+    /// The re-exported path in the old version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old_source: Option<String>,
+    /// The re-exported path in the new version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_source: Option<String>,
+    /// Whether the change is breaking.
+    pub breaking: bool,
+}
+
+/// The kind of change to an item.
+///
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub enum ChangeKind {
+    /// The item only exists in the new version.
+    Added,
+    /// The item only exists in the old version.
+    Removed,
+    /// The item exists in both versions with different signatures.
+    Changed,
+    /// The item exists in both versions under different paths.
+    Moved,
+}
+
+/// The level of a version bump, following Cargo's semver compatibility rules.
+///
+/// For `0.x.y` versions, bumping `x` is a major bump and bumping `y` is a minor bump.
+///
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub enum SemverBump {
+    /// Only bug fixes, no API changes.
+    Patch,
+    /// Backwards compatible API additions.
+    Minor,
+    /// Breaking changes.
+    Major,
+}
+
+impl SemverBump {
+    /// Computes the bump level between two versions.
+    ///
+    pub fn between(old: &semver::Version, new: &semver::Version) -> Self {
+        match (old.major, old.minor) {
+            _ if old.major != new.major => SemverBump::Major,
+            (0, _) if old.minor != new.minor => SemverBump::Major,
+            // Every `0.0.z` release is incompatible with the previous one.
+            (0, 0) if old.patch != new.patch => SemverBump::Major,
+            (0, _) => SemverBump::Minor,
+            _ if old.minor != new.minor => SemverBump::Minor,
+            _ => SemverBump::Patch,
+        }
+    }
+}
+
+/// Compares the public APIs of two versions of a crate.
+///
+/// The versions are used to compute the actual version bump; pass `None` if they are unknown.
+///
+pub fn diff_api(
+    old: &SearchIndex,
+    new: &SearchIndex,
+    versions: Option<(&semver::Version, &semver::Version)>,
+) -> ApiDiff {
+    let old_api = PublicApi::new(old);
+    let new_api = PublicApi::new(new);
+
+    let mut changes = Vec::new();
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+    for (key, old_items) in &old_api.items {
+        match new_api.items.get(key) {
+            None => removed.push((key, old_items)),
+            Some(new_items) => {
+                if signatures(old_items) == signatures(new_items) {
+                    continue;
+                }
+                let (breaking, reasons) = classify(key.0, old_items, new_items);
+                changes.push(ApiChange {
+                    kind: ChangeKind::Changed,
+                    type_: key.0,
+                    path: key.1.clone(),
+                    old: api_items(&key.1, old_items),
+                    new: api_items(&key.1, new_items),
+                    breaking,
+                    reasons,
+                });
+            }
+        }
+    }
+    for (key, new_items) in &new_api.items {
+        if !old_api.items.contains_key(key) {
+            added.push((key, new_items));
+        }
+    }
+
+    // Items removed from one path and added under another with the same name and signature have
+    // been moved; the move is breaking since the old path is gone.
+    let mut moved_to = BTreeSet::new();
+    for (key, old_items) in removed {
+        let name = key.1.rsplit("::").next().unwrap_or_default();
+        let moved = added.iter().find(|((type_, path), new_items)| {
+            *type_ == key.0
+                && !moved_to.contains(path)
+                && path.rsplit("::").next() == Some(name)
+                && signatures(old_items) == signatures(new_items)
+        });
+        match moved {
+            Some(((_, path), new_items)) => {
+                moved_to.insert(path.clone());
+                changes.push(ApiChange {
+                    kind: ChangeKind::Moved,
+                    type_: key.0,
+                    path: key.1.clone(),
+                    old: api_items(&key.1, old_items),
+                    new: api_items(path, new_items),
+                    breaking: true,
+                    reasons: vec![format!("moved to `{path}`, the old path no longer exists")],
+                });
+            }
+            None => changes.push(ApiChange {
+                kind: ChangeKind::Removed,
+                type_: key.0,
+                path: key.1.clone(),
+                old: api_items(&key.1, old_items),
+                new: Vec::new(),
+                breaking: true,
+                reasons: vec!["removed from the public API".to_string()],
+            }),
+        }
+    }
+    for (key, new_items) in added {
+        if moved_to.contains(&key.1) {
+            continue;
+        }
+        // An item that is also exported under a path that existed before has only gained a
+        // new path, e.g. it was moved and re-exported under its old path.
+        let previous = new_api.items.iter().find(|(other, items)| {
+            other.0 == key.0
+                && other.1 != key.1
+                && old_api.items.contains_key(*other)
+                && items.iter().any(|item| {
+                    new_items
+                        .iter()
+                        .any(|new_item| std::ptr::eq(*item, *new_item))
+                })
+        });
+        let (kind, reasons) = match previous {
+            Some(((_, path), _)) => (
+                ChangeKind::Moved,
+                vec![format!("also exported as `{path}`, which still exists")],
+            ),
+            None => (
+                ChangeKind::Added,
+                vec!["added to the public API".to_string()],
+            ),
+        };
+        changes.push(ApiChange {
+            kind,
+            type_: key.0,
+            path: key.1.clone(),
+            old: Vec::new(),
+            new: api_items(&key.1, new_items),
+            breaking: false,
+            reasons,
+        });
+    }
+    changes.sort_by(|a, b| (&a.path, a.type_).cmp(&(&b.path, b.type_)));
+
+    let mut reexports = Vec::new();
+    for (path, old) in &old_api.reexports {
+        match new_api.reexports.get(path) {
+            Some(new) if new.source == old.source => {}
+            Some(new) => reexports.push(ReExportChange {
+                kind: ChangeKind::Changed,
+                path: path.clone(),
+                old_source: Some(old.source.clone()),
+                new_source: Some(new.source.clone()),
+                // The name may refer to a different item now.
+                breaking: true,
+            }),
+            None => reexports.push(ReExportChange {
+                kind: ChangeKind::Removed,
+                path: path.clone(),
+                old_source: Some(old.source.clone()),
+                new_source: None,
+                breaking: !new_api.items.keys().any(|(_, p)| p == path),
+            }),
+        }
+    }
+    for (path, new) in &new_api.reexports {
+        if !old_api.reexports.contains_key(path) {
+            reexports.push(ReExportChange {
+                kind: ChangeKind::Added,
+                path: path.clone(),
+                old_source: None,
+                new_source: Some(new.source.clone()),
+                breaking: false,
+            });
+        }
+    }
+
+    let required_bump =
+        if changes.iter().any(|c| c.breaking) || reexports.iter().any(|r| r.breaking) {
+            SemverBump::Major
+        } else if !changes.is_empty() || !reexports.is_empty() {
+            SemverBump::Minor
+        } else {
+            SemverBump::Patch
+        };
+    let actual_bump = versions.map(|(old, new)| SemverBump::between(old, new));
+
+    ApiDiff {
+        changes,
+        reexports,
+        required_bump,
+        actual_bump,
+        semver_violation: actual_bump.is_some_and(|actual| actual < required_bump),
+    }
+}
+
+fn api_items(path: &str, items: &[&Item]) -> Vec<ApiItem> {
+    items
+        .iter()
+        .map(|item| ApiItem {
+            path: path.to_string(),
+            signature: item.signature.clone().unwrap_or_default(),
+            file: item.file.clone(),
+            line_range: item.line_range.clone(),
+            cfg: item.cfg.clone(),
+        })
+        .collect()
+}
+
+/// The signatures of the `cfg` variants of an item.
+fn signatures<'i>(items: &[&'i Item]) -> BTreeSet<(Option<String>, &'i str)> {
+    items
+        .iter()
+        .map(|item| {
+            (
+                item.cfg.as_ref().map(ToString::to_string),
+                item.signature.as_deref().unwrap_or_default(),
+            )
+        })
+        .collect()
+}
+
+/// Classifies a change to the signature of an item, returning whether it is breaking and why.
+fn classify(type_: ItemType, old: &[&Item], new: &[&Item]) -> (bool, Vec<String>) {
+    if old.len() != 1 || new.len() != 1 {
+        return (
+            true,
+            vec!["the `cfg`-gated variants of the item changed".to_string()],
+        );
+    }
+    let old = old[0].signature.as_deref().unwrap_or_default();
+    let new = new[0].signature.as_deref().unwrap_or_default();
+    let (old_header, old_members) = split_signature(old);
+    let (new_header, new_members) = split_signature(new);
+
+    let mut breaking = false;
+    let mut reasons = Vec::new();
+    let old_non_exhaustive = old_header.starts_with("#[non_exhaustive]");
+    if old_header != new_header {
+        let relaxed =
+            old_non_exhaustive && old_header.trim_start_matches("#[non_exhaustive] ") == new_header;
+        breaking |= !relaxed;
+        reasons.push(format!(
+            "signature changed from `{old_header}` to `{new_header}`"
+        ));
+    }
+
+    // Adding members is only breaking if users can exhaustively match or construct the item.
+    let private_fields = old_members.contains_key("..");
+    for (name, old_member) in &old_members {
+        match new_members.get(name) {
+            None if *name == ".." => {
+                reasons.push("all fields are public now".to_string());
+            }
+            None => {
+                breaking = true;
+                reasons.push(format!("`{name}` was removed"));
+            }
+            Some(new_member) if new_member == old_member => {}
+            Some(new_member) => {
+                let provided = type_ == ItemType::Trait
+                    && old_member.ends_with(';')
+                    && new_member.ends_with(" { .. }")
+                    && old_member.trim_end_matches(';') == new_member.trim_end_matches(" { .. }");
+                breaking |= !provided;
+                reasons.push(format!(
+                    "`{name}` changed from `{old_member}` to `{new_member}`"
+                ));
+            }
+        }
+    }
+    for (name, new_member) in &new_members {
+        if old_members.contains_key(name) {
+            continue;
+        }
+        let (is_breaking, reason) = match type_ {
+            ItemType::Struct if *name == ".." => (true, "the struct has private fields now".into()),
+            ItemType::Struct if old_non_exhaustive || private_fields => {
+                (false, format!("field `{name}` was added"))
+            }
+            ItemType::Struct => (
+                true,
+                format!("field `{name}` was added to a struct that could be constructed"),
+            ),
+            ItemType::Enum if old_non_exhaustive => (false, format!("variant `{name}` was added")),
+            ItemType::Enum => (
+                true,
+                format!("variant `{name}` was added to an exhaustive enum"),
+            ),
+            ItemType::Trait if new_member.ends_with(';') => (
+                true,
+                format!("required item `{name}` was added to the trait"),
+            ),
+            _ => (false, format!("`{name}` was added")),
+        };
+        breaking |= is_breaking;
+        reasons.push(reason);
+    }
+    (breaking, reasons)
+}
+
+/// Splits a signature into its header and its members, keyed by member name.
+fn split_signature(signature: &str) -> (&str, BTreeMap<&str, &str>) {
+    let mut lines = signature.lines();
+    let header = lines.next().unwrap_or_default();
+    let members = lines.map(|line| (member_name(line), line)).collect();
+    (header, members)
+}
+
+/// Extracts the name of a member from its line in a signature, e.g. `len` for `pub fn len(&self)`.
+fn member_name(line: &str) -> &str {
+    const QUALIFIERS: &[&str] = &[
+        "pub", "const", "async", "unsafe", "extern", "fn", "type", "default",
+    ];
+    let mut rest = line;
+    loop {
+        let word = rest.split_whitespace().next().unwrap_or_default();
+        if QUALIFIERS.contains(&word) || word.starts_with('"') {
+            rest = rest.trim_start()[word.len()..].trim_start();
+        } else {
+            break;
+        }
+    }
+    let end = rest
+        .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+        .unwrap_or(rest.len());
+    &rest[..end]
+}
+
+/// The differences between the files of two versions of a crate.
+///
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct FileDiff {
+    /// The files only present in the new version.
+    #[cfg_attr(feature = "utoipa", schema(value_type = Vec<String>))]
+    pub added: Vec<PathBuf>,
+    /// The files only present in the old version.
+    #[cfg_attr(feature = "utoipa", schema(value_type = Vec<String>))]
+    pub removed: Vec<PathBuf>,
+    /// The files present in both versions with different contents.
+    #[cfg_attr(feature = "utoipa", schema(value_type = Vec<String>))]
+    pub modified: Vec<PathBuf>,
+    /// The number of files that are identical in both versions.
+    pub unchanged: usize,
+}
+
+/// Compares the files of two versions of a crate.
+///
+/// If `directory` is specified, only the files within that directory are compared.
+///
+pub fn diff_files(old: &Crate, new: &Crate, directory: Option<&Path>) -> FileDiff {
+    let in_directory = |path: &Path| directory.is_none_or(|directory| path.starts_with(directory));
+    let mut diff = FileDiff::default();
+    for (path, desc) in old.files().filter(|(path, _)| in_directory(path)) {
+        match new.get_file_desc(path) {
+            None => diff.removed.push(path.to_path_buf()),
+            Some(new_desc) if old.is_same_file(desc, new, new_desc) => diff.unchanged += 1,
+            Some(_) => diff.modified.push(path.to_path_buf()),
+        }
+    }
+    for (path, _) in new.files().filter(|(path, _)| in_directory(path)) {
+        if old.get_file_desc(path).is_none() {
+            diff.added.push(path.to_path_buf());
+        }
+    }
+    diff.added.sort();
+    diff.removed.sort();
+    diff.modified.sort();
+    diff
+}
+
+/// Produces a unified diff of a file between two versions of a crate.
+///
+/// A file missing from one of the versions is compared against an empty file. Returns `None` if the
+/// file exists in neither version, and an empty string if the file is unchanged.
+///
+pub fn diff_file(old: &Crate, new: &Crate, path: &Path, context: usize) -> Option<String> {
+    let old_desc = old.get_file_desc(path);
+    let new_desc = new.get_file_desc(path);
+    let name = path.display();
+    match (old_desc, new_desc) {
+        (None, None) => return None,
+        (Some(old_desc), Some(new_desc)) if old.is_same_file(old_desc, new, new_desc) => {
+            return Some(String::new())
+        }
+        _ => {}
+    }
+    let binary = [old_desc, new_desc]
+        .into_iter()
+        .flatten()
+        .any(|desc| desc.data_type == FileDataType::NonUtf8);
+    if binary {
+        return Some(format!("Binary files a/{name} and b/{name} differ\n"));
+    }
+
+    let old_content = old_desc.map(|desc| old.file_data(desc)).unwrap_or_default();
+    let new_content = new_desc.map(|desc| new.file_data(desc)).unwrap_or_default();
+    let old_text = std::str::from_utf8(&old_content).unwrap_or_default();
+    let new_text = std::str::from_utf8(&new_content).unwrap_or_default();
+    let old_header = match old_desc {
+        Some(_) => format!("a/{name}"),
+        None => "/dev/null".to_string(),
+    };
+    let new_header = match new_desc {
+        Some(_) => format!("b/{name}"),
+        None => "/dev/null".to_string(),
+    };
+    Some(
+        similar::TextDiff::from_lines(old_text, new_text)
+            .unified_diff()
+            .context_radius(context)
+            .header(&old_header, &new_header)
+            .to_string(),
+    )
+}
@@ -5,8 +5,12 @@
 //! of making network requests, handling responses, and processing the downloaded data.
 //!
 use crate::CrateVersion;
-use reqwest::{Client, ClientBuilder};
+use reqwest::{Client, ClientBuilder, StatusCode};
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::io::Read;
+use std::sync::Arc;
 
 /// The `CrateDownloader` struct, responsible for downloading crate files from the internet.
 ///
@@ -74,4 +78,213 @@ impl CrateDownloader {
 
         Ok(data)
     }
+
+    /// Asynchronously fetches all published versions of a crate from the crates.io sparse index.
+    ///
+    /// Returns `None` if the crate does not exist in the registry or its name is not a valid crate name.
+    ///
+    pub async fn get_index_entries(&self, krate: &str) -> anyhow::Result<Option<Vec<IndexEntry>>> {
+        let Some(path) = index_path(krate) else {
+            return Ok(None);
+        };
+        let url = format!("https://index.crates.io/{path}");
+        let resp = self.client.get(url).send().await?;
+        let status = resp.status();
+        if status == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !status.is_success() {
+            anyhow::bail!("Http status is not 200: {}", resp.text().await?);
+        }
+
+        let text = resp.text().await?;
+        let entries = text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str::<IndexEntry>)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Some(entries))
+    }
+
+    /// Asynchronously fetches the registry index entry of a specific crate version.
+    ///
+    pub async fn get_index_entry(
+        &self,
+        crate_version: &CrateVersion,
+    ) -> anyhow::Result<Option<IndexEntry>> {
+        Ok(self
+            .get_index_entries(&crate_version.krate)
+            .await?
+            .and_then(|entries| {
+                entries
+                    .into_iter()
+                    .find(|entry| entry.vers.as_ref() == crate_version.version.as_ref())
+            }))
+    }
+
+    /// Asynchronously resolves a version requirement to the highest matching, non-yanked version of a crate.
+    ///
+    pub async fn resolve_version(
+        &self,
+        krate: &str,
+        req: &VersionReq,
+    ) -> anyhow::Result<Option<CrateVersion>> {
+        let Some(entries) = self.get_index_entries(krate).await? else {
+            return Ok(None);
+        };
+        Ok(max_matching_version(&entries, req).map(|entry| entry.crate_version()))
+    }
+}
+
+/// The maximum length of a crate name on crates.io.
+pub const MAX_CRATE_NAME_LENGTH: usize = 64;
+
+/// Checks whether a name is a valid crate name: at most 64 ASCII letters, digits, `_` and `-`.
+///
+pub fn is_valid_crate_name(krate: &str) -> bool {
+    !krate.is_empty()
+        && krate.len() <= MAX_CRATE_NAME_LENGTH
+        && krate
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+}
+
+/// Computes the path of a crate within the registry index.
+///
+/// Returns `None` if the name is not a valid crate name.
+/// See <https://doc.rust-lang.org/cargo/reference/registry-index.html#index-files>.
+///
+pub fn index_path(krate: &str) -> Option<String> {
+    if !is_valid_crate_name(krate) {
+        return None;
+    }
+    let name = krate.to_lowercase();
+    Some(match name.len() {
+        1 => format!("1/{name}"),
+        2 => format!("2/{name}"),
+        3 => format!("3/{}/{name}", &name[..1]),
+        _ => format!("{}/{}/{name}", &name[..2], &name[2..4]),
+    })
+}
+
+/// Selects the highest non-yanked entry whose version satisfies the requirement.
+///
+pub fn max_matching_version<'e>(
+    entries: &'e [IndexEntry],
+    req: &VersionReq,
+) -> Option<&'e IndexEntry> {
+    entries
+        .iter()
+        .filter(|entry| !entry.yanked)
+        .filter_map(|entry| Some((Version::parse(&entry.vers).ok()?, entry)))
+        .filter(|(version, _)| req.matches(version))
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, entry)| entry)
+}
+
+/// Represents a single crate version in the registry index.
+///
+/// This mirrors one line of a crate's index file, as described in
+/// <https://doc.rust-lang.org/cargo/reference/registry-index.html#json-schema>.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexEntry {
+    /// The name of the crate.
+    pub name: Arc<str>,
+    /// The version of the crate.
+    pub vers: Arc<str>,
+    /// The direct dependencies of this version.
+    #[serde(default)]
+    pub deps: Vec<IndexDependency>,
+    /// The features defined by this version, mapped to the features or dependencies they enable.
+    #[serde(default)]
+    pub features: BTreeMap<String, Vec<String>>,
+    /// Additional features using the newer feature syntax, stored separately by the registry.
+    #[serde(default)]
+    pub features2: BTreeMap<String, Vec<String>>,
+    /// Whether this version has been yanked.
+    #[serde(default)]
+    pub yanked: bool,
+    /// The native library this crate links to, if any.
+    #[serde(default)]
+    pub links: Option<String>,
+    /// The minimal supported Rust version, if specified.
+    #[serde(default)]
+    pub rust_version: Option<String>,
+}
+
+impl IndexEntry {
+    /// Returns the name and version of this entry as a `CrateVersion`.
+    ///
+    pub fn crate_version(&self) -> CrateVersion {
+        CrateVersion {
+            krate: self.name.clone(),
+            version: self.vers.clone(),
+        }
+    }
+
+    /// Iterates over all features of this entry, including those stored in `features2`.
+    ///
+    pub fn all_features(&self) -> impl Iterator<Item = (&String, &Vec<String>)> {
+        self.features.iter().chain(self.features2.iter())
+    }
+}
+
+/// Represents a dependency of a crate version in the registry index.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexDependency {
+    /// The name of the dependency, possibly renamed (see `package`).
+    pub name: String,
+    /// The semver requirement of the dependency.
+    pub req: String,
+    /// The features enabled for the dependency.
+    #[serde(default)]
+    pub features: Vec<String>,
+    /// Whether the dependency is optional.
+    #[serde(default)]
+    pub optional: bool,
+    /// Whether the default features of the dependency are enabled.
+    #[serde(default = "default_true")]
+    pub default_features: bool,
+    /// The target platform of the dependency, e.g. `cfg(unix)`.
+    #[serde(default)]
+    pub target: Option<String>,
+    /// The kind of the dependency.
+    #[serde(default)]
+    pub kind: DependencyKind,
+    /// The registry of the dependency, if it is not crates.io.
+    #[serde(default)]
+    pub registry: Option<String>,
+    /// The real name of the dependency if it is renamed.
+    #[serde(default)]
+    pub package: Option<String>,
+}
+
+impl IndexDependency {
+    /// Returns the name of the dependency crate on the registry, taking renames into account.
+    ///
+    pub fn package_name(&self) -> &str {
+        self.package.as_deref().unwrap_or(self.name.as_str())
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// The kind of a dependency.
+///
+#[derive(
+    Debug, Default, Clone, Copy, Serialize, Deserialize, Hash, Ord, PartialOrd, Eq, PartialEq,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum DependencyKind {
+    /// A normal dependency.
+    #[default]
+    Normal,
+    /// A dev-dependency.
+    Dev,
+    /// A build-dependency.
+    Build,
 }
@@ -0,0 +1,972 @@
+//! The `forge` module.
+//!
+//! Provides a common interface to the code forges hosting Rust projects: GitHub (including GitHub
+//! Enterprise Server), GitLab and Gitea or Forgejo, such as Codeberg. Each forge is addressed by
+//! its host name, e.g. `github.com` or `codeberg.org`, and supports reading files and directories,
+//! listing branches, searching issues and reading issue timelines.
+//!
+use crate::cache::FileContent;
+use crate::github::{Actor, GithubClient, Issue, IssueEvent, Repository, TimelineQuery};
+use crate::{Directory, DirectoryMut};
+use reqwest::header::HeaderMap;
+use reqwest::{Client, Proxy, RequestBuilder, Response, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+
+#[cfg(feature = "utoipa")]
+use utoipa::ToSchema;
+
+/// The number of entries requested per page from the GitLab API.
+const PER_PAGE: usize = 100;
+
+/// The number of entries requested per page from the Gitea API, whose default maximum is 50.
+const GITEA_PER_PAGE: usize = 50;
+
+/// The maximum number of entries read from a paginated timeline, as for GitHub.
+const MAX_ITEMS: usize = 3000;
+
+/// The maximum number of issues read from a search, the limit of the GitHub search API.
+const MAX_SEARCH_RESULTS: usize = 1000;
+
+/// The kinds of supported forges.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub enum ForgeKind {
+    /// GitHub or GitHub Enterprise Server.
+    Github,
+    /// GitLab, either gitlab.com or self-managed.
+    Gitlab,
+    /// Gitea or Forgejo, e.g. Codeberg.
+    Gitea,
+}
+
+impl FromStr for ForgeKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "github" => Ok(ForgeKind::Github),
+            "gitlab" => Ok(ForgeKind::Gitlab),
+            "gitea" | "forgejo" | "codeberg" => Ok(ForgeKind::Gitea),
+            kind => anyhow::bail!("Unknown forge kind: {kind}"),
+        }
+    }
+}
+
+/// A client of a code forge.
+///
+#[derive(Debug, Clone)]
+pub enum Forge {
+    /// A GitHub or GitHub Enterprise Server instance.
+    Github(GithubClient),
+    /// A GitLab instance.
+    Gitlab(GitlabClient),
+    /// A Gitea or Forgejo instance.
+    Gitea(GiteaClient),
+}
+
+impl Forge {
+    /// Creates a client for a forge from its kind and host.
+    ///
+    /// The API of GitHub Enterprise Server is expected at `https://{host}/api/v3`, that of GitLab at
+    /// `https://{host}/api/v4` and that of Gitea at `https://{host}/api/v1`. The token is optional,
+    /// as all of them allow anonymous access to public repositories, although GitHub only allows
+    /// 60 anonymous requests per hour.
+    ///
+    pub fn new(
+        kind: ForgeKind,
+        host: &str,
+        token: Option<&str>,
+        proxy: impl Into<Option<Proxy>>,
+    ) -> anyhow::Result<Self> {
+        Ok(match kind {
+            ForgeKind::Github => {
+                let client = match token {
+                    Some(token) => GithubClient::new(token, proxy)?,
+                    None => GithubClient::anonymous(proxy)?,
+                };
+                if host == "github.com" {
+                    Forge::Github(client)
+                } else {
+                    Forge::Github(client.with_base_url(&format!("https://{host}/api/v3")))
+                }
+            }
+            ForgeKind::Gitlab => Forge::Gitlab(GitlabClient::new(
+                &format!("https://{host}/api/v4"),
+                token,
+                proxy,
+            )?),
+            ForgeKind::Gitea => Forge::Gitea(GiteaClient::new(
+                &format!("https://{host}/api/v1"),
+                token,
+                proxy,
+            )?),
+        })
+    }
+
+    /// Returns the kind of the forge.
+    ///
+    pub fn kind(&self) -> ForgeKind {
+        match self {
+            Forge::Github(_) => ForgeKind::Github,
+            Forge::Gitlab(_) => ForgeKind::Gitlab,
+            Forge::Gitea(_) => ForgeKind::Gitea,
+        }
+    }
+
+    /// Reads a file of a repository.
+    ///
+    /// Returns `None` if the repository or the file does not exist.
+    ///
+    pub async fn get_file(
+        &self,
+        repo: &Repository,
+        path: &str,
+        branch: Option<&str>,
+    ) -> anyhow::Result<Option<FileContent>> {
+        match self {
+            Forge::Github(client) => Ok(client
+                .get_file(repo, path, branch)
+                .await?
+                .map(|file| file.content)),
+            Forge::Gitlab(client) => client.get_file(repo, path, branch).await,
+            Forge::Gitea(client) => client.get_file(repo, path, branch).await,
+        }
+    }
+
+    /// Lists a directory of a repository.
+    ///
+    /// Returns `None` if the repository or the directory does not exist.
+    ///
+    pub async fn read_dir(
+        &self,
+        repo: &Repository,
+        path: &str,
+        branch: Option<&str>,
+    ) -> anyhow::Result<Option<Directory>> {
+        match self {
+            Forge::Github(client) => client.read_dir(repo, path, branch).await,
+            Forge::Gitlab(client) => client.read_dir(repo, path, branch).await,
+            Forge::Gitea(client) => client.read_dir(repo, path, branch).await,
+        }
+    }
+
+    /// Lists the branches of a repository.
+    ///
+    pub async fn get_repo_branches(&self, repo: &Repository) -> anyhow::Result<Vec<String>> {
+        match self {
+            Forge::Github(client) => client.get_repo_branches(repo, None).await,
+            Forge::Gitlab(client) => client.get_repo_branches(repo).await,
+            Forge::Gitea(client) => client.get_repo_branches(repo).await,
+        }
+    }
+
+    /// Searches the issues of a repository.
+    ///
+    pub async fn search_for_issues(
+        &self,
+        repo: &Repository,
+        keyword: &str,
+    ) -> anyhow::Result<Vec<Issue>> {
+        match self {
+            Forge::Github(client) => client.search_for_issues(repo, &keyword.into()).await,
+            Forge::Gitlab(client) => client.search_for_issues(repo, keyword).await,
+            Forge::Gitea(client) => client.search_for_issues(repo, keyword).await,
+        }
+    }
+
+    /// Reads the timeline of an issue: its comments and the events that changed it.
+    ///
+    pub async fn get_issue_timeline(
+        &self,
+        repo: &Repository,
+        issue_number: u64,
+    ) -> anyhow::Result<Vec<IssueEvent>> {
+        match self {
+            Forge::Github(client) => {
+                client
+                    .get_issue_timeline(repo, issue_number, &TimelineQuery::default())
+                    .await
+            }
+            Forge::Gitlab(client) => client.get_issue_timeline(repo, issue_number).await,
+            Forge::Gitea(client) => client.get_issue_timeline(repo, issue_number).await,
+        }
+    }
+}
+
+/// A repository of a forge.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForgeRepository {
+    /// The host of the forge, e.g. `gitlab.com`.
+    pub host: Arc<str>,
+    /// The repository.
+    #[serde(flatten)]
+    pub repo: Repository,
+}
+
+/// A repository of a forge and a path within it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForgeRepositoryPath {
+    /// The host of the forge.
+    pub host: Arc<str>,
+    /// The repository.
+    #[serde(flatten)]
+    pub repo: Repository,
+    /// The path.
+    pub path: Arc<str>,
+}
+
+/// A repository of a forge and an issue number.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForgeRepositoryIssue {
+    /// The host of the forge.
+    pub host: Arc<str>,
+    /// The repository.
+    #[serde(flatten)]
+    pub repo: Repository,
+    /// The issue number.
+    pub number: u64,
+}
+
+/// The configuration of a forge, parsed from `kind:host` or `kind:host=token`,
+/// e.g. `gitea:git.example.com=0123abcd`.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForgeConfig {
+    /// The kind of the forge.
+    pub kind: ForgeKind,
+    /// The host name of the forge.
+    pub host: String,
+    /// The access token, if any.
+    pub token: Option<String>,
+}
+
+impl FromStr for ForgeConfig {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((kind, rest)) = s.trim().split_once(':') else {
+            anyhow::bail!("Invalid forge configuration, expected `kind:host[=token]`: {s}");
+        };
+        let (host, token) = match rest.split_once('=') {
+            Some((host, token)) => (host, Some(token.to_string())),
+            None => (rest, None),
+        };
+        let host = host.trim().trim_end_matches('/');
+        if host.is_empty() {
+            anyhow::bail!("Invalid forge configuration, the host is empty: {s}");
+        }
+        Ok(ForgeConfig {
+            kind: kind.parse()?,
+            host: host.to_string(),
+            token: token.filter(|token| !token.is_empty()),
+        })
+    }
+}
+
+impl ForgeConfig {
+    /// Parses a comma-separated list of forge configurations.
+    ///
+    pub fn parse_list(s: &str) -> anyhow::Result<Vec<ForgeConfig>> {
+        s.split(',')
+            .filter(|config| !config.trim().is_empty())
+            .map(str::parse)
+            .collect()
+    }
+}
+
+/// The forges known to the assistant, by host name.
+///
+/// `gitlab.com` and `codeberg.org` are always available for anonymous access to public
+/// repositories; `github.com` is available when a GitHub client is registered.
+///
+#[derive(Debug, Clone, Default)]
+pub struct ForgeRegistry {
+    forges: Arc<HashMap<String, Forge>>,
+}
+
+/// The error returned when a request addresses a forge host which is not configured.
+///
+#[derive(Debug, Clone)]
+pub struct UnknownForge(pub String);
+
+impl Display for UnknownForge {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unknown forge host: {}", self.0)
+    }
+}
+
+impl std::error::Error for UnknownForge {}
+
+impl ForgeRegistry {
+    /// Creates a registry with the public forges: `github.com` through the given client, if any,
+    /// `gitlab.com` and `codeberg.org`.
+    ///
+    pub fn with_public_forges(github: impl Into<Option<GithubClient>>) -> anyhow::Result<Self> {
+        let mut forges = HashMap::new();
+        if let Some(github) = github.into() {
+            forges.insert("github.com".to_string(), Forge::Github(github));
+        }
+        forges.insert(
+            "gitlab.com".to_string(),
+            Forge::new(ForgeKind::Gitlab, "gitlab.com", None, None)?,
+        );
+        forges.insert(
+            "codeberg.org".to_string(),
+            Forge::new(ForgeKind::Gitea, "codeberg.org", None, None)?,
+        );
+        Ok(Self {
+            forges: Arc::new(forges),
+        })
+    }
+
+    /// Adds or replaces the forge of a host.
+    ///
+    pub fn insert(&mut self, host: impl Into<String>, forge: Forge) -> Option<Forge> {
+        Arc::make_mut(&mut self.forges).insert(host.into(), forge)
+    }
+
+    /// Adds or replaces the forges described by configurations.
+    ///
+    pub fn configure(&mut self, configs: &[ForgeConfig]) -> anyhow::Result<()> {
+        for config in configs {
+            let forge = Forge::new(config.kind, &config.host, config.token.as_deref(), None)?;
+            self.insert(config.host.clone(), forge);
+        }
+        Ok(())
+    }
+
+    /// Returns the forge of a host.
+    ///
+    pub fn get(&self, host: &str) -> Result<&Forge, UnknownForge> {
+        self.forges
+            .get(host)
+            .ok_or_else(|| UnknownForge(host.to_string()))
+    }
+
+    /// Iterates over the hosts and kinds of the registered forges.
+    ///
+    pub fn hosts(&self) -> impl Iterator<Item = (&str, ForgeKind)> {
+        self.forges
+            .iter()
+            .map(|(host, forge)| (host.as_str(), forge.kind()))
+    }
+}
+
+/// Builds an HTTP client with optional default headers and proxy.
+fn build_client(headers: HeaderMap, proxy: Option<Proxy>) -> anyhow::Result<Client> {
+    let mut builder = reqwest::ClientBuilder::default().default_headers(headers);
+    if let Some(proxy) = proxy {
+        builder = builder.proxy(proxy);
+    }
+    Ok(builder.build()?)
+}
+
+/// Sends a request, returning `None` for a 404 response and an error for other failures.
+async fn send(builder: RequestBuilder, action: &str) -> anyhow::Result<Option<Response>> {
+    let resp = builder.send().await?;
+    let status = resp.status();
+    if status == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !status.is_success() {
+        anyhow::bail!(
+            "The server returned a non-200 status code when {action} ({status}): {}",
+            resp.text().await?
+        );
+    }
+    Ok(Some(resp))
+}
+
+/// Builds a URL from a base URL and path segments, percent-encoding each segment.
+pub(crate) fn api_url<I>(base: &str, segments: I) -> anyhow::Result<Url>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    extend_url(Url::parse(base)?, segments)
+}
+
+/// Appends path segments to a URL, percent-encoding each segment.
+pub(crate) fn extend_url<I>(mut url: Url, segments: I) -> anyhow::Result<Url>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    url.path_segments_mut()
+        .map_err(|_| anyhow::anyhow!("Invalid API URL"))?
+        .extend(segments);
+    Ok(url)
+}
+
+/// A client of the GitLab REST API.
+///
+#[derive(Debug, Clone)]
+pub struct GitlabClient {
+    client: Client,
+    api_base: Arc<str>,
+}
+
+#[derive(Deserialize)]
+struct GitlabTreeItem {
+    name: String,
+    r#type: String,
+}
+
+#[derive(Deserialize)]
+struct GitlabIssue {
+    iid: u64,
+    title: String,
+    web_url: String,
+    state: String,
+    description: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GitlabUser {
+    username: String,
+    #[serde(default)]
+    avatar_url: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GitlabNote {
+    id: Option<u64>,
+    body: Option<String>,
+    author: Option<GitlabUser>,
+    created_at: Option<String>,
+    updated_at: Option<String>,
+    #[serde(default)]
+    system: bool,
+}
+
+#[derive(Deserialize)]
+struct NamedItem {
+    name: String,
+}
+
+impl GitlabClient {
+    /// Creates a client of the GitLab API at a base URL, e.g. `https://gitlab.com/api/v4`.
+    ///
+    pub fn new(
+        base_url: &str,
+        token: Option<&str>,
+        proxy: impl Into<Option<Proxy>>,
+    ) -> anyhow::Result<Self> {
+        let mut headers = HeaderMap::new();
+        headers.insert(reqwest::header::USER_AGENT, "Rust Assistant".parse()?);
+        if let Some(token) = token {
+            headers.insert("PRIVATE-TOKEN", token.parse()?);
+        }
+        Ok(Self {
+            client: build_client(headers, proxy.into())?,
+            api_base: Arc::from(base_url.trim_end_matches('/')),
+        })
+    }
+
+    /// Builds the URL of a project resource. Projects are addressed by their URL-encoded full
+    /// path, so owners may be nested groups like `group/subgroup`.
+    fn project_url<'a>(
+        &self,
+        Repository { owner, repo }: &Repository,
+        segments: impl IntoIterator<Item = &'a str>,
+    ) -> anyhow::Result<Url> {
+        let project = format!("{owner}/{repo}");
+        extend_url(
+            api_url(&self.api_base, ["projects", project.as_str()])?,
+            segments,
+        )
+    }
+
+    pub async fn get_file(
+        &self,
+        repo: &Repository,
+        path: &str,
+        branch: Option<&str>,
+    ) -> anyhow::Result<Option<FileContent>> {
+        let url = self.project_url(repo, ["repository", "files", path, "raw"])?;
+        let mut builder = self.client.get(url);
+        if let Some(branch) = branch {
+            builder = builder.query(&[("ref", branch)]);
+        }
+        let Some(resp) = send(builder, "fetching the file").await? else {
+            return Ok(None);
+        };
+        Ok(Some(FileContent::from(resp.bytes().await?)))
+    }
+
+    pub async fn read_dir(
+        &self,
+        repo: &Repository,
+        path: &str,
+        branch: Option<&str>,
+    ) -> anyhow::Result<Option<Directory>> {
+        let url = self.project_url(repo, ["repository", "tree"])?;
+        let mut directory = DirectoryMut::default();
+        let mut page = 1;
+        loop {
+            let mut builder = self.client.get(url.clone()).query(&[
+                ("path", path),
+                ("per_page", &PER_PAGE.to_string()),
+                ("page", &page.to_string()),
+            ]);
+            if let Some(branch) = branch {
+                builder = builder.query(&[("ref", branch)]);
+            }
+            let Some(resp) = send(builder, "listing the directory").await? else {
+                return Ok(None);
+            };
+            let items = resp.json::<Vec<GitlabTreeItem>>().await?;
+            let count = items.len();
+            for item in items {
+                match item.r#type.as_str() {
+                    "blob" => {
+                        directory.files.insert(PathBuf::from(item.name));
+                    }
+                    "tree" => {
+                        directory.directories.insert(PathBuf::from(item.name));
+                    }
+                    _ => {}
+                }
+            }
+            if count < PER_PAGE {
+                break;
+            }
+            page += 1;
+        }
+        // GitLab returns an empty list rather than 404 for a missing path.
+        if !path.is_empty() && directory.files.is_empty() && directory.directories.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(directory.freeze()))
+    }
+
+    pub async fn get_repo_branches(&self, repo: &Repository) -> anyhow::Result<Vec<String>> {
+        let url = self.project_url(repo, ["repository", "branches"])?;
+        let mut branches = Vec::new();
+        let mut page = 1;
+        loop {
+            let builder = self.client.get(url.clone()).query(&[
+                ("per_page", PER_PAGE.to_string()),
+                ("page", page.to_string()),
+            ]);
+            let Some(resp) = send(builder, "listing the branches").await? else {
+                anyhow::bail!("The repository does not exist.");
+            };
+            let items = resp.json::<Vec<NamedItem>>().await?;
+            let count = items.len();
+            branches.extend(items.into_iter().map(|item| item.name));
+            if count < PER_PAGE {
+                break;
+            }
+            page += 1;
+        }
+        Ok(branches)
+    }
+
+    pub async fn search_for_issues(
+        &self,
+        repo: &Repository,
+        keyword: &str,
+    ) -> anyhow::Result<Vec<Issue>> {
+        let url = self.project_url(repo, ["issues"])?;
+        let mut issues = Vec::new();
+        let mut page = 1;
+        loop {
+            let builder = self.client.get(url.clone()).query(&[
+                ("search", keyword),
+                ("scope", "all"),
+                ("per_page", &PER_PAGE.to_string()),
+                ("page", &page.to_string()),
+            ]);
+            let Some(resp) = send(builder, "searching for issues").await? else {
+                anyhow::bail!("The repository does not exist.");
+            };
+            let items = resp.json::<Vec<GitlabIssue>>().await?;
+            let count = items.len();
+            issues.extend(items);
+            if count < PER_PAGE || issues.len() >= MAX_SEARCH_RESULTS {
+                break;
+            }
+            page += 1;
+        }
+        Ok(issues
+            .into_iter()
+            .take(MAX_SEARCH_RESULTS)
+            .map(|issue| Issue {
+                number: issue.iid,
+                title: issue.title,
+                url: issue.web_url,
+                state: issue.state,
+                body: issue.description,
+            })
+            .collect())
+    }
+
+    /// Reads the notes of an issue. GitLab records state changes as system notes, which are
+    /// reported as `other` events named `system`; other notes are reported as comments.
+    pub async fn get_issue_timeline(
+        &self,
+        repo: &Repository,
+        issue_number: u64,
+    ) -> anyhow::Result<Vec<IssueEvent>> {
+        let number = issue_number.to_string();
+        let url = self.project_url(repo, ["issues", number.as_str(), "notes"])?;
+        let mut notes = Vec::new();
+        let mut page = 1;
+        loop {
+            let builder = self.client.get(url.clone()).query(&[
+                ("sort", "asc"),
+                ("per_page", &PER_PAGE.to_string()),
+                ("page", &page.to_string()),
+            ]);
+            let Some(resp) = send(builder, "fetching the issue notes").await? else {
+                anyhow::bail!("The issue does not exist.");
+            };
+            let items = resp.json::<Vec<GitlabNote>>().await?;
+            let count = items.len();
+            notes.extend(items);
+            if count < PER_PAGE || notes.len() >= MAX_ITEMS {
+                break;
+            }
+            page += 1;
+        }
+        Ok(notes
+            .into_iter()
+            .take(MAX_ITEMS)
+            .map(|note| {
+                let actor = note.author.map(|author| Actor {
+                    login: author.username,
+                    avatar_url: author.avatar_url.unwrap_or_default(),
+                });
+                if note.system {
+                    IssueEvent::Other {
+                        name: "system".to_string(),
+                        actor,
+                        body: note.body,
+                        created_at: note.created_at,
+                    }
+                } else {
+                    IssueEvent::Commented {
+                        id: note.id,
+                        actor,
+                        body: note.body,
+                        url: None,
+                        reactions: None,
+                        created_at: note.created_at,
+                        updated_at: note.updated_at,
+                    }
+                }
+            })
+            .collect())
+    }
+}
+
+/// A client of the Gitea and Forgejo REST API.
+///
+#[derive(Debug, Clone)]
+pub struct GiteaClient {
+    client: Client,
+    api_base: Arc<str>,
+}
+
+#[derive(Deserialize)]
+struct GiteaContentItem {
+    name: String,
+    r#type: String,
+}
+
+#[derive(Deserialize)]
+struct GiteaIssue {
+    number: u64,
+    title: String,
+    html_url: String,
+    state: String,
+    body: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GiteaUser {
+    login: String,
+    #[serde(default)]
+    avatar_url: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GiteaTimelineEvent {
+    id: Option<u64>,
+    r#type: String,
+    user: Option<GiteaUser>,
+    created_at: Option<String>,
+    updated_at: Option<String>,
+    body: Option<String>,
+    html_url: Option<String>,
+}
+
+impl GiteaClient {
+    /// Creates a client of the Gitea API at a base URL, e.g. `https://codeberg.org/api/v1`.
+    ///
+    pub fn new(
+        base_url: &str,
+        token: Option<&str>,
+        proxy: impl Into<Option<Proxy>>,
+    ) -> anyhow::Result<Self> {
+        let mut headers = HeaderMap::new();
+        headers.insert(reqwest::header::USER_AGENT, "Rust Assistant".parse()?);
+        if let Some(token) = token {
+            headers.insert(
+                reqwest::header::AUTHORIZATION,
+                format!("token {token}").parse()?,
+            );
+        }
+        Ok(Self {
+            client: build_client(headers, proxy.into())?,
+            api_base: Arc::from(base_url.trim_end_matches('/')),
+        })
+    }
+
+    /// Builds the URL of a repository resource, with the path split into segments.
+    fn repo_url<'a>(
+        &self,
+        Repository { owner, repo }: &Repository,
+        segments: impl IntoIterator<Item = &'a str>,
+    ) -> anyhow::Result<Url> {
+        extend_url(
+            api_url(&self.api_base, ["repos", owner.as_ref(), repo.as_ref()])?,
+            segments.into_iter().filter(|segment| !segment.is_empty()),
+        )
+    }
+
+    pub async fn get_file(
+        &self,
+        repo: &Repository,
+        path: &str,
+        branch: Option<&str>,
+    ) -> anyhow::Result<Option<FileContent>> {
+        let url = self.repo_url(repo, std::iter::once("raw").chain(path.split('/')))?;
+        let mut builder = self.client.get(url);
+        if let Some(branch) = branch {
+            builder = builder.query(&[("ref", branch)]);
+        }
+        let Some(resp) = send(builder, "fetching the file").await? else {
+            return Ok(None);
+        };
+        Ok(Some(FileContent::from(resp.bytes().await?)))
+    }
+
+    pub async fn read_dir(
+        &self,
+        repo: &Repository,
+        path: &str,
+        branch: Option<&str>,
+    ) -> anyhow::Result<Option<Directory>> {
+        let url = self.repo_url(repo, std::iter::once("contents").chain(path.split('/')))?;
+        let mut builder = self.client.get(url);
+        if let Some(branch) = branch {
+            builder = builder.query(&[("ref", branch)]);
+        }
+        let Some(resp) = send(builder, "listing the directory").await? else {
+            return Ok(None);
+        };
+        let body = resp.json::<serde_json::Value>().await?;
+        if !body.is_array() {
+            anyhow::bail!("The path is not a directory.");
+        }
+        let items = serde_json::from_value::<Vec<GiteaContentItem>>(body)?;
+        let mut directory = DirectoryMut::default();
+        for item in items {
+            match item.r#type.as_str() {
+                "file" | "symlink" => {
+                    directory.files.insert(PathBuf::from(item.name));
+                }
+                "dir" => {
+                    directory.directories.insert(PathBuf::from(item.name));
+                }
+                _ => {}
+            }
+        }
+        Ok(Some(directory.freeze()))
+    }
+
+    pub async fn get_repo_branches(&self, repo: &Repository) -> anyhow::Result<Vec<String>> {
+        let url = self.repo_url(repo, ["branches"])?;
+        let mut branches = Vec::new();
+        let mut page = 1;
+        loop {
+            let builder = self.client.get(url.clone()).query(&[
+                ("limit", GITEA_PER_PAGE.to_string()),
+                ("page", page.to_string()),
+            ]);
+            let Some(resp) = send(builder, "listing the branches").await? else {
+                anyhow::bail!("The repository does not exist.");
+            };
+            let items = resp.json::<Vec<NamedItem>>().await?;
+            let count = items.len();
+            branches.extend(items.into_iter().map(|item| item.name));
+            if count < GITEA_PER_PAGE {
+                break;
+            }
+            page += 1;
+        }
+        Ok(branches)
+    }
+
+    pub async fn search_for_issues(
+        &self,
+        repo: &Repository,
+        keyword: &str,
+    ) -> anyhow::Result<Vec<Issue>> {
+        let url = self.repo_url(repo, ["issues"])?;
+        let mut issues = Vec::new();
+        let mut page = 1;
+        loop {
+            let builder = self.client.get(url.clone()).query(&[
+                ("q", keyword),
+                ("type", "issues"),
+                ("state", "all"),
+                ("limit", &GITEA_PER_PAGE.to_string()),
+                ("page", &page.to_string()),
+            ]);
+            let Some(resp) = send(builder, "searching for issues").await? else {
+                anyhow::bail!("The repository does not exist.");
+            };
+            let items = resp.json::<Vec<GiteaIssue>>().await?;
+            let count = items.len();
+            issues.extend(items);
+            if count < GITEA_PER_PAGE || issues.len() >= MAX_SEARCH_RESULTS {
+                break;
+            }
+            page += 1;
+        }
+        Ok(issues
+            .into_iter()
+            .take(MAX_SEARCH_RESULTS)
+            .map(|issue| Issue {
+                number: issue.number,
+                title: issue.title,
+                url: issue.html_url,
+                state: issue.state,
+                body: issue.body,
+            })
+            .collect())
+    }
+
+    pub async fn get_issue_timeline(
+        &self,
+        repo: &Repository,
+        issue_number: u64,
+    ) -> anyhow::Result<Vec<IssueEvent>> {
+        let number = issue_number.to_string();
+        let url = self.repo_url(repo, ["issues", number.as_str(), "timeline"])?;
+        let mut events = Vec::new();
+        let mut page = 1;
+        loop {
+            let builder = self.client.get(url.clone()).query(&[
+                ("limit", GITEA_PER_PAGE.to_string()),
+                ("page", page.to_string()),
+            ]);
+            let Some(resp) = send(builder, "fetching the issue timeline").await? else {
+                anyhow::bail!("The issue does not exist.");
+            };
+            let items = resp.json::<Vec<GiteaTimelineEvent>>().await?;
+            let count = items.len();
+            events.extend(items);
+            if count < GITEA_PER_PAGE || events.len() >= MAX_ITEMS {
+                break;
+            }
+            page += 1;
+        }
+        Ok(events
+            .into_iter()
+            .take(MAX_ITEMS)
+            .map(|event| {
+                let actor = event.user.map(|user| Actor {
+                    login: user.login,
+                    avatar_url: user.avatar_url.unwrap_or_default(),
+                });
+                let body = event.body.filter(|body| !body.is_empty());
+                let created_at = event.created_at;
+                match event.r#type.as_str() {
+                    "comment" => IssueEvent::Commented {
+                        id: event.id,
+                        actor,
+                        body,
+                        url: event.html_url,
+                        reactions: None,
+                        created_at,
+                        updated_at: event.updated_at,
+                    },
+                    "close" => IssueEvent::Closed {
+                        actor,
+                        commit_id: None,
+                        commit_url: None,
+                        pull_request: None,
+                        state_reason: None,
+                        created_at,
+                    },
+                    "reopen" => IssueEvent::Reopened { actor, created_at },
+                    _ => IssueEvent::Other {
+                        name: event.r#type,
+                        actor,
+                        body,
+                        created_at,
+                    },
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_forge_configs() -> anyhow::Result<()> {
+        let configs = ForgeConfig::parse_list(
+            "gitea:git.example.com=secret, gitlab:gitlab.example.com/,github:ghe.example.com=t",
+        )?;
+        assert_eq!(
+            configs[0],
+            ForgeConfig {
+                kind: ForgeKind::Gitea,
+                host: "git.example.com".to_string(),
+                token: Some("secret".to_string()),
+            }
+        );
+        assert_eq!(configs[1].kind, ForgeKind::Gitlab);
+        assert_eq!(configs[1].host, "gitlab.example.com");
+        assert!(configs[1].token.is_none());
+        assert!("forgejo:codeberg.org".parse::<ForgeConfig>().is_ok());
+        assert!("svn:example.com".parse::<ForgeConfig>().is_err());
+        assert!("github.com".parse::<ForgeConfig>().is_err());
+
+        let Forge::Github(client) = Forge::new(
+            configs[2].kind,
+            &configs[2].host,
+            configs[2].token.as_deref(),
+            None,
+        )?
+        else {
+            panic!("expected a GitHub forge");
+        };
+        assert_eq!(client.base_url(), "https://ghe.example.com/api/v3");
+
+        let gitlab = GitlabClient::new("https://gitlab.com/api/v4", None, None)?;
+        let url = gitlab.project_url(
+            &Repository::from(("group/sub", "project")),
+            ["repository", "files", "src/lib.rs", "raw"],
+        )?;
+        assert_eq!(
+            url.as_str(),
+            "https://gitlab.com/api/v4/projects/group%2Fsub%2Fproject/repository/files/src%2Flib.rs/raw"
+        );
+        Ok(())
+    }
+}
@@ -22,42 +22,68 @@
 //!
 //! - `app`: Contains the core application logic for the Rust Assistant.
 //! - `cache`: Provides caching functionalities for crates.
+//! - `cfg`: Represents and evaluates `#[cfg(...)]` predicates of items.
+//! - `diff`: Compares the public APIs of two versions of a crate.
 //! - `download`: Handles the downloading of crates and their contents.
+//! - `forge`: Reads repositories and issues from GitHub, GitLab and Gitea forges.
+//! - `manifest`: Parses the `Cargo.toml` manifests of crates.
+//! - `outline`: Builds the symbol trees of Rust source files.
+//! - `overview`: Summarizes the manifest, docs, modules and size of a crate.
+//! - `resolve`: Resolves the transitive dependency graphs of crates from the registry index.
 //! - `search`: Implements search algorithms and data structures for efficient crate content search.
+//! - `vcs`: Maps published crate versions to the commits and directories of their repositories.
+//! - `xref`: Resolves the paths used in source files to find the usages of items.
 //!
 pub mod app;
 
 #[cfg(feature = "axum")]
 pub mod axum;
 pub mod cache;
+pub mod cfg;
+pub mod diff;
 pub mod download;
+pub mod forge;
 pub mod github;
+pub mod manifest;
+pub mod outline;
+pub mod overview;
+pub mod resolve;
 pub mod search;
+pub mod vcs;
+pub mod xref;
 
+use crate::cfg::{Cfg, CfgConfig, TargetCfg};
+use crate::resolve::ResolveOptions;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeSet;
 use std::fmt::{Display, Formatter};
 use std::num::NonZeroUsize;
 use std::ops::{Range, RangeInclusive};
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
 #[cfg(feature = "utoipa")]
 use utoipa::ToSchema;
 
 pub use app::*;
 pub use github::*;
+pub use manifest::Manifest;
 pub use search::*;
 
 /// Represents the name and version of a crate.
 ///
 /// This struct is used to uniquely identify a crate with its name and version number.
 #[derive(Debug, Deserialize, Serialize, Hash, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
 pub struct CrateVersion {
     /// The exact name of the crate
     #[serde(rename = "crate")]
+    #[cfg_attr(feature = "utoipa", schema(value_type = String))]
     pub krate: Arc<str>,
     /// The semantic version number of the specified crate, following the Semantic versioning specification.
+    #[cfg_attr(feature = "utoipa", schema(value_type = String))]
     pub version: Arc<str>,
 }
 
@@ -95,6 +121,20 @@ impl Display for CrateVersion {
     }
 }
 
+impl FromStr for CrateVersion {
+    type Err = anyhow::Error;
+
+    /// Parses a `CrateVersion` from a string in `name@version` form, e.g. `serde@1.0.203`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().split_once('@') {
+            Some((krate, version)) if !krate.is_empty() && !version.is_empty() => {
+                Ok(Self::from((krate.trim(), version.trim())))
+            }
+            _ => anyhow::bail!("Invalid crate version '{s}', expected 'name@version'."),
+        }
+    }
+}
+
 /// Represents a path within a specific crate's directory structure.
 ///
 /// It combines the crate version information with the relative path within the crate.
@@ -107,6 +147,52 @@ pub struct CrateVersionPath {
     pub path: Arc<str>,
 }
 
+/// Represents two versions of the same crate to compare.
+///
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CrateVersionPair {
+    /// The exact name of the crate.
+    #[serde(rename = "crate")]
+    pub krate: Arc<str>,
+    /// The old version.
+    pub old: Arc<str>,
+    /// The new version.
+    pub new: Arc<str>,
+}
+
+impl CrateVersionPair {
+    /// Returns the old version of the crate.
+    ///
+    pub fn old_version(&self) -> CrateVersion {
+        CrateVersion::from((&self.krate, &self.old))
+    }
+
+    /// Returns the new version of the crate.
+    ///
+    pub fn new_version(&self) -> CrateVersion {
+        CrateVersion::from((&self.krate, &self.new))
+    }
+}
+
+/// Represents a path within two versions of the same crate.
+///
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CrateVersionPairPath {
+    /// The name of the crate and the two versions.
+    #[serde(flatten)]
+    pub versions: CrateVersionPair,
+    /// The path.
+    pub path: Arc<str>,
+}
+
+/// Represents the options of a unified diff.
+///
+#[derive(Debug, Default, Deserialize, Serialize, Copy, Clone)]
+pub struct DiffOptions {
+    /// The number of unchanged lines shown around each change, 3 by default.
+    pub context: Option<usize>,
+}
+
 /// Represents a range of lines in a file.
 ///
 /// This struct is used to specify a start and end line for operations that work with line ranges.
@@ -184,6 +270,45 @@ pub struct ItemQuery {
     /// Optional path within the crate to narrow down the search scope.
     #[cfg_attr(feature = "utoipa", schema(value_type = String))]
     pub path: Option<PathBuf>,
+    /// A comma-separated list of enabled cargo features, e.g. "full,serde".
+    /// If specified, items gated behind other features are filtered out.
+    /// The `default` feature is enabled unless `no_default_features` is set.
+    pub features: Option<String>,
+    /// Whether the `default` feature is disabled when `features` is specified.
+    #[serde(default)]
+    pub no_default_features: bool,
+    /// The target triple, e.g. "x86_64-unknown-linux-gnu".
+    /// If specified, items gated behind other targets are filtered out.
+    pub target: Option<String>,
+}
+
+impl ItemQuery {
+    /// Builds the configuration item `cfg` predicates are evaluated against.
+    ///
+    /// The enabled features are expanded with the features they enable according to the
+    /// crate's manifest, if available.
+    ///
+    pub fn cfg_config(&self, manifest: Option<&Manifest>) -> anyhow::Result<CfgConfig> {
+        let features = self.features.as_ref().map(|features| {
+            let features = features
+                .split(',')
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .chain((!self.no_default_features).then_some("default"));
+            match manifest {
+                Some(manifest) => manifest.expand_features(features),
+                None => features.map(String::from).collect(),
+            }
+        });
+        let target = self
+            .target
+            .as_deref()
+            .map(str::trim)
+            .filter(|target| !target.is_empty())
+            .map(TargetCfg::from_str)
+            .transpose()?;
+        Ok(CfgConfig { features, target })
+    }
 }
 
 /// Represents an item found in a crate.
@@ -203,6 +328,111 @@ pub struct Item {
     /// The range of lines in the file where the item is defined.
     #[cfg_attr(feature = "utoipa", schema(value_type = RangeSchema))]
     pub line_range: RangeInclusive<NonZeroUsize>,
+    /// The `cfg` predicate the item is gated by, including those inherited from enclosing modules,
+    /// e.g. `all(feature = "net", unix)`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Option<String>))]
+    pub cfg: Option<Cfg>,
+    /// The full path of the item from the library root, e.g. `crate::io::Read`.
+    ///
+    /// For impl blocks this is the path of the module containing them. Not set for items
+    /// outside the library module tree, such as binaries, tests and build scripts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Whether the item is declared `pub`, or `#[macro_export]` for macros.
+    #[serde(default)]
+    pub public: bool,
+    /// Whether the item is reachable from outside the crate by its path, i.e. it is public and
+    /// all the modules containing it are public. Impl blocks of library modules are always exported.
+    #[serde(default)]
+    pub exported: bool,
+    /// The normalized signature of the item: its header on the first line, followed by one public
+    /// member per line for structs, enums, traits and inherent impls.
+    ///
+    /// It is only used to compare versions, and is not serialized with search results; the API
+    /// diff reports it in [`ApiItem`](crate::diff::ApiItem).
+    #[serde(skip)]
+    pub signature: Option<String>,
+    /// The implemented trait and the self type of impl blocks, including those generated by
+    /// `#[derive(...)]`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impl_info: Option<ImplInfo>,
+}
+
+/// The structure of an impl block.
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct ImplInfo {
+    /// The path of the implemented trait as written, without generic arguments, e.g. `fmt::Display`.
+    /// Not set for inherent impls.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trait_path: Option<String>,
+    /// The self type as written, e.g. `Vec<T>`.
+    pub self_type: String,
+    /// The name of the self type, without its path, references and generic arguments, e.g. `Vec`.
+    /// Not set for blanket impls and for types which are not paths, like tuples or slices.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub self_name: Option<String>,
+    /// Whether the impl covers any type satisfying some bounds, e.g. `impl<T: Read> Read for &mut T`.
+    #[serde(default)]
+    pub blanket: bool,
+    /// The traits bounding the type parameter of a blanket impl, e.g. `Read`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bounds: Vec<String>,
+    /// Whether the impl is generated by `#[derive(...)]`.
+    #[serde(default)]
+    pub derived: bool,
+}
+
+impl ImplInfo {
+    /// Returns the name of the implemented trait, i.e. the last segment of its path.
+    ///
+    pub fn trait_name(&self) -> Option<&str> {
+        let path = self.trait_path.as_deref()?;
+        Some(path.rsplit("::").next().unwrap_or(path))
+    }
+}
+
+/// Represents the results of an item search in a crate.
+///
+/// The item index only covers files that could be parsed. If any Rust source file within the
+/// search scope failed to parse, `incomplete` is set and the failures are listed, so clients
+/// know to fall back to line search.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ItemSearchResult {
+    /// The items found.
+    pub items: Vec<Item>,
+    /// Whether some files within the search scope were not indexed because they failed to parse.
+    pub incomplete: bool,
+    /// The files within the search scope that failed to parse.
+    pub parse_errors: Vec<ParseError>,
+}
+
+/// Represents a Rust source file that failed to parse during indexing.
+///
+/// Files using syntax newer than the parser supports, or heavily macro-generated files,
+/// are missing from the item index and can only be found by line search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct ParseError {
+    /// The file that failed to parse.
+    #[cfg_attr(feature = "utoipa", schema(value_type = String))]
+    pub file: Arc<Path>,
+    /// The error message reported by the parser.
+    pub message: String,
+    /// The line number where the error starts.
+    #[cfg_attr(feature = "utoipa", schema(value_type = usize))]
+    pub line: NonZeroUsize,
+    /// The column number where the error starts.
+    #[cfg_attr(feature = "utoipa", schema(value_type = usize))]
+    pub column: NonZeroUsize,
+    /// The line number where the error ends.
+    #[cfg_attr(feature = "utoipa", schema(value_type = usize))]
+    pub end_line: NonZeroUsize,
+    /// The column number where the error ends.
+    #[cfg_attr(feature = "utoipa", schema(value_type = usize))]
+    pub end_column: NonZeroUsize,
 }
 
 /// Defines various types of items that can be searched for in a crate.
@@ -235,6 +465,29 @@ pub enum ItemType {
     TypeAlias,
 }
 
+/// Represents a query for the impls of a trait or of a type.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct ImplQuery {
+    /// The name or path of the trait or type, e.g. "AsyncRead" or "io::Read". Generic arguments are ignored.
+    pub name: String,
+    /// The directory containing the impls to search.
+    #[cfg_attr(feature = "utoipa", schema(value_type = Option<String>))]
+    pub path: Option<PathBuf>,
+}
+
+/// Represents the results of a search for the impls of a trait or of a type.
+///
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct ImplSearchResult {
+    /// The impls found, including those generated by `#[derive(...)]`.
+    pub impls: Vec<Item>,
+    /// The blanket impls which may also apply, depending on their bounds.
+    pub blanket: Vec<Item>,
+}
+
 /// Represents a query for searching lines within files in a crate.
 ///
 /// This struct is used for specifying criteria for line-based searches, such as finding specific text within files.
@@ -295,6 +548,235 @@ pub struct Line {
     pub column_range: Range<NonZeroUsize>,
 }
 
+/// Represents the set of crates covered by a multi-crate search.
+///
+#[derive(Debug, Clone)]
+pub enum CrateSet {
+    /// An explicit list of crates.
+    Crates(Vec<CrateVersion>),
+    /// A root crate and its dependencies, up to the specified depth.
+    ///
+    /// A depth of `0` covers the root crate only, `1` adds its direct dependencies, and so on.
+    Dependencies {
+        /// The root crate.
+        root: CrateVersion,
+        /// The maximum dependency depth.
+        depth: usize,
+    },
+}
+
+/// Limits applied to a multi-crate search as a whole.
+///
+#[derive(Debug, Clone, Copy)]
+pub struct SearchBudget {
+    /// The maximum number of results returned across all crates.
+    pub max_results: NonZeroUsize,
+    /// The maximum time spent on the search, including downloading crates.
+    pub timeout: Duration,
+}
+
+impl Default for SearchBudget {
+    fn default() -> Self {
+        Self {
+            max_results: NonZeroUsize::new(200).unwrap_or(NonZeroUsize::MIN),
+            timeout: Duration::from_secs(20),
+        }
+    }
+}
+
+/// Represents the item whose usages are searched for, as passed in query strings.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct UsageQuery {
+    /// The path of the item within its crate, e.g. "de::Deserialize" or "Bytes::new".
+    pub path: String,
+}
+
+/// Represents a position in a source file, as passed in query strings.
+///
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct Position {
+    /// The line number, starting at 1.
+    #[cfg_attr(feature = "utoipa", schema(value_type = usize))]
+    pub line: NonZeroUsize,
+    /// The column number, starting at 1.
+    #[cfg_attr(feature = "utoipa", schema(value_type = usize))]
+    pub column: NonZeroUsize,
+}
+
+/// Represents the options of a dependency graph resolution, as passed in query strings.
+///
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct DependencyQuery {
+    /// A comma-separated list of features enabled on the crate, e.g. "full,serde".
+    pub features: Option<String>,
+    /// Whether the default features of the crate are disabled.
+    #[serde(default)]
+    pub no_default_features: bool,
+    /// Whether all features of the crate are enabled.
+    #[serde(default)]
+    pub all_features: bool,
+    /// The target triple, e.g. "x86_64-unknown-linux-gnu".
+    /// If specified, target-specific dependencies of other targets are excluded.
+    pub target: Option<String>,
+    /// Whether the dev-dependencies of the crate are included.
+    #[serde(default)]
+    pub dev: bool,
+}
+
+impl From<DependencyQuery> for ResolveOptions {
+    fn from(query: DependencyQuery) -> Self {
+        ResolveOptions {
+            features: query
+                .features
+                .iter()
+                .flat_map(|features| features.split(','))
+                .map(str::trim)
+                .filter(|feature| !feature.is_empty())
+                .map(String::from)
+                .collect(),
+            no_default_features: query.no_default_features,
+            all_features: query.all_features,
+            target: query
+                .target
+                .map(|target| target.trim().to_string())
+                .filter(|target| !target.is_empty()),
+            dev: query.dev,
+        }
+    }
+}
+
+/// Represents the crates and limits of a multi-crate search, as passed in query strings.
+///
+/// Either `crates` or `root` must be provided.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct MultiCrateQuery {
+    /// A comma-separated list of crates to search, each in `name@version` form, e.g. "serde@1.0.203,tokio@1.38.0".
+    #[serde(default)]
+    pub crates: String,
+    /// The root crate in `name@version` form, whose dependency tree is searched.
+    pub root: Option<String>,
+    /// The maximum dependency depth when searching the dependency tree of `root`. Defaults to 1.
+    pub depth: Option<usize>,
+    /// The maximum number of results returned across all crates.
+    #[cfg_attr(feature = "utoipa", schema(value_type = Option<usize>))]
+    pub limit: Option<NonZeroUsize>,
+    /// The time budget of the search in milliseconds.
+    pub timeout_ms: Option<u64>,
+}
+
+impl MultiCrateQuery {
+    /// Converts the query into the set of crates to search.
+    ///
+    pub fn crate_set(&self) -> anyhow::Result<CrateSet> {
+        if let Some(root) = &self.root {
+            return Ok(CrateSet::Dependencies {
+                root: root.parse()?,
+                depth: self.depth.unwrap_or(1),
+            });
+        }
+
+        let crates = self
+            .crates
+            .split(',')
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .map(CrateVersion::from_str)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        if crates.is_empty() {
+            anyhow::bail!("Either 'crates' or 'root' must be provided.");
+        }
+        Ok(CrateSet::Crates(crates))
+    }
+
+    /// Converts the query into the limits of the search, falling back to the defaults.
+    ///
+    pub fn budget(&self) -> SearchBudget {
+        let default = SearchBudget::default();
+        SearchBudget {
+            max_results: self.limit.unwrap_or(default.max_results),
+            timeout: self
+                .timeout_ms
+                .map(Duration::from_millis)
+                .unwrap_or(default.timeout),
+        }
+    }
+}
+
+/// An item found in a multi-crate search, tagged with the crate it belongs to.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct CrateItem {
+    /// The crate containing the item.
+    #[serde(flatten)]
+    pub crate_version: CrateVersion,
+    /// The item.
+    #[serde(flatten)]
+    pub item: Item,
+}
+
+/// A usage found in a multi-crate usage search, tagged with the crate it belongs to.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct CrateUsage {
+    /// The crate containing the usage.
+    #[serde(flatten)]
+    pub crate_version: CrateVersion,
+    /// The usage.
+    #[serde(flatten)]
+    pub usage: xref::Usage,
+}
+
+/// A line found in a multi-crate search, tagged with the crate it belongs to.
+///
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct CrateLine {
+    /// The crate containing the line.
+    #[serde(flatten)]
+    pub crate_version: CrateVersion,
+    /// The line.
+    #[serde(flatten)]
+    pub line: Line,
+}
+
+/// A crate that could not be searched in a multi-crate search.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct CrateSearchError {
+    /// The crate that failed.
+    #[serde(flatten)]
+    pub crate_version: CrateVersion,
+    /// The error message.
+    pub error: String,
+}
+
+/// The merged results of a search across multiple crates.
+///
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct MultiCrateSearchResult<T> {
+    /// The results, ordered by crate in the order the crates were resolved.
+    pub results: Vec<T>,
+    /// The crates that were searched successfully.
+    pub searched: Vec<CrateVersion>,
+    /// The crates that could not be searched.
+    pub failed: Vec<CrateSearchError>,
+    /// The crates whose results may be incomplete, because some of their files failed to parse.
+    pub incomplete: Vec<CrateVersion>,
+    /// Whether results were dropped because the result limit was reached.
+    pub truncated: bool,
+    /// Whether some crates were not searched because the time budget was exhausted.
+    pub timed_out: bool,
+}
+
 /// Schema for representing a range, used in other structs to describe line and column ranges.
 #[cfg(feature = "utoipa")]
 #[derive(ToSchema)]
@@ -308,7 +790,7 @@ pub struct RangeSchema {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cache::{Crate, CrateCache, CrateTar};
+    use crate::cache::{Crate, CrateCache, CrateTar, FileContent};
     use crate::download::CrateDownloader;
     use std::num::NonZeroUsize;
 
@@ -355,4 +837,950 @@ mod tests {
         println!("{:#?}", lines);
         Ok(())
     }
+
+    #[test]
+    fn multi_crate_query() -> anyhow::Result<()> {
+        let query = MultiCrateQuery {
+            crates: "serde@1.0.203, tokio@1.38.0,".to_string(),
+            root: None,
+            depth: None,
+            limit: None,
+            timeout_ms: Some(500),
+        };
+        let CrateSet::Crates(crates) = query.crate_set()? else {
+            panic!("expected a crate list");
+        };
+        assert_eq!(
+            crates,
+            vec![
+                CrateVersion::from(("serde", "1.0.203")),
+                CrateVersion::from(("tokio", "1.38.0"))
+            ]
+        );
+        assert_eq!(query.budget().timeout, Duration::from_millis(500));
+
+        let query = MultiCrateQuery {
+            crates: String::new(),
+            root: Some("axum@0.8.1".to_string()),
+            depth: Some(2),
+            limit: None,
+            timeout_ms: None,
+        };
+        assert!(matches!(
+            query.crate_set()?,
+            CrateSet::Dependencies { root, depth: 2 } if root == CrateVersion::from(("axum", "0.8.1"))
+        ));
+        assert!("axum".parse::<CrateVersion>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn trait_implementors_and_implemented_traits() {
+        let mut builder = SearchIndexBuilder::default();
+        assert!(builder.update(
+            "src/lib.rs",
+            r#"
+            pub trait Read {}
+            pub trait ReadExt {}
+            #[derive(Debug, Clone, serde::Serialize)]
+            pub struct Bytes<T>(T);
+            impl<T> Read for Bytes<T> {}
+            impl<T> std::fmt::Display for &Bytes<T> {}
+            impl<R: Read + ?Sized> ReadExt for R {}
+            impl<W> Write for W where W: std::io::Write {}
+            impl Bytes<u8> { pub fn new() -> Self { Bytes(0) } }
+            "#,
+        ));
+        let index = builder.finish();
+
+        let read = index.implementors("Read", None);
+        assert_eq!(read.impls.len(), 1);
+        let info = read.impls[0].impl_info.as_ref().unwrap();
+        assert_eq!(info.self_type, "Bytes<T>");
+        assert_eq!(info.self_name.as_deref(), Some("Bytes"));
+        assert!(read.blanket.is_empty());
+        assert!(index.implementors("io::Read", None).impls.len() == 1);
+        assert!(index.implementors("fmt::Display", None).impls.len() == 1);
+        assert!(index.implementors("io::Display", None).impls.is_empty());
+
+        let ext = index.implementors("ReadExt", None);
+        assert!(ext.impls.is_empty());
+        let info = ext.blanket[0].impl_info.as_ref().unwrap();
+        assert!(info.blanket);
+        assert_eq!(info.bounds, vec!["Read"]);
+
+        let debug = index.implementors("Debug", None);
+        let info = debug.impls[0].impl_info.as_ref().unwrap();
+        assert!(info.derived);
+        assert_eq!(debug.impls[0].name, "impl Debug for Bytes<T>");
+        assert_eq!(debug.impls[0].line_range.start().get(), 4);
+
+        let traits = index.implemented_traits("bytes::Bytes<u8>", None);
+        let names = traits
+            .impls
+            .iter()
+            .map(|item| item.impl_info.as_ref().unwrap().trait_path.clone().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                "Clone",
+                "Debug",
+                "serde::Serialize",
+                "Read",
+                "std::fmt::Display"
+            ]
+        );
+        // `ReadExt` applies through `Read`, the `Write` blanket impl does not.
+        assert_eq!(traits.blanket.len(), 1);
+        assert_eq!(
+            traits.blanket[0].impl_info.as_ref().unwrap().trait_name(),
+            Some("ReadExt")
+        );
+    }
+
+    #[test]
+    fn record_parse_errors() {
+        let mut builder = SearchIndexBuilder::default();
+        assert!(builder.update("src/lib.rs", "pub struct Foo;"));
+        assert!(!builder.update("src/broken.rs", "pub struct Bar {\n    x: u8,\n"));
+        let index = builder.finish();
+
+        assert_eq!(index.parse_errors.len(), 1);
+        let error = &index.parse_errors[0];
+        assert_eq!(error.file.as_ref(), Path::new("src/broken.rs"));
+        assert!(!error.message.is_empty());
+        assert!(index.parse_errors(Some(Path::new("src"))).len() == 1);
+        assert!(index.parse_errors(Some(Path::new("examples"))).is_empty());
+    }
+
+    #[test]
+    fn report_invalid_manifest() -> anyhow::Result<()> {
+        let krate = build_crate(
+            "broken",
+            "0.1.0",
+            &[
+                ("Cargo.toml", b"[package\nname = \"broken\"\n"),
+                ("src/lib.rs", b""),
+            ],
+        )?;
+        assert!(krate.manifest().is_none());
+        assert!(krate.manifest_error().is_some());
+
+        let krate = build_crate("bare", "0.1.0", &[("src/lib.rs", b"")])?;
+        assert!(krate.manifest().is_none());
+        assert!(krate.manifest_error().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn inherit_module_cfgs() -> anyhow::Result<()> {
+        let krate = build_crate(
+            "sockets",
+            "0.1.0",
+            &[
+                (
+                    "Cargo.toml",
+                    b"[package]\nname = \"sockets\"\n\n[features]\nfull = [\"net\"]\nnet = []\ntcp = []\n",
+                ),
+                (
+                    "src/lib.rs",
+                    b"#[cfg(feature = \"net\")]\npub mod net;\n\n#[cfg(unix)]\nmod sys {\n    pub struct Fd;\n}\n",
+                ),
+                ("src/net.rs", b"mod tcp;\npub struct Socket;"),
+                (
+                    "src/net/tcp.rs",
+                    b"#[cfg_attr(docsrs, doc(cfg(feature = \"tcp\")))]\npub struct TcpStream;",
+                ),
+            ],
+        )?;
+
+        let index = krate.item_index();
+        let cfg_of = |name: &str| {
+            index.structs[name][0]
+                .cfg
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default()
+        };
+        assert_eq!(cfg_of("fd"), "unix");
+        assert_eq!(cfg_of("socket"), r#"feature = "net""#);
+        assert_eq!(
+            cfg_of("tcpstream"),
+            r#"all(feature = "net", feature = "tcp")"#
+        );
+
+        let visible = |features: Option<&str>, target: Option<&str>| -> anyhow::Result<_> {
+            let query = ItemQuery {
+                type_: ItemType::Struct,
+                query: String::new(),
+                path: None,
+                features: features.map(String::from),
+                no_default_features: false,
+                target: target.map(String::from),
+            };
+            let mut names = krate
+                .search_item(&query)?
+                .items
+                .into_iter()
+                .map(|item| item.name)
+                .collect::<Vec<_>>();
+            names.sort();
+            Ok(names)
+        };
+        // `full` enables `net` through the manifest.
+        assert_eq!(
+            visible(Some("full"), Some("x86_64-pc-windows-msvc"))?,
+            vec!["Socket"]
+        );
+        assert_eq!(
+            visible(Some("net,tcp"), Some("x86_64-unknown-linux-gnu"))?,
+            vec!["Fd", "Socket", "TcpStream"]
+        );
+        assert_eq!(
+            visible(Some(""), Some("x86_64-unknown-linux-gnu"))?,
+            vec!["Fd"]
+        );
+        // Without features or a target, nothing is filtered out.
+        assert_eq!(visible(None, None)?, vec!["Fd", "Socket", "TcpStream"]);
+        // A malformed target is reported as such, so the API can answer 400.
+        assert!(visible(None, Some("foo"))
+            .unwrap_err()
+            .is::<cfg::InvalidTarget>());
+        Ok(())
+    }
+
+    #[test]
+    fn diff_public_api() {
+        let index = |lib: &str, bytes: &str| {
+            let mut builder = SearchIndexBuilder::default();
+            builder.update("src/lib.rs", lib);
+            builder.update("src/bytes.rs", bytes);
+            builder.finish()
+        };
+        let old = index(
+            r#"
+mod bytes;
+pub use bytes::Bytes;
+pub use std::borrow::Cow;
+
+pub enum Kind { A, B }
+#[non_exhaustive]
+pub enum Error { Io }
+pub struct Config { pub size: usize }
+pub trait Buf {
+    fn remaining(&self) -> usize;
+}
+pub fn parse(input: &str) -> Option<Vec<u8>> { None }
+pub mod old { pub fn helper() {} }
+"#,
+            "pub struct Bytes;\nimpl Bytes {\n    pub fn len(&self) -> usize { 0 }\n}",
+        );
+        let new = index(
+            r#"
+mod bytes;
+pub use bytes::Bytes;
+
+pub enum Kind { A, B, C }
+#[non_exhaustive]
+pub enum Error { Io, Parse }
+pub struct Config { pub size: usize, pub depth: u8 }
+pub trait Buf {
+    fn remaining(&self) -> usize;
+    fn has_remaining(&self) -> bool { true }
+}
+pub fn parse(input: &str, strict: bool) -> Option<Vec<u8>> { None }
+pub mod new { pub fn helper() {} }
+"#,
+            "pub struct Bytes;\nimpl Bytes {\n    pub fn len(&self) -> usize { 0 }\n    pub fn is_empty(&self) -> bool { true }\n}",
+        );
+
+        let parse = new.functions["parse"][0].clone();
+        assert_eq!(parse.path.as_deref(), Some("crate::parse"));
+        assert_eq!(
+            parse.signature.as_deref(),
+            Some("pub fn parse(input: &str, strict: bool) -> Option<Vec<u8>>")
+        );
+        // Signatures are only reported by the API diff, not with search results.
+        assert!(serde_json::to_value(&parse)
+            .unwrap()
+            .get("signature")
+            .is_none());
+        let bytes = &new.structs["bytes"][0];
+        assert_eq!(bytes.path.as_deref(), Some("crate::bytes::Bytes"));
+        assert!(bytes.public && !bytes.exported);
+
+        let versions = (semver::Version::new(1, 2, 0), semver::Version::new(1, 3, 0));
+        let diff = diff::diff_api(&old, &new, Some((&versions.0, &versions.1)));
+        let change = |path: &str| {
+            diff.changes
+                .iter()
+                .find(|change| change.path == path)
+                .map(|change| (change.kind, change.breaking))
+        };
+        use diff::ChangeKind::*;
+        assert_eq!(change("crate::Kind"), Some((Changed, true)));
+        assert_eq!(change("crate::Error"), Some((Changed, false)));
+        assert_eq!(change("crate::Config"), Some((Changed, true)));
+        assert_eq!(change("crate::Buf"), Some((Changed, false)));
+        assert_eq!(change("crate::parse"), Some((Changed, true)));
+        assert_eq!(change("crate::old::helper"), Some((Moved, true)));
+        assert_eq!(change("impl crate::bytes::Bytes"), Some((Changed, false)));
+        assert_eq!(change("crate::Bytes"), None);
+        assert_eq!(diff.reexports.len(), 1);
+        assert_eq!(diff.reexports[0].path, "crate::Cow");
+        assert!(diff.reexports[0].breaking);
+        assert_eq!(diff.required_bump, diff::SemverBump::Major);
+        assert_eq!(diff.actual_bump, Some(diff::SemverBump::Minor));
+        assert!(diff.semver_violation);
+    }
+
+    #[test]
+    fn compute_registry_index_paths() {
+        use crate::download::index_path;
+        assert_eq!(index_path("a").as_deref(), Some("1/a"));
+        assert_eq!(index_path("cc").as_deref(), Some("2/cc"));
+        assert_eq!(index_path("syn").as_deref(), Some("3/s/syn"));
+        assert_eq!(
+            index_path("Serde_json").as_deref(),
+            Some("se/rd/serde_json")
+        );
+        // Names from the URL are not trusted: non-ASCII names would split a character.
+        assert_eq!(index_path("aéb"), None);
+        assert_eq!(index_path(""), None);
+        assert_eq!(index_path("../etc"), None);
+        assert_eq!(index_path(&"a".repeat(65)), None);
+    }
+
+    #[test]
+    fn key_impls_by_their_self_type() {
+        let index = |lib: &str, a: &str, b: &str| {
+            let mut builder = SearchIndexBuilder::default();
+            builder.update("src/lib.rs", lib);
+            builder.update("src/a.rs", a);
+            builder.update("src/b.rs", b);
+            builder.finish()
+        };
+        let lib =
+            "pub mod a;\npub mod b;\npub(crate) struct Helper;\nimpl Helper { fn run(&self) {} }";
+        let old = index(
+            lib,
+            "pub struct Error;\nimpl std::fmt::Display for Error {}",
+            "use std::fmt;\npub struct Error;\nimpl fmt::Display for Error {}\nimpl From<u8> for Error {}",
+        );
+        let new = index(
+            "pub mod a;\npub mod b;\npub(crate) struct Helper;",
+            "pub struct Error;\nimpl std::fmt::Display for Error {}",
+            "use std::fmt;\npub struct Error;\nimpl fmt::Display for Error {}\nimpl From<u16> for Error {}",
+        );
+
+        let api = search::PublicApi::new(&old);
+        let impls = api
+            .items
+            .keys()
+            .filter(|(type_, _)| *type_ == ItemType::ImplTraitForType)
+            .map(|(_, path)| path.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            impls,
+            [
+                "impl From<u8> for crate::b::Error",
+                "impl fmt::Display for crate::b::Error",
+                "impl std::fmt::Display for crate::a::Error",
+            ]
+        );
+        // Impls of private types are not part of the public API.
+        assert!(!api.items.keys().any(|(_, path)| path.contains("Helper")));
+
+        let diff = diff::diff_api(&old, &new, None);
+        let changes = diff
+            .changes
+            .iter()
+            .map(|change| (change.kind, change.path.as_str()))
+            .collect::<Vec<_>>();
+        use diff::ChangeKind::*;
+        assert_eq!(
+            changes,
+            [
+                (Added, "impl From<u16> for crate::b::Error"),
+                (Removed, "impl From<u8> for crate::b::Error"),
+            ]
+        );
+    }
+
+    /// Builds a crate from in-memory files.
+    fn build_crate(name: &str, version: &str, files: &[(&str, &[u8])]) -> anyhow::Result<Crate> {
+        let crate_version = CrateVersion::from((name, version));
+        let mut builder = tar::Builder::new(Vec::new());
+        for (path, content) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            let path = crate_version.root_dir().join(path);
+            builder.append_data(&mut header, path, *content)?;
+        }
+        let tar_data = builder.into_inner()?;
+        Ok(Crate::try_from(CrateTar::from((crate_version, tar_data)))?)
+    }
+
+    #[test]
+    fn diff_crate_files() -> anyhow::Result<()> {
+        let krate = |version: &str, files: &[(&str, &[u8])]| build_crate("demo", version, files);
+        let old = krate(
+            "0.1.0",
+            &[
+                ("Cargo.toml", b"[package]\nname = \"demo\"\n"),
+                ("src/lib.rs", b"mod a;\npub fn f() {}\n"),
+                ("src/a.rs", b"pub struct A;\n"),
+                ("logo.png", &[0xff, 0xfe, 0x00]),
+            ],
+        )?;
+        let new = krate(
+            "0.2.0",
+            &[
+                ("Cargo.toml", b"[package]\nname = \"demo\"\n"),
+                ("src/lib.rs", b"mod b;\npub fn f() {}\n"),
+                ("src/b.rs", b"pub struct B;\n"),
+                ("logo.png", &[0xff, 0xfe, 0x01]),
+            ],
+        )?;
+
+        let all = diff::diff_files(&old, &new, None);
+        assert_eq!(all.added, vec![PathBuf::from("src/b.rs")]);
+        assert_eq!(all.removed, vec![PathBuf::from("src/a.rs")]);
+        assert_eq!(
+            all.modified,
+            vec![PathBuf::from("logo.png"), PathBuf::from("src/lib.rs")]
+        );
+        assert_eq!(all.unchanged, 1);
+        let src = diff::diff_files(&old, &new, Some(Path::new("src")));
+        assert!(src.modified == vec![PathBuf::from("src/lib.rs")] && src.unchanged == 0);
+
+        let lib = diff::diff_file(&old, &new, Path::new("src/lib.rs"), 3).unwrap();
+        assert_eq!(
+            lib,
+            "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,2 +1,2 @@\n-mod a;\n+mod b;\n pub fn f() {}\n"
+        );
+        let added = diff::diff_file(&old, &new, Path::new("src/b.rs"), 3).unwrap();
+        assert!(added.starts_with("--- /dev/null\n+++ b/src/b.rs\n"));
+        let binary = diff::diff_file(&old, &new, Path::new("logo.png"), 3).unwrap();
+        assert_eq!(binary, "Binary files a/logo.png and b/logo.png differ\n");
+        assert_eq!(
+            diff::diff_file(&old, &new, Path::new("Cargo.toml"), 3).as_deref(),
+            Some("")
+        );
+        assert!(diff::diff_file(&old, &new, Path::new("missing.rs"), 3).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn find_usages_in_dependent_crate() -> anyhow::Result<()> {
+        let target = build_crate(
+            "demo",
+            "0.1.0",
+            &[
+                ("Cargo.toml", b"[package]\nname = \"demo\"\n"),
+                (
+                    "src/lib.rs",
+                    b"pub mod codec;\npub use codec::{Codec, Encoder};\n",
+                ),
+                (
+                    "src/codec.rs",
+                    b"pub struct Encoder;\nimpl Encoder {\n    pub fn new() -> Self { Encoder }\n}\npub trait Codec {}\n",
+                ),
+            ],
+        )?;
+        let dependent = build_crate(
+            "app",
+            "1.0.0",
+            &[
+                (
+                    "Cargo.toml",
+                    b"[package]\nname = \"app\"\n[dependencies]\nenc = { package = \"demo\", version = \"0.1\" }\n",
+                ),
+                (
+                    "src/lib.rs",
+                    b"use enc::codec::Encoder as E;\nuse enc::*;\nmod inner;\npub fn run() {\n    let e = E::new();\n}\n#[derive(Codec)]\npub struct S;\n",
+                ),
+                (
+                    "src/inner.rs",
+                    b"use super::E;\npub fn other() -> E {\n    E::new()\n}\npub fn unrelated() {\n    Vec::<u8>::new();\n}\n",
+                ),
+            ],
+        )?;
+
+        let new = xref::UsageTarget::new(target.item_index(), "demo", "Encoder::new");
+        assert_eq!(new.member.as_deref(), Some("new"));
+        let (usages, incomplete) = xref::find_usages(&dependent, &new)?;
+        assert!(!incomplete);
+        let found = usages
+            .iter()
+            .map(|usage| (usage.file.to_str().unwrap(), usage.line.get(), usage.kind))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![
+                ("src/inner.rs", 3, xref::UsageKind::Path),
+                ("src/lib.rs", 5, xref::UsageKind::Path),
+            ]
+        );
+        assert_eq!(usages[1].path, "E::new");
+        assert_eq!(usages[1].resolved, "enc::codec::Encoder::new");
+        assert_eq!(usages[1].snippet, "let e = E::new();");
+
+        let codec = xref::UsageTarget::new(target.item_index(), "demo", "demo::Codec");
+        let (usages, _) = xref::find_usages(&dependent, &codec)?;
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].kind, xref::UsageKind::Derive);
+        assert_eq!(usages[0].resolved, "enc::Codec");
+
+        let unrelated = build_crate(
+            "other",
+            "1.0.0",
+            &[("Cargo.toml", b"[package]\nname = \"other\"\n")],
+        )?;
+        assert!(xref::find_usages(&unrelated, &codec).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn goto_definition_and_references() -> anyhow::Result<()> {
+        let target = build_crate(
+            "demo",
+            "0.1.0",
+            &[
+                ("Cargo.toml", b"[package]\nname = \"demo\"\n"),
+                ("src/lib.rs", b"mod codec;\npub use codec::Encoder;\n"),
+                ("src/codec.rs", b"pub struct Encoder;\n"),
+            ],
+        )?;
+        let krate = build_crate(
+            "app",
+            "1.0.0",
+            &[
+                (
+                    "Cargo.toml",
+                    b"[package]\nname = \"app\"\n[dependencies]\nenc = { package = \"demo\", version = \"0.1\" }\n",
+                ),
+                (
+                    "src/lib.rs",
+                    b"mod inner;\npub use inner::helper;\npub struct S;\nimpl S {\n    pub fn make() -> Self { S }\n}\npub fn run() -> enc::Encoder {\n    helper();\n    S::make();\n    enc::Encoder\n}\n",
+                ),
+                ("src/inner.rs", b"use super::S;\npub fn helper() -> S {\n    S\n}\n"),
+            ],
+        )?;
+        let parsed = xref::ParsedCrate::new(&krate);
+        let lib = Path::new("src/lib.rs");
+        assert!(parsed.symbol_at(lib, 7, 1).is_none());
+
+        let helper = parsed.symbol_at(lib, 8, 5).unwrap();
+        assert_eq!(helper.name, "helper");
+        let xref::Resolution::Local(path, items) = xref::resolve_symbol(&krate, &helper) else {
+            panic!("`helper` should resolve within the crate");
+        };
+        assert_eq!(path.join("::"), "crate::inner::helper");
+        assert_eq!(items[0].file.as_ref(), Path::new("src/inner.rs"));
+
+        let make = parsed.symbol_at(lib, 9, 9).unwrap();
+        assert_eq!(make.path, "S::make");
+        let xref::Resolution::Local(_, items) = xref::resolve_symbol(&krate, &make) else {
+            panic!("`S::make` should resolve within the crate");
+        };
+        assert_eq!(items[0].line_range.start().get(), 5);
+        assert_eq!(items[0].path.as_deref(), Some("crate::S::make"));
+
+        let encoder = parsed.symbol_at(lib, 10, 10).unwrap();
+        let xref::Resolution::Dependency(dependency, path) = xref::resolve_symbol(&krate, &encoder)
+        else {
+            panic!("`enc::Encoder` should lead to a dependency");
+        };
+        assert_eq!(dependency.package_name(), "demo");
+        let lib_root = target.item_index().lib_root.clone();
+        let xref::Resolution::Local(_, items) = xref::resolve_path(&target, path, &lib_root) else {
+            panic!("`Encoder` should be found in the dependency");
+        };
+        assert_eq!(items[0].file.as_ref(), Path::new("src/codec.rs"));
+
+        let references = xref::find_references(&krate, "S");
+        let found = references
+            .usages
+            .iter()
+            .map(|usage| (usage.file.to_str().unwrap(), usage.line.get()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![
+                ("src/inner.rs", 1),
+                ("src/inner.rs", 2),
+                ("src/inner.rs", 3),
+                ("src/lib.rs", 4),
+                ("src/lib.rs", 5),
+                ("src/lib.rs", 9),
+            ]
+        );
+        let references = xref::find_references(&krate, "app::S::make");
+        assert_eq!(references.usages.len(), 1);
+        assert_eq!(references.usages[0].snippet, "S::make();");
+        Ok(())
+    }
+
+    #[test]
+    fn rank_methods_of_referenced_types_first() -> anyhow::Result<()> {
+        let krate = build_crate(
+            "shapes",
+            "0.1.0",
+            &[
+                ("src/lib.rs", b"mod a;\nmod b;\nmod user;\n"),
+                (
+                    "src/a.rs",
+                    b"pub struct A;\nimpl A {\n    pub fn len(&self) -> usize { 0 }\n}\n",
+                ),
+                (
+                    "src/b.rs",
+                    b"pub struct B;\nimpl B {\n    pub fn len(&self) -> usize { 1 }\n}\n",
+                ),
+                (
+                    "src/user.rs",
+                    b"use crate::b::B;\npub fn size(b: B) -> usize {\n    b.len()\n}\n",
+                ),
+            ],
+        )?;
+        // The index is built once and shared by the clones of the crate.
+        assert!(std::ptr::eq(krate.xref_index(), krate.clone().xref_index()));
+
+        let user = Path::new("src/user.rs");
+        let parsed = xref::ParsedCrate::with_filter(&krate, |path, _| path == user);
+        assert_eq!(parsed.files.len(), 1);
+        let len = parsed.symbol_at(user, 3, 7).unwrap();
+        assert_eq!(len.kind, xref::UsageKind::MethodCall);
+        let xref::Resolution::Local(_, items) = xref::resolve_symbol(&krate, &len) else {
+            panic!("`len` should resolve to the methods of the crate");
+        };
+        let files = items
+            .iter()
+            .map(|item| item.file.to_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(files, vec!["src/b.rs", "src/a.rs"]);
+        Ok(())
+    }
+
+    #[test]
+    fn crate_overview() -> anyhow::Result<()> {
+        let krate = build_crate(
+            "demo",
+            "0.1.0",
+            &[
+                (
+                    "Cargo.toml",
+                    b"[package]\nname = \"demo\"\nversion = \"0.1.0\"\nbuild = \"build.rs\"\n\n\
+                      [features]\nfull = []\n\n[dependencies]\nbytes = \"1\"\n\n\
+                      [dev-dependencies]\ntokio = \"1\"\n\n[[example]]\nname = \"hello\"\n",
+                ),
+                ("README.md", b"# Demo\n\nA demo crate.\n"),
+                ("build.rs", b"fn main() {}\n"),
+                (
+                    "src/lib.rs",
+                    b"#![doc = include_str!(\"../README.md\")]\n\n\
+                      /// Codecs.\npub mod codec;\nmod util;\npub mod io;\n\n\
+                      pub use codec::Encoder;\npub fn run() {}\n",
+                ),
+                (
+                    "src/codec.rs",
+                    b"pub struct Encoder;\n\npub struct Decoder;\n",
+                ),
+                ("src/util.rs", b"pub fn helper() {}\n"),
+                (
+                    "src/io/mod.rs",
+                    b"//! Input and output.\n\npub trait Read {}\n",
+                ),
+                ("examples/hello.rs", b"fn main() {}\n"),
+            ],
+        )?;
+
+        let overview = krate.overview();
+        assert_eq!(overview.package.unwrap().name, "demo");
+        assert_eq!(overview.features, vec!["full"]);
+        assert_eq!(overview.dependencies.normal, 1);
+        assert_eq!(overview.dependencies.dev, 1);
+        assert_eq!(overview.docs.as_deref(), Some("# Demo\n\nA demo crate."));
+        assert_eq!(overview.readme.as_deref(), Some("README.md"));
+
+        let modules = overview
+            .modules
+            .iter()
+            .map(|module| (module.name.as_str(), module.doc.as_deref()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            modules,
+            vec![
+                ("codec", Some("Codecs.")),
+                ("io", Some("Input and output."))
+            ]
+        );
+        assert_eq!(
+            overview.modules[1].file.as_deref(),
+            Some(Path::new("src/io/mod.rs"))
+        );
+        assert_eq!(overview.reexports.len(), 1);
+        assert_eq!(overview.reexports[0].path, "crate::Encoder");
+
+        assert_eq!(overview.item_counts[&ItemType::Struct], 2);
+        assert_eq!(overview.item_counts[&ItemType::Function], 4);
+        assert_eq!(overview.public_item_counts[&ItemType::Function], 1);
+
+        let lines = overview
+            .lines_of_code
+            .iter()
+            .map(|dir| (dir.path.to_str().unwrap(), dir.files, dir.lines))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            vec![
+                ("", 1, 1),
+                ("examples", 1, 1),
+                ("src", 3, 10),
+                ("src/io", 1, 2)
+            ]
+        );
+        assert_eq!(overview.examples, 1);
+        assert_eq!(overview.tests, 0);
+        assert!(overview.build_script);
+        assert!(!overview.proc_macro);
+        assert!(!overview.incomplete);
+
+        // The modules of a binary-only crate are looked up under its binary root.
+        let krate = build_crate(
+            "tool",
+            "0.1.0",
+            &[
+                (
+                    "Cargo.toml",
+                    b"[package]\nname = \"tool\"\nversion = \"0.1.0\"\n",
+                ),
+                (
+                    "src/main.rs",
+                    b"pub mod cli;\npub mod inline {}\n#[path = \"gone.rs\"]\npub mod missing;\nfn main() {}\n",
+                ),
+                ("src/cli.rs", b"pub fn run() {}\n"),
+            ],
+        )?;
+        let overview = krate.overview();
+        let modules = overview
+            .modules
+            .iter()
+            .map(|module| (module.name.as_str(), module.file.as_deref()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            modules,
+            vec![
+                ("cli", Some(Path::new("src/cli.rs"))),
+                ("inline", Some(Path::new("src/main.rs"))),
+                ("missing", None)
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn build_crate_from_repository_tarball() -> anyhow::Result<()> {
+        let mut builder = tar::Builder::new(Vec::new());
+        let files: &[(&str, &[u8])] = &[
+            ("pax_global_header", b"52 comment=0123abc\n"),
+            ("owner-repo-0123abc/README.md", b"# Repo\n"),
+            (
+                "owner-repo-0123abc/src/lib.rs",
+                b"pub mod io;\npub struct Repo;\n",
+            ),
+            ("owner-repo-0123abc/src/io.rs", b"pub trait Read {}\n"),
+        ];
+        for (path, content) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, path, *content)?;
+        }
+        let krate = Crate::from_repository_tar(&builder.into_inner()?)?;
+
+        assert!(krate.get_file_desc("pax_global_header").is_none());
+        assert!(krate.get_file_desc("src/lib.rs").is_some());
+        let root = krate.read_directory("").unwrap();
+        assert!(root.files.contains(Path::new("README.md")));
+        assert!(root.directories.contains(Path::new("src")));
+
+        let result = krate.search_item(&ItemQuery {
+            type_: ItemType::Trait,
+            query: "read".to_string(),
+            path: None,
+            features: None,
+            no_default_features: false,
+            target: None,
+        })?;
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].path.as_deref(), Some("crate::io::Read"));
+        Ok(())
+    }
+
+    #[test]
+    fn evict_crates_over_shared_memory_budget() -> anyhow::Result<()> {
+        let repository = |content: &[u8]| -> anyhow::Result<Crate> {
+            let mut builder = tar::Builder::new(Vec::new());
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, "owner-repo-0123abc/src/lib.rs", content)?;
+            Ok(Crate::from_repository_tar(&builder.into_inner()?)?)
+        };
+        let commit = |sha: &str| RepositoryCommit {
+            repo: Repository::from(("owner", "repo")),
+            sha: Arc::from(sha),
+        };
+
+        let first = repository(&[b'a'; 600])?;
+        let second = repository(&[b'b'; 600])?;
+        let budget = cache::MemoryBudget::new(1000);
+        let cache = CrateCache::with_memory_budget(NonZeroUsize::new(16).unwrap(), budget.clone());
+
+        cache.set_repository(commit("1"), first);
+        assert_eq!(budget.used(), 600);
+        cache.set_repository(commit("2"), second);
+        assert!(cache.get_repository(&commit("1")).is_none());
+        assert!(cache.get_repository(&commit("2")).is_some());
+        assert_eq!(budget.used(), 600);
+
+        drop(cache);
+        assert_eq!(budget.used(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn release_memory_of_crates_evicted_at_capacity() -> anyhow::Result<()> {
+        let budget = cache::MemoryBudget::new(1 << 20);
+        let cache = CrateCache::with_memory_budget(NonZeroUsize::new(2).unwrap(), budget.clone());
+        let mut sizes = Vec::new();
+        for minor in 0..10 {
+            let version = format!("0.{minor}.0");
+            let krate = build_crate("budget", &version, &[("src/lib.rs", b"pub fn f() {}")])?;
+            sizes.push(krate.memory_usage());
+            cache.set_crate(CrateVersion::from(("budget", version.as_str())), krate);
+        }
+        assert_eq!(budget.used(), sizes[8] + sizes[9]);
+        assert!(cache
+            .get_crate(&CrateVersion::from(("budget", "0.0.0")))
+            .is_none());
+
+        // Replacing a crate releases the previous one.
+        let krate = build_crate("budget", "0.9.0", &[("src/lib.rs", b"pub fn g() {}")])?;
+        let size = krate.memory_usage();
+        assert!(cache
+            .set_crate(CrateVersion::from(("budget", "0.9.0")), krate)
+            .is_some());
+        assert_eq!(budget.used(), sizes[8] + size);
+        Ok(())
+    }
+
+    #[test]
+    fn keep_each_cache_within_its_share_of_the_budget() {
+        struct Blob(usize);
+        impl cache::MemoryUsage for Blob {
+            fn memory_usage(&self) -> usize {
+                self.0
+            }
+        }
+
+        let budget = cache::MemoryBudget::new(1000);
+        let capacity = NonZeroUsize::new(16).unwrap();
+        let mut crates = cache::BudgetedLru::new(capacity, budget.clone(), 60);
+        let mut responses = cache::BudgetedLru::new(capacity, budget.clone(), 10);
+        crates.put(1, Blob(500));
+        for key in 0..10 {
+            responses.put(key, Blob(40));
+        }
+        // The responses are limited to 100 bytes without evicting the crate.
+        assert!(crates.get(&1).is_some());
+        assert_eq!(responses.used(), 80);
+        assert_eq!(budget.used(), 580);
+    }
+
+    #[test]
+    fn read_line_range_of_file_content() -> anyhow::Result<()> {
+        let content = FileContent::from(bytes::Bytes::from_static(b"one\ntwo\nthree\nfour\n"));
+        let range = |start: Option<usize>, end: Option<usize>| FileLineRange {
+            start: start.and_then(NonZeroUsize::new),
+            end: end.and_then(NonZeroUsize::new),
+        };
+
+        let middle = content
+            .get_file_line_range(range(Some(2), Some(3)))?
+            .unwrap();
+        assert_eq!(middle.data.as_ref(), b"two\nthree\n");
+        let tail = content.get_file_line_range(range(Some(4), None))?.unwrap();
+        assert_eq!(tail.data.as_ref(), b"four\n");
+        let whole = content.get_file_line_range(range(None, None))?.unwrap();
+        assert_eq!(whole.data.len(), content.data.len());
+        assert!(content.get_file_line_range(range(Some(9), None))?.is_none());
+
+        let binary = FileContent::from(bytes::Bytes::from_static(b"\xff\xfe"));
+        assert!(binary.get_file_line_range(range(Some(1), None)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn map_crate_version_to_repository_source() -> anyhow::Result<()> {
+        let krate = build_crate(
+            "codec",
+            "1.2.0",
+            &[
+                (
+                    "Cargo.toml",
+                    b"[package]\nname = \"codec\"\nversion = \"1.2.0\"\nrepository = \"https://github.com/owner/codecs.git\"\n",
+                ),
+                (
+                    ".cargo_vcs_info.json",
+                    b"{\n  \"git\": {\n    \"sha1\": \"0123456789abcdef0123456789abcdef01234567\"\n  },\n  \"path_in_vcs\": \"crates/codec\"\n}",
+                ),
+                ("src/lib.rs", b"pub struct Codec;\n"),
+            ],
+        )?;
+        let source = vcs::CrateSource::of(&krate);
+        assert_eq!(source.github, Some(Repository::from(("owner", "codecs"))));
+        assert_eq!(
+            source.commit_sha.as_deref(),
+            Some("0123456789abcdef0123456789abcdef01234567")
+        );
+        assert!(!source.dirty);
+        assert_eq!(
+            source.repository_path("src/lib.rs"),
+            "crates/codec/src/lib.rs"
+        );
+        assert_eq!(
+            source.repository_path("Cargo.toml.orig"),
+            "crates/codec/Cargo.toml"
+        );
+
+        let krate = build_crate("plain", "0.1.0", &[("src/lib.rs", b"")])?;
+        let source = vcs::CrateSource::of(&krate);
+        assert!(source.github.is_none() && source.commit_sha.is_none());
+        assert_eq!(source.repository_path("src/lib.rs"), "src/lib.rs");
+
+        for url in [
+            "https://github.com/owner/repo",
+            "https://www.github.com/owner/repo/",
+            "git+https://github.com/owner/repo.git",
+            "ssh://git@github.com/owner/repo.git",
+            "git@github.com:owner/repo.git",
+            "https://github.com/owner/repo/tree/main/crates/foo",
+        ] {
+            assert_eq!(
+                vcs::parse_github_url(url),
+                Some(Repository::from(("owner", "repo"))),
+                "{url}"
+            );
+        }
+        assert!(vcs::parse_github_url("https://gitlab.com/owner/repo").is_none());
+        assert!(vcs::parse_github_url("https://github.com/owner").is_none());
+        Ok(())
+    }
 }
@@ -0,0 +1,708 @@
+//! The `manifest` module.
+//!
+//! This module parses the normalized `Cargo.toml` shipped in published crates into a typed
+//! `Manifest`, exposing package metadata, features, dependencies and build targets without
+//! requiring clients to fetch and interpret the raw file.
+//!
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "utoipa")]
+use utoipa::ToSchema;
+
+/// The error of a crate whose `Cargo.toml` exists but cannot be parsed.
+///
+#[derive(Debug, Clone)]
+pub struct InvalidManifest(pub String);
+
+impl Display for InvalidManifest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to parse Cargo.toml: {}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidManifest {}
+
+/// Represents the parsed `Cargo.toml` manifest of a crate.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct Manifest {
+    /// The `[package]` section.
+    pub package: Package,
+    /// The features of the crate, mapped to the features and dependencies they enable.
+    pub features: BTreeMap<String, Vec<String>>,
+    /// The dependencies of the crate.
+    pub dependencies: Dependencies,
+    /// The library target, if any.
+    pub lib: Option<Target>,
+    /// The binary targets.
+    pub bins: Vec<Target>,
+    /// The example targets.
+    pub examples: Vec<Target>,
+    /// The integration test targets.
+    pub tests: Vec<Target>,
+    /// The benchmark targets.
+    pub benches: Vec<Target>,
+}
+
+/// Represents the `[package]` section of a manifest.
+///
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct Package {
+    /// The name of the package.
+    pub name: String,
+    /// The version of the package.
+    pub version: String,
+    /// The Rust edition, `2015` if unspecified.
+    pub edition: String,
+    /// The minimal supported Rust version.
+    pub rust_version: Option<String>,
+    /// The SPDX license expression.
+    pub license: Option<String>,
+    /// The path to a non-standard license file.
+    pub license_file: Option<String>,
+    /// A short description of the package.
+    pub description: Option<String>,
+    /// The URL of the package homepage.
+    pub homepage: Option<String>,
+    /// The URL of the package documentation.
+    pub documentation: Option<String>,
+    /// The URL of the package source repository.
+    pub repository: Option<String>,
+    /// The path to the README file.
+    pub readme: Option<String>,
+    /// The authors of the package.
+    pub authors: Vec<String>,
+    /// The keywords of the package.
+    pub keywords: Vec<String>,
+    /// The crates.io categories of the package.
+    pub categories: Vec<String>,
+    /// The native library the package links to.
+    pub links: Option<String>,
+    /// The path to the build script, if the package has one.
+    pub build: Option<String>,
+}
+
+/// The dependencies of a crate, split by kind and target.
+///
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct Dependencies {
+    /// The `[dependencies]` section.
+    pub normal: Vec<Dependency>,
+    /// The `[dev-dependencies]` section.
+    pub dev: Vec<Dependency>,
+    /// The `[build-dependencies]` section.
+    pub build: Vec<Dependency>,
+    /// The `[target.'...'.*dependencies]` sections.
+    pub target: Vec<TargetDependencies>,
+}
+
+/// The dependencies of a crate on a specific target platform.
+///
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct TargetDependencies {
+    /// The target triple or `cfg(...)` expression, e.g. `cfg(unix)`.
+    pub target: String,
+    /// The normal dependencies on this target.
+    pub normal: Vec<Dependency>,
+    /// The dev-dependencies on this target.
+    pub dev: Vec<Dependency>,
+    /// The build-dependencies on this target.
+    pub build: Vec<Dependency>,
+}
+
+/// Represents a single dependency declared in a manifest.
+///
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct Dependency {
+    /// The name the dependency is referred to by, which is the key in the manifest.
+    pub name: String,
+    /// The real package name if the dependency is renamed.
+    pub package: Option<String>,
+    /// The semver requirement of the dependency.
+    pub req: Option<String>,
+    /// The features enabled for the dependency.
+    pub features: Vec<String>,
+    /// Whether the dependency is optional.
+    pub optional: bool,
+    /// Whether the default features of the dependency are enabled.
+    pub default_features: bool,
+    /// The registry of the dependency, if it is not crates.io.
+    pub registry: Option<String>,
+    /// The local path of the dependency, if any.
+    pub path: Option<String>,
+    /// The git repository of the dependency, if any.
+    pub git: Option<String>,
+}
+
+impl Dependency {
+    /// Returns the name of the dependency crate on the registry, taking renames into account.
+    ///
+    pub fn package_name(&self) -> &str {
+        self.package.as_deref().unwrap_or(self.name.as_str())
+    }
+}
+
+/// Represents a build target, such as `[lib]` or a `[[bin]]` entry.
+///
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct Target {
+    /// The name of the target.
+    pub name: String,
+    /// The path of the target's root source file.
+    pub path: Option<String>,
+    /// The features required to build the target.
+    pub required_features: Vec<String>,
+    /// The crate types of the target, e.g. `cdylib`.
+    pub crate_type: Vec<String>,
+    /// Whether the target is a procedural macro library.
+    pub proc_macro: bool,
+    /// Whether the target was discovered from the file layout rather than declared explicitly.
+    pub auto_discovered: bool,
+}
+
+impl Manifest {
+    /// Parses a `Cargo.toml` manifest.
+    ///
+    /// The `files` are the relative paths of all files in the crate, used to discover targets that
+    /// are not declared explicitly, following Cargo's conventions.
+    ///
+    pub fn parse<'f>(
+        content: &str,
+        files: impl IntoIterator<Item = &'f Path>,
+    ) -> anyhow::Result<Self> {
+        let raw: raw::Manifest = toml::from_str(content)?;
+        let raw::Manifest {
+            package,
+            features,
+            dependencies,
+            dev_dependencies,
+            dev_dependencies2,
+            build_dependencies,
+            build_dependencies2,
+            target,
+            lib,
+            bin,
+            example,
+            test,
+            bench,
+        } = raw;
+
+        let Some(package) = package else {
+            anyhow::bail!("The manifest has no [package] section.");
+        };
+
+        let mut manifest = Manifest {
+            package: Package {
+                name: package.name,
+                version: package.version.into_string().unwrap_or_default(),
+                edition: package
+                    .edition
+                    .and_then(raw::MaybeWorkspace::into_string)
+                    .unwrap_or_else(|| "2015".to_string()),
+                rust_version: package
+                    .rust_version
+                    .and_then(raw::MaybeWorkspace::into_string),
+                license: package.license.and_then(raw::MaybeWorkspace::into_string),
+                license_file: package
+                    .license_file
+                    .and_then(raw::MaybeWorkspace::into_string),
+                description: package
+                    .description
+                    .and_then(raw::MaybeWorkspace::into_string),
+                homepage: package.homepage.and_then(raw::MaybeWorkspace::into_string),
+                documentation: package
+                    .documentation
+                    .and_then(raw::MaybeWorkspace::into_string),
+                repository: package
+                    .repository
+                    .and_then(raw::MaybeWorkspace::into_string),
+                readme: package.readme.and_then(raw::Readme::into_path),
+                authors: package.authors.unwrap_or_default(),
+                keywords: package.keywords.unwrap_or_default(),
+                categories: package.categories.unwrap_or_default(),
+                links: package.links,
+                build: package.build.and_then(raw::Build::into_path),
+            },
+            features: features.unwrap_or_default(),
+            dependencies: Dependencies {
+                normal: convert_dependencies(dependencies),
+                dev: convert_dependencies(dev_dependencies.or(dev_dependencies2)),
+                build: convert_dependencies(build_dependencies.or(build_dependencies2)),
+                target: target
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(target, deps)| TargetDependencies {
+                        target,
+                        normal: convert_dependencies(deps.dependencies),
+                        dev: convert_dependencies(deps.dev_dependencies.or(deps.dev_dependencies2)),
+                        build: convert_dependencies(
+                            deps.build_dependencies.or(deps.build_dependencies2),
+                        ),
+                    })
+                    .collect(),
+            },
+            lib: lib.map(|lib| lib.into_target()),
+            bins: convert_targets(bin),
+            examples: convert_targets(example),
+            tests: convert_targets(test),
+            benches: convert_targets(bench),
+        };
+
+        manifest.discover_targets(
+            files,
+            TargetDiscovery {
+                bins: package.autobins.unwrap_or(true),
+                examples: package.autoexamples.unwrap_or(true),
+                tests: package.autotests.unwrap_or(true),
+                benches: package.autobenches.unwrap_or(true),
+            },
+        );
+
+        Ok(manifest)
+    }
+
+    /// Expands a set of enabled features with all the features they transitively enable.
+    ///
+    /// Entries like `dep:name` and `name?/feature` do not enable a feature of this crate, while
+    /// `name/feature` also enables the implicit feature of an optional dependency `name`.
+    ///
+    pub fn expand_features<'f>(
+        &self,
+        features: impl IntoIterator<Item = &'f str>,
+    ) -> BTreeSet<String> {
+        let mut enabled = BTreeSet::new();
+        let mut queue = features.into_iter().map(String::from).collect::<Vec<_>>();
+        while let Some(feature) = queue.pop() {
+            if !enabled.insert(feature.clone()) {
+                continue;
+            }
+            for entry in self.features.get(&feature).into_iter().flatten() {
+                if entry.starts_with("dep:") {
+                    continue;
+                }
+                match entry.split_once('/') {
+                    Some((dep, _)) if dep.ends_with('?') => {}
+                    Some((dep, _)) => queue.push(dep.to_string()),
+                    None => queue.push(entry.clone()),
+                }
+            }
+        }
+        enabled
+    }
+
+    /// Returns whether the crate's library is a procedural macro.
+    ///
+    pub fn is_proc_macro(&self) -> bool {
+        self.lib.as_ref().is_some_and(|lib| lib.proc_macro)
+    }
+
+    /// Adds the targets implied by Cargo's conventional file layout that are not declared explicitly.
+    ///
+    /// See <https://doc.rust-lang.org/cargo/reference/cargo-targets.html#target-auto-discovery>.
+    ///
+    fn discover_targets<'f>(
+        &mut self,
+        files: impl IntoIterator<Item = &'f Path>,
+        discovery: TargetDiscovery,
+    ) {
+        let package_name = self.package.name.replace('-', "_");
+        let mut lib = None;
+        let mut bins = Vec::new();
+        let mut examples = Vec::new();
+        let mut tests = Vec::new();
+        let mut benches = Vec::new();
+
+        for file in files {
+            if file == Path::new("src/lib.rs") {
+                lib = Some(file);
+            } else if file == Path::new("src/main.rs") {
+                bins.push((self.package.name.clone(), file));
+            } else if let Some(name) = conventional_target_name(file, "src/bin") {
+                bins.push((name, file));
+            } else if let Some(name) = conventional_target_name(file, "examples") {
+                examples.push((name, file));
+            } else if let Some(name) = conventional_target_name(file, "tests") {
+                tests.push((name, file));
+            } else if let Some(name) = conventional_target_name(file, "benches") {
+                benches.push((name, file));
+            }
+        }
+
+        match (&mut self.lib, lib) {
+            (Some(target), _) => {
+                if target.name.is_empty() {
+                    target.name = package_name;
+                }
+                if target.path.is_none() && lib.is_some() {
+                    target.path = Some("src/lib.rs".to_string());
+                }
+            }
+            (None, Some(path)) => {
+                self.lib = Some(Target {
+                    name: package_name,
+                    path: Some(path_string(path)),
+                    auto_discovered: true,
+                    ..Default::default()
+                });
+            }
+            (None, None) => {}
+        }
+
+        for (enabled, declared, discovered) in [
+            (discovery.bins, &mut self.bins, bins),
+            (discovery.examples, &mut self.examples, examples),
+            (discovery.tests, &mut self.tests, tests),
+            (discovery.benches, &mut self.benches, benches),
+        ] {
+            if !enabled {
+                continue;
+            }
+            for (name, path) in discovered {
+                let path = path_string(path);
+                if declared
+                    .iter()
+                    .any(|target| target.name == name || target.path.as_ref() == Some(&path))
+                {
+                    continue;
+                }
+                declared.push(Target {
+                    name,
+                    path: Some(path),
+                    auto_discovered: true,
+                    ..Default::default()
+                });
+            }
+        }
+    }
+}
+
+/// Whether each kind of target is discovered automatically.
+#[derive(Debug, Clone, Copy)]
+struct TargetDiscovery {
+    bins: bool,
+    examples: bool,
+    tests: bool,
+    benches: bool,
+}
+
+/// Returns the conventional target name of a file within a target directory.
+///
+/// Both `dir/name.rs` and `dir/name/main.rs` are recognized.
+fn conventional_target_name(file: &Path, dir: &str) -> Option<String> {
+    let relative = file.strip_prefix(dir).ok()?;
+    let mut components = relative.iter();
+    let first = PathBuf::from(components.next()?);
+    match (components.next(), components.next()) {
+        (None, _) => {
+            if first.extension()? != "rs" {
+                return None;
+            }
+            Some(first.file_stem()?.to_string_lossy().into_owned())
+        }
+        (Some(main), None) if main == "main.rs" => Some(first.to_string_lossy().into_owned()),
+        _ => None,
+    }
+}
+
+fn path_string(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+fn convert_dependencies(deps: Option<BTreeMap<String, raw::Dependency>>) -> Vec<Dependency> {
+    deps.unwrap_or_default()
+        .into_iter()
+        .map(|(name, dep)| match dep {
+            raw::Dependency::Simple(req) => Dependency {
+                name,
+                req: Some(req),
+                default_features: true,
+                ..Default::default()
+            },
+            raw::Dependency::Detailed(detail) => Dependency {
+                name,
+                package: detail.package,
+                req: detail.version,
+                features: detail.features.unwrap_or_default(),
+                optional: detail.optional.unwrap_or_default(),
+                default_features: detail
+                    .default_features
+                    .or(detail.default_features2)
+                    .unwrap_or(true),
+                registry: detail.registry,
+                path: detail.path,
+                git: detail.git,
+            },
+        })
+        .collect()
+}
+
+fn convert_targets(targets: Option<Vec<raw::Target>>) -> Vec<Target> {
+    targets
+        .unwrap_or_default()
+        .into_iter()
+        .map(|target| target.into_target())
+        .collect()
+}
+
+/// Raw deserialization types mirroring the `Cargo.toml` format.
+mod raw {
+    use serde::Deserialize;
+    use std::collections::BTreeMap;
+
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    pub struct Manifest {
+        pub package: Option<Package>,
+        pub features: Option<BTreeMap<String, Vec<String>>>,
+        pub dependencies: Option<BTreeMap<String, Dependency>>,
+        pub dev_dependencies: Option<BTreeMap<String, Dependency>>,
+        #[serde(rename = "dev_dependencies")]
+        pub dev_dependencies2: Option<BTreeMap<String, Dependency>>,
+        pub build_dependencies: Option<BTreeMap<String, Dependency>>,
+        #[serde(rename = "build_dependencies")]
+        pub build_dependencies2: Option<BTreeMap<String, Dependency>>,
+        pub target: Option<BTreeMap<String, Platform>>,
+        pub lib: Option<Target>,
+        pub bin: Option<Vec<Target>>,
+        pub example: Option<Vec<Target>>,
+        pub test: Option<Vec<Target>>,
+        pub bench: Option<Vec<Target>>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    pub struct Package {
+        pub name: String,
+        #[serde(default)]
+        pub version: MaybeWorkspace,
+        pub edition: Option<MaybeWorkspace>,
+        pub rust_version: Option<MaybeWorkspace>,
+        pub license: Option<MaybeWorkspace>,
+        pub license_file: Option<MaybeWorkspace>,
+        pub description: Option<MaybeWorkspace>,
+        pub homepage: Option<MaybeWorkspace>,
+        pub documentation: Option<MaybeWorkspace>,
+        pub repository: Option<MaybeWorkspace>,
+        pub readme: Option<Readme>,
+        pub authors: Option<Vec<String>>,
+        pub keywords: Option<Vec<String>>,
+        pub categories: Option<Vec<String>>,
+        pub links: Option<String>,
+        pub build: Option<Build>,
+        pub autobins: Option<bool>,
+        pub autoexamples: Option<bool>,
+        pub autotests: Option<bool>,
+        pub autobenches: Option<bool>,
+    }
+
+    /// A string field, which is `{ workspace = true }` in unnormalized manifests.
+    #[derive(Debug, Default, Deserialize)]
+    #[serde(untagged)]
+    pub enum MaybeWorkspace {
+        Value(String),
+        Workspace {},
+        #[default]
+        Missing,
+    }
+
+    impl MaybeWorkspace {
+        pub fn into_string(self) -> Option<String> {
+            match self {
+                MaybeWorkspace::Value(value) => Some(value),
+                _ => None,
+            }
+        }
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[serde(untagged)]
+    pub enum Readme {
+        Path(String),
+        Enabled(bool),
+    }
+
+    impl Readme {
+        pub fn into_path(self) -> Option<String> {
+            match self {
+                Readme::Path(path) => Some(path),
+                Readme::Enabled(true) => Some("README.md".to_string()),
+                Readme::Enabled(false) => None,
+            }
+        }
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[serde(untagged)]
+    pub enum Build {
+        Path(String),
+        Enabled(bool),
+    }
+
+    impl Build {
+        pub fn into_path(self) -> Option<String> {
+            match self {
+                Build::Path(path) => Some(path),
+                Build::Enabled(true) => Some("build.rs".to_string()),
+                Build::Enabled(false) => None,
+            }
+        }
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[serde(untagged)]
+    pub enum Dependency {
+        Simple(String),
+        Detailed(DependencyDetail),
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    pub struct DependencyDetail {
+        pub version: Option<String>,
+        pub package: Option<String>,
+        pub features: Option<Vec<String>>,
+        pub optional: Option<bool>,
+        pub default_features: Option<bool>,
+        #[serde(rename = "default_features")]
+        pub default_features2: Option<bool>,
+        pub registry: Option<String>,
+        pub path: Option<String>,
+        pub git: Option<String>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    pub struct Platform {
+        pub dependencies: Option<BTreeMap<String, Dependency>>,
+        pub dev_dependencies: Option<BTreeMap<String, Dependency>>,
+        #[serde(rename = "dev_dependencies")]
+        pub dev_dependencies2: Option<BTreeMap<String, Dependency>>,
+        pub build_dependencies: Option<BTreeMap<String, Dependency>>,
+        #[serde(rename = "build_dependencies")]
+        pub build_dependencies2: Option<BTreeMap<String, Dependency>>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    pub struct Target {
+        pub name: Option<String>,
+        pub path: Option<String>,
+        pub required_features: Option<Vec<String>>,
+        pub crate_type: Option<Vec<String>>,
+        #[serde(rename = "crate_type")]
+        pub crate_type2: Option<Vec<String>>,
+        pub proc_macro: Option<bool>,
+        #[serde(rename = "proc_macro")]
+        pub proc_macro2: Option<bool>,
+    }
+
+    impl Target {
+        pub fn into_target(self) -> super::Target {
+            let crate_type = self.crate_type.or(self.crate_type2).unwrap_or_default();
+            super::Target {
+                name: self.name.unwrap_or_default(),
+                path: self.path,
+                required_features: self.required_features.unwrap_or_default(),
+                proc_macro: self.proc_macro.or(self.proc_macro2).unwrap_or_default()
+                    || crate_type.iter().any(|t| t == "proc-macro"),
+                crate_type,
+                auto_discovered: false,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_normalized_manifest() -> anyhow::Result<()> {
+        let content = r#"
+[package]
+edition = "2021"
+rust-version = "1.63"
+name = "demo"
+version = "0.3.1"
+build = "build.rs"
+autobins = false
+license = "MIT OR Apache-2.0"
+
+[lib]
+name = "demo"
+path = "src/lib.rs"
+
+[[example]]
+name = "hello"
+path = "examples/hello.rs"
+required-features = ["full"]
+
+[dependencies.serde]
+version = "1.0.100"
+features = ["derive"]
+optional = true
+
+[dependencies.bytes_crate]
+version = "1"
+package = "bytes"
+default-features = false
+
+[dev-dependencies]
+tokio = "1"
+
+[target."cfg(unix)".dependencies.libc]
+version = "0.2"
+
+[features]
+default = ["std"]
+full = ["dep:serde", "std"]
+std = []
+"#;
+        let files = [
+            "Cargo.toml",
+            "build.rs",
+            "src/lib.rs",
+            "src/bin/tool.rs",
+            "examples/hello.rs",
+            "examples/world/main.rs",
+            "tests/it.rs",
+        ];
+        let manifest = Manifest::parse(content, files.iter().map(Path::new))?;
+
+        assert_eq!(manifest.package.name, "demo");
+        assert_eq!(manifest.package.edition, "2021");
+        assert_eq!(manifest.package.rust_version.as_deref(), Some("1.63"));
+        assert_eq!(manifest.package.build.as_deref(), Some("build.rs"));
+        assert_eq!(manifest.features["full"], vec!["dep:serde", "std"]);
+
+        let normal = &manifest.dependencies.normal;
+        assert_eq!(normal.len(), 2);
+        let bytes = normal.iter().find(|d| d.name == "bytes_crate").unwrap();
+        assert_eq!(bytes.package_name(), "bytes");
+        assert!(!bytes.default_features);
+        assert!(normal.iter().any(|d| d.name == "serde" && d.optional));
+        assert_eq!(manifest.dependencies.dev[0].req.as_deref(), Some("1"));
+        assert_eq!(manifest.dependencies.target[0].target, "cfg(unix)");
+
+        assert!(manifest.lib.is_some());
+        assert!(manifest.bins.is_empty());
+        let examples = manifest
+            .examples
+            .iter()
+            .map(|t| (t.name.as_str(), t.auto_discovered))
+            .collect::<Vec<_>>();
+        assert_eq!(examples, vec![("hello", false), ("world", true)]);
+        assert_eq!(manifest.tests[0].name, "it");
+        assert!(!manifest.is_proc_macro());
+        Ok(())
+    }
+}
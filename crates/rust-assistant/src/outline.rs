@@ -0,0 +1,359 @@
+//! The `outline` module.
+//!
+//! Builds the symbol tree of a Rust source file: its modules, types, impls, traits, functions and
+//! macros, nested the way they are declared, with the line range of each symbol. Clients can use
+//! the outline to read only the relevant part of a large file.
+//!
+use crate::search::tokens;
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroUsize;
+use std::ops::RangeInclusive;
+use syn::spanned::Spanned;
+use syn::{Attribute, Expr, ImplItem, Item, Lit, Meta, TraitItem, Visibility};
+
+#[cfg(feature = "utoipa")]
+use utoipa::ToSchema;
+
+/// A symbol of a Rust source file, with the symbols declared within it.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct OutlineItem {
+    /// The name of the symbol, e.g. `Foo` or `impl Display for Foo`.
+    pub name: String,
+    /// The kind of the symbol.
+    pub kind: OutlineKind,
+    /// The range of lines of the symbol, including its doc comments and attributes.
+    #[cfg_attr(feature = "utoipa", schema(value_type = crate::RangeSchema))]
+    pub line_range: RangeInclusive<NonZeroUsize>,
+    /// The visibility of the symbol as written, e.g. `pub` or `pub(crate)`. Not set for private
+    /// symbols and for those without a visibility of their own, like impls and trait items.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<String>,
+    /// The first line of the doc comment of the symbol.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+    /// The symbols declared within this one, e.g. the items of an inline module or the methods
+    /// of an impl.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "utoipa", schema(no_recursion))]
+    pub children: Vec<OutlineItem>,
+}
+
+/// The kinds of symbols in an outline.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub enum OutlineKind {
+    /// A module, either inline or declared with `mod foo;`.
+    Module,
+    /// A struct definition.
+    Struct,
+    /// An enum definition.
+    Enum,
+    /// A variant of an enum.
+    Variant,
+    /// A union definition.
+    Union,
+    /// A trait definition.
+    Trait,
+    /// An impl block.
+    Impl,
+    /// A free function.
+    Function,
+    /// A function of an impl or a trait.
+    Method,
+    /// A constant, free or associated.
+    Const,
+    /// A static item.
+    Static,
+    /// A type alias, or an associated type.
+    TypeAlias,
+    /// A `macro_rules!` definition.
+    Macro,
+}
+
+/// Builds the outline of a Rust source file.
+///
+/// Returns the parse error if the file is not valid Rust.
+///
+pub fn outline(source: &str) -> syn::Result<Vec<OutlineItem>> {
+    let file = syn::parse_file(source)?;
+    Ok(file.items.iter().filter_map(outline_item).collect())
+}
+
+/// Returns the outline of an item, or `None` for items which are not symbols, like `use`.
+fn outline_item(item: &Item) -> Option<OutlineItem> {
+    let (name, kind, attrs, vis, children) = match item {
+        Item::Mod(i) => (
+            i.ident.to_string(),
+            OutlineKind::Module,
+            &i.attrs,
+            Some(&i.vis),
+            i.content
+                .iter()
+                .flat_map(|(_, items)| items)
+                .filter_map(outline_item)
+                .collect(),
+        ),
+        Item::Struct(i) => (
+            i.ident.to_string(),
+            OutlineKind::Struct,
+            &i.attrs,
+            Some(&i.vis),
+            Vec::new(),
+        ),
+        Item::Enum(i) => (
+            i.ident.to_string(),
+            OutlineKind::Enum,
+            &i.attrs,
+            Some(&i.vis),
+            i.variants
+                .iter()
+                .map(|variant| {
+                    symbol(
+                        variant.ident.to_string(),
+                        OutlineKind::Variant,
+                        variant.span(),
+                        &variant.attrs,
+                        None,
+                    )
+                })
+                .collect(),
+        ),
+        Item::Union(i) => (
+            i.ident.to_string(),
+            OutlineKind::Union,
+            &i.attrs,
+            Some(&i.vis),
+            Vec::new(),
+        ),
+        Item::Trait(i) => (
+            i.ident.to_string(),
+            OutlineKind::Trait,
+            &i.attrs,
+            Some(&i.vis),
+            i.items.iter().filter_map(trait_item).collect(),
+        ),
+        Item::Impl(i) => {
+            let self_ty = &i.self_ty;
+            let name = match &i.trait_ {
+                Some((bang, path, _)) => {
+                    format!(
+                        "impl {} for {}",
+                        tokens(quote::quote!(#bang #path)),
+                        tokens(self_ty)
+                    )
+                }
+                None => format!("impl {}", tokens(self_ty)),
+            };
+            (
+                name,
+                OutlineKind::Impl,
+                &i.attrs,
+                None,
+                i.items.iter().filter_map(impl_item).collect(),
+            )
+        }
+        Item::Fn(i) => (
+            i.sig.ident.to_string(),
+            OutlineKind::Function,
+            &i.attrs,
+            Some(&i.vis),
+            Vec::new(),
+        ),
+        Item::Const(i) => (
+            i.ident.to_string(),
+            OutlineKind::Const,
+            &i.attrs,
+            Some(&i.vis),
+            Vec::new(),
+        ),
+        Item::Static(i) => (
+            i.ident.to_string(),
+            OutlineKind::Static,
+            &i.attrs,
+            Some(&i.vis),
+            Vec::new(),
+        ),
+        Item::Type(i) => (
+            i.ident.to_string(),
+            OutlineKind::TypeAlias,
+            &i.attrs,
+            Some(&i.vis),
+            Vec::new(),
+        ),
+        Item::Macro(i) => (
+            i.ident.as_ref()?.to_string(),
+            OutlineKind::Macro,
+            &i.attrs,
+            None,
+            Vec::new(),
+        ),
+        _ => return None,
+    };
+    let mut symbol = symbol(name, kind, item.span(), attrs, vis);
+    symbol.children = children;
+    Some(symbol)
+}
+
+/// Returns the outline of an item of a trait.
+fn trait_item(item: &TraitItem) -> Option<OutlineItem> {
+    let (name, kind, attrs) = match item {
+        TraitItem::Fn(i) => (i.sig.ident.to_string(), OutlineKind::Method, &i.attrs),
+        TraitItem::Const(i) => (i.ident.to_string(), OutlineKind::Const, &i.attrs),
+        TraitItem::Type(i) => (i.ident.to_string(), OutlineKind::TypeAlias, &i.attrs),
+        _ => return None,
+    };
+    Some(symbol(name, kind, item.span(), attrs, None))
+}
+
+/// Returns the outline of an item of an impl.
+fn impl_item(item: &ImplItem) -> Option<OutlineItem> {
+    let (name, kind, attrs, vis) = match item {
+        ImplItem::Fn(i) => (
+            i.sig.ident.to_string(),
+            OutlineKind::Method,
+            &i.attrs,
+            &i.vis,
+        ),
+        ImplItem::Const(i) => (i.ident.to_string(), OutlineKind::Const, &i.attrs, &i.vis),
+        ImplItem::Type(i) => (
+            i.ident.to_string(),
+            OutlineKind::TypeAlias,
+            &i.attrs,
+            &i.vis,
+        ),
+        _ => return None,
+    };
+    Some(symbol(name, kind, item.span(), attrs, Some(vis)))
+}
+
+/// Creates a symbol without children.
+fn symbol(
+    name: String,
+    kind: OutlineKind,
+    span: proc_macro2::Span,
+    attrs: &[Attribute],
+    vis: Option<&Visibility>,
+) -> OutlineItem {
+    // Outer attributes are part of the item's span, but doc comments may start before it.
+    let start = attrs
+        .iter()
+        .map(|attr| attr.span().start().line)
+        .fold(span.start().line, usize::min);
+    let end = span.end().line;
+    OutlineItem {
+        name,
+        kind,
+        line_range: NonZeroUsize::new(start).unwrap_or(NonZeroUsize::MIN)
+            ..=NonZeroUsize::new(end).unwrap_or(NonZeroUsize::MAX),
+        visibility: vis
+            .filter(|vis| !matches!(vis, Visibility::Inherited))
+            .map(tokens),
+        doc: doc_summary(attrs),
+        children: Vec::new(),
+    }
+}
+
+/// Returns the first non-empty line of the outer doc comments among attributes.
+fn doc_summary(attrs: &[Attribute]) -> Option<String> {
+    attrs
+        .iter()
+        .filter(|attr| matches!(attr.style, syn::AttrStyle::Outer))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(meta) if meta.path.is_ident("doc") => match &meta.value {
+                Expr::Lit(expr) => match &expr.lit {
+                    Lit::Str(doc) => Some(doc.value()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        })
+        .flat_map(|doc| {
+            doc.lines()
+                .map(|line| line.trim().to_string())
+                .collect::<Vec<_>>()
+        })
+        .find(|line| !line.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outline_symbols() {
+        let source = r#"
+//! Crate docs.
+use std::fmt;
+
+/// A value.
+///
+/// More details.
+#[derive(Debug)]
+pub struct Foo;
+
+pub(crate) mod inner {
+    pub enum Kind {
+        /// The first kind.
+        A,
+        B(u8),
+    }
+}
+
+impl fmt::Display for Foo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Ok(())
+    }
+}
+
+pub trait Named {
+    const NAME: &'static str;
+    fn name(&self) -> &str;
+}
+
+macro_rules! noop { () => {}; }
+"#;
+        let items = outline(source).unwrap();
+        let names = items
+            .iter()
+            .map(|item| (item.name.as_str(), item.kind))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                ("Foo", OutlineKind::Struct),
+                ("inner", OutlineKind::Module),
+                ("impl fmt::Display for Foo", OutlineKind::Impl),
+                ("Named", OutlineKind::Trait),
+                ("noop", OutlineKind::Macro),
+            ]
+        );
+
+        let foo = &items[0];
+        assert_eq!(
+            foo.line_range,
+            NonZeroUsize::new(5).unwrap()..=NonZeroUsize::new(9).unwrap()
+        );
+        assert_eq!(foo.visibility.as_deref(), Some("pub"));
+        assert_eq!(foo.doc.as_deref(), Some("A value."));
+
+        let inner = &items[1];
+        assert_eq!(inner.visibility.as_deref(), Some("pub(crate)"));
+        let kind = &inner.children[0];
+        assert_eq!(kind.children.len(), 2);
+        assert_eq!(kind.children[0].doc.as_deref(), Some("The first kind."));
+        assert_eq!(kind.children[0].line_range.start().get(), 13);
+
+        let display = &items[2];
+        assert_eq!(display.children[0].name, "fmt");
+        assert_eq!(display.children[0].kind, OutlineKind::Method);
+        assert!(display.children[0].visibility.is_none());
+        assert_eq!(items[3].children.len(), 2);
+
+        assert!(outline("fn broken(").is_err());
+    }
+}
@@ -0,0 +1,342 @@
+//! The `overview` module.
+//!
+//! Summarizes a crate in a single response: its manifest, crate-level documentation, top-level
+//! public modules and re-exports, item counts, lines of code and targets. This covers what a
+//! client would otherwise gather by reading the root directory, `Cargo.toml`, `lib.rs` and the
+//! README one by one.
+//!
+use crate::cache::Crate;
+use crate::manifest::{Manifest, Package};
+use crate::outline::{self, OutlineKind};
+use crate::ItemType;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use syn::{Expr, Lit, Meta};
+
+#[cfg(feature = "utoipa")]
+use utoipa::ToSchema;
+
+/// A summary of a crate.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct CrateOverview {
+    /// The `[package]` section of the manifest, if the crate has a `Cargo.toml`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package: Option<Package>,
+    /// The names of the features of the crate.
+    pub features: Vec<String>,
+    /// The number of normal, build and dev dependencies, including target-specific ones.
+    pub dependencies: DependencyCounts,
+    /// The crate-level `//!` documentation of the library, or of the binary for crates without
+    /// a library. Documentation included with `#![doc = include_str!(...)]` is resolved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs: Option<String>,
+    /// The path of the README file, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub readme: Option<String>,
+    /// The public modules declared in the library root.
+    pub modules: Vec<ModuleSummary>,
+    /// The `pub use` declarations of the library root.
+    pub reexports: Vec<ReExportSummary>,
+    /// The number of items of each type in the crate.
+    pub item_counts: BTreeMap<ItemType, usize>,
+    /// The number of items of each type reachable from outside the crate.
+    pub public_item_counts: BTreeMap<ItemType, usize>,
+    /// The non-blank lines of Rust code in each directory, not counting subdirectories.
+    pub lines_of_code: Vec<DirectoryLines>,
+    /// The number of examples.
+    pub examples: usize,
+    /// The number of integration tests.
+    pub tests: usize,
+    /// The number of benchmarks.
+    pub benches: usize,
+    /// Whether the crate has a build script.
+    pub build_script: bool,
+    /// Whether the crate is a procedural macro library.
+    pub proc_macro: bool,
+    /// Whether some Rust source files failed to parse, so the counts may be incomplete.
+    pub incomplete: bool,
+}
+
+/// The number of dependencies of a crate by kind.
+///
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct DependencyCounts {
+    /// The number of normal dependencies.
+    pub normal: usize,
+    /// The number of build dependencies.
+    pub build: usize,
+    /// The number of dev-dependencies.
+    pub dev: usize,
+}
+
+/// A public module of the library root.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct ModuleSummary {
+    /// The name of the module.
+    pub name: String,
+    /// The file implementing the module, or the crate root for inline modules. Not set if the file
+    /// of the module cannot be found.
+    #[cfg_attr(feature = "utoipa", schema(value_type = Option<String>))]
+    pub file: Option<PathBuf>,
+    /// The first line of the documentation of the module.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+}
+
+/// A `pub use` declaration of the library root.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct ReExportSummary {
+    /// The path of the re-export, e.g. `crate::Bytes`.
+    pub path: String,
+    /// The path of the re-exported item, e.g. `crate::bytes::Bytes` or `bytes::Bytes`.
+    pub source: String,
+}
+
+/// The lines of Rust code in a directory.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct DirectoryLines {
+    /// The path of the directory.
+    #[cfg_attr(feature = "utoipa", schema(value_type = String))]
+    pub path: PathBuf,
+    /// The number of Rust source files directly in the directory.
+    pub files: usize,
+    /// The number of non-blank lines of these files.
+    pub lines: usize,
+}
+
+/// Builds the overview of a crate.
+///
+pub fn overview(krate: &Crate) -> CrateOverview {
+    let manifest = krate.manifest().map(|manifest| manifest.as_ref());
+    let index = krate.item_index();
+
+    let mut item_counts = BTreeMap::new();
+    let mut public_item_counts = BTreeMap::new();
+    for item in index.items() {
+        *item_counts.entry(item.type_).or_default() += 1;
+        if item.exported {
+            *public_item_counts.entry(item.type_).or_default() += 1;
+        }
+    }
+
+    let mut directories = BTreeMap::<PathBuf, DirectoryLines>::new();
+    for (path, desc) in krate.files() {
+        if !path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("rs"))
+        {
+            continue;
+        }
+        let directory = path.parent().unwrap_or(Path::new("")).to_path_buf();
+        let data = krate.file_data(desc);
+        let lines = data
+            .split(|byte| *byte == b'\n')
+            .filter(|line| line.iter().any(|byte| !byte.is_ascii_whitespace()))
+            .count();
+        let entry = directories
+            .entry(directory.clone())
+            .or_insert_with(|| DirectoryLines {
+                path: directory,
+                files: 0,
+                lines: 0,
+            });
+        entry.files += 1;
+        entry.lines += lines;
+    }
+
+    // The crate root whose documentation describes the crate.
+    let root = match manifest.and_then(|manifest| manifest.bins.first()) {
+        Some(bin) if krate.get_file_desc(&index.lib_root).is_none() => {
+            bin.path.as_deref().map(PathBuf::from)
+        }
+        _ => Some(index.lib_root.clone()),
+    };
+    let root_source = root.as_deref().and_then(|root| read_source(krate, root));
+    let root_file = root_source
+        .as_deref()
+        .and_then(|source| syn::parse_file(source).ok());
+
+    let docs = root_file
+        .as_ref()
+        .zip(root.as_deref())
+        .and_then(|(file, root)| crate_docs(krate, &file.attrs, root));
+    let modules = root
+        .as_deref()
+        .zip(root_source.as_deref())
+        .map(|(root, source)| module_summaries(krate, root, source))
+        .unwrap_or_default();
+    let reexports = index
+        .reexports
+        .iter()
+        .filter(|reexport| reexport.file.as_ref() == index.lib_root && reexport.cfg.is_none())
+        .filter_map(|reexport| {
+            Some(ReExportSummary {
+                path: reexport.path.clone()?,
+                source: reexport.source.clone(),
+            })
+        })
+        .collect();
+
+    let readme = manifest
+        .and_then(|manifest| manifest.package.readme.clone())
+        .or_else(|| {
+            ["README.md", "README", "README.txt"]
+                .into_iter()
+                .find(|name| krate.get_file_desc(name).is_some())
+                .map(String::from)
+        });
+
+    CrateOverview {
+        package: manifest.map(|manifest| manifest.package.clone()),
+        features: manifest
+            .map(|manifest| manifest.features.keys().cloned().collect())
+            .unwrap_or_default(),
+        dependencies: manifest.map(dependency_counts).unwrap_or_default(),
+        docs,
+        readme,
+        modules,
+        reexports,
+        item_counts,
+        public_item_counts,
+        lines_of_code: directories.into_values().collect(),
+        examples: manifest.map_or(0, |manifest| manifest.examples.len()),
+        tests: manifest.map_or(0, |manifest| manifest.tests.len()),
+        benches: manifest.map_or(0, |manifest| manifest.benches.len()),
+        build_script: manifest.is_some_and(|manifest| manifest.package.build.is_some())
+            || krate.get_file_desc("build.rs").is_some(),
+        proc_macro: manifest.is_some_and(Manifest::is_proc_macro),
+        incomplete: !index.parse_errors.is_empty(),
+    }
+}
+
+/// Counts the dependencies of a manifest by kind.
+fn dependency_counts(manifest: &Manifest) -> DependencyCounts {
+    let dependencies = &manifest.dependencies;
+    let mut counts = DependencyCounts {
+        normal: dependencies.normal.len(),
+        build: dependencies.build.len(),
+        dev: dependencies.dev.len(),
+    };
+    for target in &dependencies.target {
+        counts.normal += target.normal.len();
+        counts.build += target.build.len();
+        counts.dev += target.dev.len();
+    }
+    counts
+}
+
+/// Reads a file of a crate as UTF-8.
+fn read_source(krate: &Crate, path: &Path) -> Option<String> {
+    let desc = krate.get_file_desc(path)?;
+    String::from_utf8(krate.file_data(desc).to_vec()).ok()
+}
+
+/// Returns the inner doc comments of a file, resolving `#![doc = include_str!("...")]` relative
+/// to the file.
+fn crate_docs(krate: &Crate, attrs: &[syn::Attribute], file: &Path) -> Option<String> {
+    let mut docs = Vec::new();
+    for attr in attrs {
+        if !matches!(attr.style, syn::AttrStyle::Inner(_)) {
+            continue;
+        }
+        let Meta::NameValue(meta) = &attr.meta else {
+            continue;
+        };
+        if !meta.path.is_ident("doc") {
+            continue;
+        }
+        match &meta.value {
+            Expr::Lit(expr) => {
+                if let Lit::Str(doc) = &expr.lit {
+                    let doc = doc.value();
+                    docs.push(doc.strip_prefix(' ').unwrap_or(&doc).to_string());
+                }
+            }
+            Expr::Macro(expr) if expr.mac.path.is_ident("include_str") => {
+                let Ok(include) = expr.mac.parse_body::<syn::LitStr>() else {
+                    continue;
+                };
+                let directory = file.parent().unwrap_or(Path::new(""));
+                let path = normalize(&directory.join(include.value()));
+                if let Some(source) = read_source(krate, &path) {
+                    docs.push(source);
+                }
+            }
+            _ => {}
+        }
+    }
+    (!docs.is_empty()).then(|| docs.join("\n").trim().to_string())
+}
+
+/// Returns the public modules declared in the source of a crate root.
+fn module_summaries(krate: &Crate, root: &Path, source: &str) -> Vec<ModuleSummary> {
+    let index = krate.item_index();
+    let Ok(items) = outline::outline(source) else {
+        return Vec::new();
+    };
+    items
+        .into_iter()
+        .filter(|item| {
+            item.kind == OutlineKind::Module && item.visibility.as_deref() == Some("pub")
+        })
+        .map(|item| {
+            let inline = index.modules.iter().any(|declaration| {
+                declaration.inline
+                    && declaration.file.as_ref() == root
+                    && declaration.parents.is_empty()
+                    && declaration.name == item.name
+            });
+            let file = if inline {
+                Some(root.to_path_buf())
+            } else {
+                index
+                    .file_modules
+                    .iter()
+                    .find(|(_, module)| {
+                        module.root == root && module.path == std::slice::from_ref(&item.name)
+                    })
+                    .map(|(file, _)| file.clone())
+            };
+            let doc = item.doc.or_else(|| {
+                let file = file.as_deref()?;
+                let source = read_source(krate, file)?;
+                let attrs = syn::parse_file(&source).ok()?.attrs;
+                let docs = crate_docs(krate, &attrs, file)?;
+                docs.lines()
+                    .map(str::trim)
+                    .find(|line| !line.is_empty())
+                    .map(String::from)
+            });
+            ModuleSummary {
+                name: item.name,
+                file,
+                doc,
+            }
+        })
+        .collect()
+}
+
+/// Removes `.` and `..` components from a relative path.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
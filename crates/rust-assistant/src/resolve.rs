@@ -0,0 +1,765 @@
+//! The `resolve` module.
+//!
+//! Builds the transitive dependency graph of a crate version from the crates.io registry index.
+//! Every index entry carries the dependencies and the feature table of the published manifest,
+//! so the graph can be resolved without downloading the crates themselves.
+//!
+//! Features are unified across the graph like Cargo does: a crate depended on by several crates
+//! gets the union of the features they request, which may in turn enable more optional dependencies.
+//!
+use crate::cfg::{Cfg, CfgConfig, TargetCfg};
+use crate::download::{
+    max_matching_version, CrateDownloader, DependencyKind, IndexDependency, IndexEntry,
+};
+use crate::CrateVersion;
+use fnv::FnvHashMap;
+use semver::VersionReq;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt::{Display, Formatter, Write};
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::task::JoinSet;
+
+#[cfg(feature = "utoipa")]
+use utoipa::ToSchema;
+
+/// The maximum number of crates in a resolved dependency graph.
+pub const MAX_GRAPH_NODES: usize = 2000;
+
+/// Options controlling how a dependency graph is resolved.
+///
+#[derive(Debug, Clone, Default)]
+pub struct ResolveOptions {
+    /// The features enabled on the root crate.
+    pub features: BTreeSet<String>,
+    /// Whether the default features of the root crate are disabled.
+    pub no_default_features: bool,
+    /// Whether all features of the root crate are enabled.
+    pub all_features: bool,
+    /// The target triple to resolve for. Target-specific dependencies of other targets are excluded.
+    ///
+    /// If `None`, the dependencies of all targets are included.
+    pub target: Option<String>,
+    /// Whether the dev-dependencies of the root crate are included.
+    pub dev: bool,
+}
+
+/// The error of enabling a feature the root crate does not declare.
+///
+#[derive(Debug)]
+pub struct UnknownFeature {
+    /// The unknown feature.
+    pub feature: String,
+    /// The root crate.
+    pub krate: CrateVersion,
+}
+
+impl Display for UnknownFeature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Unknown feature `{}` of crate {}",
+            self.feature, self.krate
+        )
+    }
+}
+
+impl std::error::Error for UnknownFeature {}
+
+/// The transitive dependency graph of a crate version.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct DependencyGraph {
+    /// The crates in the graph. The root crate is always the first node.
+    pub nodes: Vec<DependencyNode>,
+    /// The dependency edges between the nodes.
+    pub edges: Vec<DependencyEdge>,
+    /// The dependencies that could not be resolved from the registry index.
+    pub unresolved: Vec<UnresolvedDependency>,
+}
+
+/// A crate version in a dependency graph.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct DependencyNode {
+    /// The index of the node in the graph.
+    pub id: usize,
+    /// The name and version of the crate.
+    #[serde(flatten)]
+    pub crate_version: CrateVersion,
+    /// The features enabled on the crate, after unification across the graph.
+    pub features: BTreeSet<String>,
+}
+
+/// A dependency of one crate on another in a dependency graph.
+///
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct DependencyEdge {
+    /// The id of the dependent crate.
+    pub from: usize,
+    /// The id of the dependency.
+    pub to: usize,
+    /// The kind of the dependency.
+    #[cfg_attr(feature = "utoipa", schema(value_type = String))]
+    pub kind: DependencyKind,
+    /// The name the dependent crate refers to the dependency by, if it is renamed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rename: Option<String>,
+    /// Whether the dependency is optional and was enabled by a feature.
+    pub optional: bool,
+    /// The target-specific condition of the dependency, e.g. `cfg(unix)`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+}
+
+/// A dependency that could not be resolved.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct UnresolvedDependency {
+    /// The id of the dependent crate.
+    pub from: usize,
+    /// The name of the dependency.
+    pub name: String,
+    /// The version requirement of the dependency.
+    pub req: String,
+    /// The reason the dependency could not be resolved.
+    pub reason: String,
+}
+
+/// A unit of work of the resolver.
+#[derive(Debug, Clone)]
+enum Task {
+    /// Adds the root crate to the graph.
+    Root,
+    /// Enables a feature of a node.
+    Feature(usize, String),
+    /// Enables an optional dependency of a node.
+    EnableDependency(usize, String),
+    /// Enables a feature of a dependency of a node, only once the dependency is enabled.
+    DependencyFeature(usize, String, String),
+    /// Resolves the `n`-th dependency of a node and links it into the graph.
+    Link(usize, usize),
+}
+
+/// The resolution state of a crate in the graph.
+#[derive(Debug)]
+struct Node {
+    entry: IndexEntry,
+    features: BTreeSet<String>,
+    enabled_dependencies: BTreeSet<String>,
+    /// The features to enable on dependencies, by dependency name.
+    dependency_features: BTreeMap<String, BTreeSet<String>>,
+    /// The linked nodes, by dependency name.
+    links: BTreeMap<String, BTreeSet<usize>>,
+}
+
+/// An incremental dependency graph resolver.
+///
+/// The resolver does not fetch anything itself: it asks for the index entries of the crates it
+/// needs through [`DependencyResolver::missing`], which are then supplied with
+/// [`DependencyResolver::add_index_entries`]. [`resolve_dependencies`] drives it against crates.io.
+///
+#[derive(Debug)]
+pub struct DependencyResolver {
+    root: CrateVersion,
+    options: ResolveOptions,
+    target: Option<TargetCfg>,
+    entries: FnvHashMap<String, Option<Arc<[IndexEntry]>>>,
+    nodes: Vec<Node>,
+    ids: FnvHashMap<CrateVersion, usize>,
+    edges: BTreeSet<DependencyEdge>,
+    unresolved: Vec<UnresolvedDependency>,
+    queue: VecDeque<Task>,
+    blocked: Vec<Task>,
+    missing: BTreeSet<String>,
+}
+
+impl DependencyResolver {
+    /// Creates a resolver for the dependency graph of a crate version.
+    ///
+    pub fn new(root: CrateVersion, options: ResolveOptions) -> anyhow::Result<Self> {
+        let target = options
+            .target
+            .as_deref()
+            .map(TargetCfg::from_str)
+            .transpose()?;
+        Ok(Self {
+            missing: BTreeSet::from([root.krate.to_string()]),
+            blocked: vec![Task::Root],
+            root,
+            options,
+            target,
+            entries: Default::default(),
+            nodes: Vec::new(),
+            ids: Default::default(),
+            edges: Default::default(),
+            unresolved: Vec::new(),
+            queue: Default::default(),
+        })
+    }
+
+    /// Returns the crates whose index entries are needed to continue the resolution.
+    ///
+    /// The resolution is complete when this is empty after calling [`DependencyResolver::resolve`].
+    ///
+    pub fn missing(&self) -> impl Iterator<Item = &str> {
+        self.missing.iter().map(String::as_str)
+    }
+
+    /// Supplies the index entries of a crate, or `None` if it does not exist in the registry.
+    ///
+    pub fn add_index_entries(&mut self, krate: &str, entries: Option<Vec<IndexEntry>>) {
+        self.missing.remove(krate);
+        self.entries
+            .insert(krate.to_string(), entries.map(Arc::from));
+    }
+
+    /// Resolves as much of the graph as possible with the index entries supplied so far.
+    ///
+    pub fn resolve(&mut self) -> anyhow::Result<()> {
+        self.queue.extend(self.blocked.drain(..));
+        while let Some(task) = self.queue.pop_front() {
+            match task {
+                Task::Root => self.add_root()?,
+                Task::Feature(node, feature) => self.enable_feature(node, feature),
+                Task::EnableDependency(node, name) => self.enable_dependency(node, name),
+                Task::DependencyFeature(node, name, feature) => {
+                    self.enable_dependency_feature(node, name, feature)
+                }
+                Task::Link(node, index) => self.link(node, index)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Finishes the resolution and returns the graph.
+    ///
+    pub fn finish(self) -> DependencyGraph {
+        DependencyGraph {
+            nodes: self
+                .nodes
+                .into_iter()
+                .enumerate()
+                .map(|(id, node)| DependencyNode {
+                    id,
+                    crate_version: node.entry.crate_version(),
+                    features: node.features,
+                })
+                .collect(),
+            edges: self.edges.into_iter().collect(),
+            unresolved: self.unresolved,
+        }
+    }
+
+    /// Returns the index entries of a crate, or marks them as missing and blocks the task.
+    fn entries_or_block(&mut self, krate: &str, task: Task) -> Option<Option<Arc<[IndexEntry]>>> {
+        match self.entries.get(krate) {
+            Some(entries) => Some(entries.clone()),
+            None => {
+                self.missing.insert(krate.to_string());
+                self.blocked.push(task);
+                None
+            }
+        }
+    }
+
+    fn add_root(&mut self) -> anyhow::Result<()> {
+        let Some(entries) = self.entries_or_block(&self.root.krate.clone(), Task::Root) else {
+            return Ok(());
+        };
+        let Some(entry) = entries
+            .iter()
+            .flat_map(|entries| entries.iter())
+            .find(|entry| entry.vers.as_ref() == self.root.version.as_ref())
+        else {
+            anyhow::bail!("Crate not found in the registry index: {}", self.root);
+        };
+
+        let root = self.add_node(entry.clone());
+        let mut features = self.options.features.clone();
+        if !self.options.no_default_features {
+            features.insert("default".to_string());
+        }
+        if self.options.all_features {
+            let entry = &self.nodes[root].entry;
+            features.extend(entry.all_features().map(|(name, _)| name.clone()));
+            features.extend(
+                entry
+                    .deps
+                    .iter()
+                    .filter(|dep| dep.optional)
+                    .map(|dep| dep.name.clone()),
+            );
+        }
+        for feature in features {
+            let known = self.nodes[root]
+                .entry
+                .all_features()
+                .any(|(name, _)| *name == feature)
+                || self.nodes[root]
+                    .entry
+                    .deps
+                    .iter()
+                    .any(|dep| dep.optional && dep.name == feature);
+            if !known && feature != "default" {
+                return Err(UnknownFeature {
+                    feature,
+                    krate: self.root.clone(),
+                }
+                .into());
+            }
+            self.queue.push_back(Task::Feature(root, feature));
+        }
+        Ok(())
+    }
+
+    fn add_node(&mut self, entry: IndexEntry) -> usize {
+        let crate_version = entry.crate_version();
+        if let Some(id) = self.ids.get(&crate_version) {
+            return *id;
+        }
+        let id = self.nodes.len();
+        for (index, dep) in entry.deps.iter().enumerate() {
+            if !dep.optional && self.applies(id, dep) {
+                self.queue.push_back(Task::Link(id, index));
+            }
+        }
+        self.ids.insert(crate_version, id);
+        self.nodes.push(Node {
+            entry,
+            features: Default::default(),
+            enabled_dependencies: Default::default(),
+            dependency_features: Default::default(),
+            links: Default::default(),
+        });
+        id
+    }
+
+    /// Checks whether a dependency is part of the graph under the resolve options.
+    fn applies(&self, node: usize, dep: &IndexDependency) -> bool {
+        let kind = match dep.kind {
+            DependencyKind::Normal | DependencyKind::Build => true,
+            DependencyKind::Dev => node == 0 && self.options.dev,
+        };
+        let target = match (&dep.target, &self.target) {
+            (Some(spec), Some(target)) => target_matches(
+                spec,
+                self.options.target.as_deref().unwrap_or_default(),
+                target,
+            ),
+            _ => true,
+        };
+        kind && target
+    }
+
+    fn enable_feature(&mut self, node: usize, feature: String) {
+        if !self.nodes[node].features.insert(feature.clone()) {
+            return;
+        }
+        let entry = &self.nodes[node].entry;
+        let values = entry
+            .all_features()
+            .find(|(name, _)| **name == feature)
+            .map(|(_, values)| values.clone());
+        let Some(values) = values else {
+            // A feature implicitly defined by an optional dependency.
+            if entry
+                .deps
+                .iter()
+                .any(|dep| dep.optional && dep.name == feature)
+            {
+                self.queue.push_back(Task::EnableDependency(node, feature));
+            }
+            return;
+        };
+        for value in values {
+            if let Some(name) = value.strip_prefix("dep:") {
+                self.queue
+                    .push_back(Task::EnableDependency(node, name.to_string()));
+            } else if let Some((name, feature)) = value.split_once('/') {
+                match name.strip_suffix('?') {
+                    Some(name) => self.queue.push_back(Task::DependencyFeature(
+                        node,
+                        name.to_string(),
+                        feature.to_string(),
+                    )),
+                    None => {
+                        self.queue
+                            .push_back(Task::EnableDependency(node, name.to_string()));
+                        self.queue.push_back(Task::DependencyFeature(
+                            node,
+                            name.to_string(),
+                            feature.to_string(),
+                        ));
+                    }
+                }
+            } else {
+                self.queue.push_back(Task::Feature(node, value));
+            }
+        }
+    }
+
+    fn enable_dependency(&mut self, node: usize, name: String) {
+        if !self.nodes[node].enabled_dependencies.insert(name.clone()) {
+            return;
+        }
+        for (index, dep) in self.nodes[node].entry.deps.iter().enumerate() {
+            if dep.optional && dep.name == name && self.applies(node, dep) {
+                self.queue.push_back(Task::Link(node, index));
+            }
+        }
+    }
+
+    fn enable_dependency_feature(&mut self, node: usize, name: String, feature: String) {
+        let state = &mut self.nodes[node];
+        if !state
+            .dependency_features
+            .entry(name.clone())
+            .or_default()
+            .insert(feature.clone())
+        {
+            return;
+        }
+        for target in state.links.get(&name).into_iter().flatten() {
+            self.queue
+                .push_back(Task::Feature(*target, feature.clone()));
+        }
+    }
+
+    fn link(&mut self, node: usize, index: usize) -> anyhow::Result<()> {
+        let dep = self.nodes[node].entry.deps[index].clone();
+        if dep.registry.is_some() {
+            self.unresolved.push(UnresolvedDependency {
+                from: node,
+                name: dep.package_name().to_string(),
+                req: dep.req.clone(),
+                reason: "the dependency is not on crates.io".to_string(),
+            });
+            return Ok(());
+        }
+        let Some(entries) = self.entries_or_block(dep.package_name(), Task::Link(node, index))
+        else {
+            return Ok(());
+        };
+        let req = VersionReq::parse(&dep.req)?;
+        let Some(entry) = entries
+            .as_deref()
+            .and_then(|entries| max_matching_version(entries, &req))
+        else {
+            self.unresolved.push(UnresolvedDependency {
+                from: node,
+                name: dep.package_name().to_string(),
+                req: dep.req.clone(),
+                reason: "no published version matches the requirement".to_string(),
+            });
+            return Ok(());
+        };
+        if self.nodes.len() >= MAX_GRAPH_NODES && !self.ids.contains_key(&entry.crate_version()) {
+            anyhow::bail!("The dependency graph has more than {MAX_GRAPH_NODES} crates.");
+        }
+
+        let target = self.add_node(entry.clone());
+        self.edges.insert(DependencyEdge {
+            from: node,
+            to: target,
+            kind: dep.kind,
+            rename: dep.package.is_some().then(|| dep.name.clone()),
+            optional: dep.optional,
+            target: dep.target.clone(),
+        });
+        let state = &mut self.nodes[node];
+        state
+            .links
+            .entry(dep.name.clone())
+            .or_default()
+            .insert(target);
+
+        let mut features = dep.features.clone();
+        if dep.default_features {
+            features.push("default".to_string());
+        }
+        features.extend(
+            state
+                .dependency_features
+                .get(&dep.name)
+                .into_iter()
+                .flatten()
+                .cloned(),
+        );
+        for feature in features {
+            self.queue.push_back(Task::Feature(target, feature));
+        }
+        Ok(())
+    }
+}
+
+/// Checks whether a target-specific dependency applies to a target.
+///
+/// The `spec` is either a target triple or a `cfg(...)` expression.
+///
+pub fn target_matches(spec: &str, triple: &str, target: &TargetCfg) -> bool {
+    match spec
+        .trim()
+        .strip_prefix("cfg(")
+        .and_then(|spec| spec.strip_suffix(')'))
+    {
+        Some(predicate) => match Cfg::from_str(predicate) {
+            Ok(cfg) => {
+                let config = CfgConfig {
+                    features: None,
+                    target: Some(target.clone()),
+                };
+                cfg.eval(&config) != Some(false)
+            }
+            Err(_) => true,
+        },
+        None => spec.trim() == triple,
+    }
+}
+
+/// Resolves the dependency graph of a crate version against the crates.io registry index.
+///
+/// The index files of each level of the graph are fetched concurrently.
+///
+pub async fn resolve_dependencies(
+    downloader: &CrateDownloader,
+    root: CrateVersion,
+    options: ResolveOptions,
+) -> anyhow::Result<DependencyGraph> {
+    let mut resolver = DependencyResolver::new(root, options)?;
+    loop {
+        let missing = resolver.missing().map(String::from).collect::<Vec<_>>();
+        if missing.is_empty() {
+            break;
+        }
+        let mut tasks = JoinSet::new();
+        for krate in missing {
+            let downloader = downloader.clone();
+            tasks.spawn(async move {
+                let entries = downloader.get_index_entries(&krate).await;
+                (krate, entries)
+            });
+        }
+        while let Some(result) = tasks.join_next().await {
+            let (krate, entries) = result?;
+            resolver.add_index_entries(&krate, entries?);
+        }
+        resolver.resolve()?;
+    }
+    Ok(resolver.finish())
+}
+
+impl DependencyGraph {
+    /// Renders the graph like `cargo tree` does.
+    ///
+    /// Dependencies that have already been expanded are marked with `(*)` instead of being repeated.
+    ///
+    pub fn to_tree(&self) -> String {
+        let mut output = String::new();
+        if !self.nodes.is_empty() {
+            let mut expanded = BTreeSet::new();
+            self.write_tree(0, "", &mut expanded, &mut output);
+        }
+        output
+    }
+
+    fn write_node(&self, id: usize, expanded: bool, output: &mut String) {
+        let node = &self.nodes[id];
+        let _ = write!(
+            output,
+            "{} v{}",
+            node.crate_version.krate, node.crate_version.version
+        );
+        if expanded && self.edges.iter().any(|edge| edge.from == id) {
+            output.push_str(" (*)");
+        }
+        output.push('\n');
+    }
+
+    fn write_tree(
+        &self,
+        id: usize,
+        prefix: &str,
+        expanded: &mut BTreeSet<usize>,
+        output: &mut String,
+    ) {
+        let already_expanded = !expanded.insert(id);
+        if prefix.is_empty() {
+            self.write_node(id, already_expanded, output);
+        }
+        if already_expanded {
+            return;
+        }
+
+        let sections = [
+            (DependencyKind::Normal, None),
+            (DependencyKind::Build, Some("[build-dependencies]")),
+            (DependencyKind::Dev, Some("[dev-dependencies]")),
+        ];
+        for (kind, header) in sections {
+            let mut children = self
+                .edges
+                .iter()
+                .filter(|edge| edge.from == id && edge.kind == kind)
+                .map(|edge| edge.to)
+                .collect::<Vec<_>>();
+            children.sort_by(|a, b| {
+                self.nodes[*a]
+                    .crate_version
+                    .cmp(&self.nodes[*b].crate_version)
+            });
+            children.dedup();
+            if children.is_empty() {
+                continue;
+            }
+            if let Some(header) = header {
+                let _ = writeln!(output, "{prefix}{header}");
+            }
+            for (index, child) in children.iter().enumerate() {
+                let last = index + 1 == children.len();
+                output.push_str(prefix);
+                output.push_str(if last { "└── " } else { "├── " });
+                self.write_node(*child, expanded.contains(child), output);
+                let prefix = format!("{prefix}{}", if last { "    " } else { "│   " });
+                self.write_tree(*child, &prefix, expanded, output);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn index() -> Vec<IndexEntry> {
+        let dep = |name: &str, req: &str| json!({"name": name, "req": req});
+        [
+            json!({
+                "name": "app", "vers": "1.0.0",
+                "features": {"default": ["json"], "json": ["dep:serde_json"], "derive": ["serde/derive"]},
+                "deps": [
+                    {"name": "serde", "req": "^1", "optional": true},
+                    {"name": "serde_json", "req": "^1", "optional": true},
+                    {"name": "tokio", "req": "^1", "features": ["rt"], "default_features": false},
+                    {"name": "winapi", "req": "^0.3", "target": "cfg(windows)"},
+                    {"name": "cc", "req": "^1", "kind": "build"},
+                    {"name": "criterion", "req": "^0.5", "kind": "dev"},
+                ],
+            }),
+            json!({
+                "name": "serde", "vers": "1.0.0",
+                "features": {"default": ["std"], "std": [], "derive": ["serde_derive"]},
+                "deps": [{"name": "serde_derive", "req": "=1.0.0", "optional": true}],
+            }),
+            json!({"name": "serde", "vers": "1.0.1", "yanked": true}),
+            json!({
+                "name": "serde_json", "vers": "1.0.0",
+                "features": {"default": ["std"], "std": ["serde/std"]},
+                "deps": [{"name": "serde", "req": "^1.0", "default_features": false}],
+            }),
+            json!({"name": "serde_derive", "vers": "1.0.0"}),
+            json!({"name": "tokio", "vers": "1.0.0", "features": {"rt": [], "net": []}}),
+            json!({"name": "winapi", "vers": "0.3.9"}),
+            json!({"name": "cc", "vers": "1.0.0", "deps": [dep("missing", "^1")]}),
+        ]
+        .into_iter()
+        .map(serde_json::from_value)
+        .collect::<Result<_, _>>()
+        .unwrap()
+    }
+
+    fn resolve(options: ResolveOptions) -> anyhow::Result<DependencyGraph> {
+        let index = index();
+        let mut resolver = DependencyResolver::new(CrateVersion::from(("app", "1.0.0")), options)?;
+        loop {
+            let missing = resolver.missing().map(String::from).collect::<Vec<_>>();
+            if missing.is_empty() {
+                return Ok(resolver.finish());
+            }
+            for krate in missing {
+                let entries = index
+                    .iter()
+                    .filter(|entry| entry.name.as_ref() == krate)
+                    .cloned()
+                    .collect::<Vec<_>>();
+                resolver.add_index_entries(&krate, (!entries.is_empty()).then_some(entries));
+            }
+            resolver.resolve()?;
+        }
+    }
+
+    #[test]
+    fn resolve_features() -> anyhow::Result<()> {
+        let graph = resolve(ResolveOptions {
+            target: Some("x86_64-unknown-linux-gnu".to_string()),
+            ..Default::default()
+        })?;
+        let features = |name: &str| {
+            graph
+                .nodes
+                .iter()
+                .find(|node| node.crate_version.krate.as_ref() == name)
+                .map(|node| node.features.iter().cloned().collect::<Vec<_>>())
+        };
+        assert_eq!(graph.nodes.len(), 5);
+        assert_eq!(features("serde"), Some(vec!["std".to_string()]));
+        assert_eq!(features("tokio"), Some(vec!["rt".to_string()]));
+        assert_eq!(features("winapi"), None);
+        assert_eq!(features("serde_derive"), None);
+        assert_eq!(graph.unresolved.len(), 1);
+        assert_eq!(graph.unresolved[0].name, "missing");
+        assert_eq!(
+            graph.to_tree(),
+            "app v1.0.0\n\
+             ├── serde_json v1.0.0\n\
+             │   └── serde v1.0.0\n\
+             └── tokio v1.0.0\n\
+             [build-dependencies]\n\
+             └── cc v1.0.0\n"
+        );
+
+        let graph = resolve(ResolveOptions {
+            features: BTreeSet::from(["derive".to_string()]),
+            no_default_features: true,
+            ..Default::default()
+        })?;
+        let names = graph
+            .nodes
+            .iter()
+            .map(|node| node.crate_version.krate.to_string())
+            .collect::<BTreeSet<_>>();
+        assert_eq!(
+            names,
+            ["app", "cc", "serde", "serde_derive", "tokio", "winapi"]
+                .map(String::from)
+                .into()
+        );
+        let serde = graph
+            .nodes
+            .iter()
+            .find(|node| node.crate_version.krate.as_ref() == "serde")
+            .unwrap();
+        assert_eq!(serde.crate_version.version.as_ref(), "1.0.0");
+        assert!(serde.features.contains("derive") && serde.features.contains("std"));
+
+        assert!(resolve(ResolveOptions {
+            features: BTreeSet::from(["unknown".to_string()]),
+            ..Default::default()
+        })
+        .unwrap_err()
+        .is::<UnknownFeature>());
+        assert!(resolve(ResolveOptions {
+            target: Some("foo".to_string()),
+            ..Default::default()
+        })
+        .unwrap_err()
+        .is::<crate::cfg::InvalidTarget>());
+        Ok(())
+    }
+}
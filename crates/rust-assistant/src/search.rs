@@ -5,15 +5,33 @@
 //! and other relevant data. It could include various search algorithms and data structures optimized
 //! for quick and efficient search operations, like `SearchIndex`.
 //!
-use fnv::FnvHashMap;
+use fnv::{FnvHashMap, FnvHashSet};
+use quote::ToTokens;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 use std::num::NonZeroUsize;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::{Attribute, ItemEnum, ItemFn, ItemImpl, ItemMacro, ItemStruct, ItemTrait};
+use syn::{
+    Attribute, Expr, Fields, Generics, Ident, ImplItem, ItemEnum, ItemFn, ItemImpl, ItemMacro,
+    ItemMod, ItemStruct, ItemTrait, ItemUse, Lit, Meta, Token, TraitBoundModifier, TraitItem, Type,
+    TypeParamBound, UseTree, Visibility, WherePredicate,
+};
 
-use crate::{Item, ItemQuery, ItemType};
+use crate::cfg::Cfg;
+use crate::{ImplInfo, ImplSearchResult, Item, ItemQuery, ItemType, ParseError};
+
+/// The traits implemented automatically by the compiler, assumed to hold for any type.
+const AUTO_TRAITS: &[&str] = &[
+    "Send",
+    "Sync",
+    "Sized",
+    "Unpin",
+    "UnwindSafe",
+    "RefUnwindSafe",
+];
 
 /// A mutable search index containing categorized items for searching within a crate.
 ///
@@ -31,13 +49,61 @@ pub struct SearchIndexMut {
     pub attribute_macros: FnvHashMap<String, Vec<Item>>,
     pub functions: FnvHashMap<String, Vec<Item>>,
     pub type_aliases: FnvHashMap<String, Vec<Item>>,
+    pub modules: Vec<ModuleDeclaration>,
+    pub reexports: Vec<ReExport>,
+    pub file_modules: FnvHashMap<PathBuf, FileModule>,
+    /// The root source file of the library, e.g. `src/lib.rs`.
+    pub lib_root: PathBuf,
+    pub parse_errors: Vec<ParseError>,
+}
+
+/// A `mod` item declared in a Rust source file, either inline or referring to another file.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleDeclaration {
+    /// The name of the module.
+    pub name: String,
+    /// The file containing the declaration.
+    pub file: Arc<Path>,
+    /// The names of the inline modules enclosing the declaration within its file.
+    pub parents: Vec<String>,
+    /// Whether the module body is declared inline.
+    pub inline: bool,
+    /// The value of the `#[path = "..."]` attribute, if any.
+    pub path_attr: Option<String>,
+    /// Whether the module and all the inline modules enclosing it within its file are `pub`.
+    pub public: bool,
+    /// The `cfg` predicate of the declaration, including those of enclosing inline modules, if any.
+    pub cfg: Option<Cfg>,
+}
+
+/// A name re-exported by a `pub use` declaration.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReExport {
+    /// The path the name is exported as, e.g. `crate::Bytes`, or `crate::prelude::*` for globs.
+    ///
+    /// Only set if the declaration is reachable from the library root through public modules.
+    pub path: Option<String>,
+    /// The path of the re-exported item, with `self` and `super` resolved, e.g. `crate::bytes::Bytes`.
+    pub source: String,
+    /// The file containing the declaration.
+    pub file: Arc<Path>,
+    /// The line number of the declaration.
+    pub line: NonZeroUsize,
+    /// The `cfg` predicate of the declaration, if any.
+    pub cfg: Option<Cfg>,
+    /// The module containing the declaration, relative to its file until the index is finished.
+    module: Vec<String>,
 }
 
 impl SearchIndexMut {
     /// Searches for items within the index based on the provided query.
     ///
     pub fn search(&self, query: &ItemQuery) -> Vec<Item> {
-        let ItemQuery { type_, query, path } = query;
+        let ItemQuery {
+            type_, query, path, ..
+        } = query;
         let query = query.to_lowercase();
         let path = path.as_ref().map(|p| p.as_path());
         match type_ {
@@ -65,6 +131,139 @@ impl SearchIndexMut {
             ItemType::TypeAlias => filter_items(&query, &self.type_aliases, path),
         }
     }
+
+    /// Iterates over all items in the index.
+    ///
+    pub fn items(&self) -> impl Iterator<Item = &Item> {
+        [
+            &self.structs,
+            &self.enums,
+            &self.traits,
+            &self.impl_types,
+            &self.impl_trait_for_types,
+            &self.macros,
+            &self.attribute_macros,
+            &self.functions,
+            &self.type_aliases,
+        ]
+        .into_iter()
+        .flat_map(|items| items.values())
+        .flatten()
+    }
+
+    /// Iterates mutably over all items in the index.
+    ///
+    pub fn items_mut(&mut self) -> impl Iterator<Item = &mut Item> {
+        [
+            &mut self.structs,
+            &mut self.enums,
+            &mut self.traits,
+            &mut self.impl_types,
+            &mut self.impl_trait_for_types,
+            &mut self.macros,
+            &mut self.attribute_macros,
+            &mut self.functions,
+            &mut self.type_aliases,
+        ]
+        .into_iter()
+        .flat_map(|items| items.values_mut())
+        .flatten()
+    }
+
+    /// Returns the parse errors of the files within an optional path.
+    ///
+    pub fn parse_errors(&self, path: Option<&Path>) -> Vec<ParseError> {
+        self.parse_errors
+            .iter()
+            .filter(|error| path.is_none_or(|path| error.file.starts_with(path)))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the impls of a trait, including derived ones, within an optional path.
+    ///
+    /// Blanket impls of the trait are returned separately, since whether they apply to a type
+    /// depends on their bounds.
+    ///
+    pub fn implementors(&self, name: &str, path: Option<&Path>) -> ImplSearchResult {
+        let mut result = ImplSearchResult::default();
+        for item in self.impls(path) {
+            let Some(info) = &item.impl_info else {
+                continue;
+            };
+            if info
+                .trait_path
+                .as_deref()
+                .is_some_and(|trait_path| path_matches(trait_path, name))
+            {
+                match info.blanket {
+                    true => result.blanket.push(item.clone()),
+                    false => result.impls.push(item.clone()),
+                }
+            }
+        }
+        result
+    }
+
+    /// Returns the trait impls of a type, including derived ones, within an optional path.
+    ///
+    /// Blanket impls are returned if the type implements all the traits bounding them in the
+    /// crate, not counting auto traits like `Send` or `Sized`.
+    ///
+    pub fn implemented_traits(&self, name: &str, path: Option<&Path>) -> ImplSearchResult {
+        let mut result = ImplSearchResult::default();
+        let mut blanket = Vec::new();
+        for item in self.impls(path) {
+            let Some(info) = &item.impl_info else {
+                continue;
+            };
+            if info.trait_path.is_none() {
+                continue;
+            }
+            if info.blanket {
+                blanket.push(item);
+            } else if info
+                .self_name
+                .as_deref()
+                .is_some_and(|self_name| path_matches(self_name, name))
+            {
+                result.impls.push(item.clone());
+            }
+        }
+        let implemented = result
+            .impls
+            .iter()
+            .filter_map(|item| item.impl_info.as_ref()?.trait_name())
+            .chain(AUTO_TRAITS.iter().copied())
+            .collect::<BTreeSet<_>>();
+        result.blanket = blanket
+            .into_iter()
+            .filter(|item| {
+                item.impl_info.as_ref().is_some_and(|info| {
+                    info.bounds.iter().all(|bound| {
+                        implemented.contains(bound.rsplit("::").next().unwrap_or(bound))
+                    })
+                })
+            })
+            .cloned()
+            .collect();
+        result
+    }
+
+    /// Returns the impl blocks within an optional path, sorted by file, line and name.
+    fn impls(&self, path: Option<&Path>) -> Vec<&Item> {
+        let mut impls = self
+            .impl_types
+            .values()
+            .chain(self.impl_trait_for_types.values())
+            .flatten()
+            .filter(|item| path.is_none_or(|path| item.file.starts_with(path)))
+            .collect::<Vec<_>>();
+        impls.sort_by(|a, b| {
+            (&a.file, a.line_range.start(), &a.name).cmp(&(&b.file, b.line_range.start(), &b.name))
+        });
+        impls
+    }
 }
 
 /// Filters items from a hashmap based on a query and optional path.
@@ -76,9 +275,8 @@ fn filter_items(
 ) -> Vec<Item> {
     let flatten = items
         .iter()
-        .filter(|(name, _)| name.contains(&query))
-        .map(|(_, item)| item)
-        .flatten();
+        .filter(|(name, _)| name.contains(query))
+        .flat_map(|(_, item)| item);
     match path {
         None => flatten.cloned().collect::<Vec<Item>>(),
         Some(path) => flatten
@@ -99,6 +297,293 @@ impl SearchIndexMut {
     }
 }
 
+/// The public API of a crate: the items reachable from outside the crate, by path.
+///
+/// Items are registered under their own path if they are exported, and under the paths of the
+/// `pub use` declarations re-exporting them. Impl blocks are registered if the local types and
+/// traits they involve are exported, under the canonical path of their self type and the trait as
+/// written, e.g. `impl fmt::Display for crate::error::Error`.
+///
+#[derive(Debug, Default)]
+pub struct PublicApi<'a> {
+    /// The items by type and path.
+    pub items: BTreeMap<(ItemType, String), Vec<&'a Item>>,
+    /// The re-exports that do not refer to items of the crate, by path.
+    pub reexports: BTreeMap<String, &'a ReExport>,
+}
+
+impl<'a> PublicApi<'a> {
+    /// Collects the public API of a crate from its item index.
+    ///
+    pub fn new(index: &'a SearchIndexMut) -> Self {
+        let mut api = PublicApi::default();
+        let mut public = BTreeMap::<&str, Vec<&Item>>::new();
+        let mut impls = Vec::new();
+        for item in index.items() {
+            let Some(path) = &item.path else {
+                continue;
+            };
+            match item.type_ {
+                ItemType::ImplType | ItemType::ImplTraitForType => impls.push(item),
+                _ if item.exported => api.insert(item.type_, path.clone(), item),
+                _ => {}
+            }
+            if item.public {
+                public.entry(path.as_str()).or_default().push(item);
+            }
+        }
+
+        for reexport in &index.reexports {
+            let Some(path) = &reexport.path else {
+                continue;
+            };
+            let mut resolved = false;
+            if let Some(prefix) = reexport.source.strip_suffix("::*") {
+                let module = path.trim_end_matches("::*");
+                for (source, items) in public.range(prefix..) {
+                    let Some(rest) = source.strip_prefix(prefix) else {
+                        break;
+                    };
+                    let Some(name) = rest.strip_prefix("::") else {
+                        continue;
+                    };
+                    if name.contains("::") {
+                        continue;
+                    }
+                    for item in items {
+                        api.insert(item.type_, format!("{module}::{name}"), item);
+                        resolved = true;
+                    }
+                }
+            } else if let Some(items) = public.get(reexport.source.as_str()) {
+                for item in items {
+                    api.insert(item.type_, path.clone(), item);
+                    resolved = true;
+                }
+            }
+            if !resolved {
+                api.reexports.insert(path.clone(), reexport);
+            }
+        }
+
+        let types = LocalTypes::new(index);
+        let exported = api
+            .items
+            .values()
+            .flatten()
+            .map(|item| *item as *const Item)
+            .collect::<FnvHashSet<_>>();
+        for item in impls {
+            if let Some(key) = impl_key(item, &types, |item| {
+                exported.contains(&(item as *const Item))
+            }) {
+                api.insert(item.type_, key, item);
+            }
+        }
+        api
+    }
+
+    fn insert(&mut self, type_: ItemType, path: String, item: &'a Item) {
+        let items = self.items.entry((type_, path)).or_default();
+        if !items.iter().any(|i| std::ptr::eq(*i, item)) {
+            items.push(item);
+        }
+    }
+
+    /// Returns the items, other than impl blocks, available at a path.
+    ///
+    pub fn lookup(&self, path: &str) -> Vec<&'a Item> {
+        let mut items = Vec::new();
+        for ((type_, _), found) in self.items.iter().filter(|((_, p), _)| p == path) {
+            if !matches!(type_, ItemType::ImplType | ItemType::ImplTraitForType) {
+                items.extend(found.iter().copied());
+            }
+        }
+        items
+    }
+
+    /// Returns all paths an item is available at.
+    ///
+    pub fn paths_of(&self, item: &Item) -> BTreeSet<&str> {
+        self.items
+            .iter()
+            .filter(|(_, items)| items.iter().any(|i| std::ptr::eq(*i, item)))
+            .map(|((_, path), _)| path.as_str())
+            .collect()
+    }
+}
+
+/// The types and traits defined in the library, to resolve the paths written in impl blocks.
+///
+struct LocalTypes<'a> {
+    by_path: FnvHashMap<&'a str, &'a Item>,
+    by_name: FnvHashMap<&'a str, Vec<&'a Item>>,
+    modules: FnvHashSet<&'a str>,
+}
+
+/// The outcome of resolving a path written in an impl block.
+///
+enum Resolved<'a> {
+    /// The path refers to an item of the library.
+    Local(&'a Item),
+    /// The name is imported from one of several items of the library with that name.
+    Ambiguous(Vec<&'a Item>),
+    /// The path refers to an item of another crate, or to a type parameter.
+    Foreign,
+}
+
+impl<'a> LocalTypes<'a> {
+    fn new(index: &'a SearchIndexMut) -> Self {
+        let mut types = LocalTypes {
+            by_path: FnvHashMap::default(),
+            by_name: FnvHashMap::default(),
+            modules: FnvHashSet::default(),
+        };
+        for item in index.items() {
+            let Some(path) = item.path.as_deref() else {
+                continue;
+            };
+            let mut module = path;
+            while let Some((parent, _)) = module.rsplit_once("::") {
+                types.modules.insert(parent);
+                module = parent;
+            }
+            if matches!(
+                item.type_,
+                ItemType::Struct | ItemType::Enum | ItemType::TypeAlias | ItemType::Trait
+            ) {
+                types.by_path.insert(path, item);
+                types.by_name.entry(&item.name).or_default().push(item);
+            }
+        }
+        types
+    }
+
+    /// Resolves a path written in a module to a type, or to a trait if `trait_` is set.
+    ///
+    /// Single names are looked up in the module first, then among the items of the library with
+    /// that name, as they are usually brought into scope by a `use` declaration.
+    ///
+    fn resolve(&self, path: &syn::Path, module: &str, trait_: bool) -> Resolved<'a> {
+        let is_kind = |item: &Item| (item.type_ == ItemType::Trait) == trait_;
+        let module = module
+            .split("::")
+            .skip(1)
+            .map(String::from)
+            .collect::<Vec<_>>();
+        let written = path_name(path);
+        if path.leading_colon.is_some() {
+            return Resolved::Foreign;
+        }
+        if path.segments.len() > 1 {
+            let resolved = resolve_use_path(&written, &module, |path| {
+                self.modules.contains(path) || self.by_path.contains_key(path)
+            });
+            return match self
+                .by_path
+                .get(resolved.as_str())
+                .filter(|item| is_kind(item))
+            {
+                Some(item) => Resolved::Local(item),
+                None => Resolved::Foreign,
+            };
+        }
+        let in_module = join_path(&module, Some(&written));
+        if let Some(item) = self
+            .by_path
+            .get(in_module.as_str())
+            .filter(|item| is_kind(item))
+        {
+            return Resolved::Local(item);
+        }
+        let mut named = self
+            .by_name
+            .get(written.as_str())
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|item| is_kind(item))
+            .collect::<Vec<_>>();
+        match named.len() {
+            0 => Resolved::Foreign,
+            1 => Resolved::Local(named.remove(0)),
+            _ => Resolved::Ambiguous(named),
+        }
+    }
+}
+
+/// Returns the key of an impl block in the public API, or `None` if it involves a type or a trait
+/// of the library which is not exported.
+///
+/// Local paths are replaced by their canonical paths, so impls of types with the same name in
+/// different modules get different keys. An impl of a name imported from one of several items
+/// with that name is also keyed by its module.
+///
+fn impl_key(item: &Item, types: &LocalTypes, exported: impl Fn(&Item) -> bool) -> Option<String> {
+    let module = item.path.as_deref()?;
+    let Ok(mut parsed) = syn::parse_str::<ItemImpl>(&format!("{} {{}}", item.name)) else {
+        return Some(item.name.clone());
+    };
+    let blanket = item.impl_info.as_ref().is_some_and(|info| info.blanket);
+    let mut ambiguous = false;
+    let mut canonicalize = |path: &mut syn::Path, trait_: bool| -> bool {
+        match types.resolve(path, module, trait_) {
+            Resolved::Local(local) => {
+                if let Some(canonical) = local.path.as_deref() {
+                    set_path(path, canonical);
+                }
+                exported(local)
+            }
+            Resolved::Ambiguous(candidates) => {
+                ambiguous = true;
+                candidates.into_iter().any(&exported)
+            }
+            Resolved::Foreign => true,
+        }
+    };
+
+    let mut self_ty = &mut *parsed.self_ty;
+    while let Type::Reference(reference) = self_ty {
+        self_ty = &mut reference.elem;
+    }
+    if let Type::Path(type_path) = self_ty {
+        if type_path.qself.is_none() && !blanket && !canonicalize(&mut type_path.path, false) {
+            return None;
+        }
+    }
+    if let Some((_, path, _)) = &mut parsed.trait_ {
+        if !canonicalize(path, true) {
+            return None;
+        }
+    }
+
+    let self_ty = tokens(&parsed.self_ty);
+    let mut key = match &parsed.trait_ {
+        Some((bang, path, _)) => {
+            let bang = if bang.is_some() { "!" } else { "" };
+            format!("impl {bang}{} for {self_ty}", tokens(path))
+        }
+        None => format!("impl {self_ty}"),
+    };
+    if ambiguous {
+        key.push_str(&format!(" in {module}"));
+    }
+    Some(key)
+}
+
+/// Replaces the segments of a path by those of a canonical path, keeping the generic arguments of
+/// the last segment.
+///
+fn set_path(path: &mut syn::Path, canonical: &str) {
+    let Ok(mut new) = syn::parse_str::<syn::Path>(canonical) else {
+        return;
+    };
+    if let (Some(last), Some(old)) = (new.segments.last_mut(), path.segments.last()) {
+        last.arguments = old.arguments.clone();
+    }
+    *path = new;
+}
+
 /// A builder for constructing a `SearchIndex`.
 ///
 /// This struct facilitates the creation and population of a `SearchIndexMut`
@@ -106,26 +591,289 @@ impl SearchIndexMut {
 #[derive(Debug, Default)]
 pub struct SearchIndexBuilder {
     index: SearchIndexMut,
+    files: FnvHashSet<PathBuf>,
+    lib_root: Option<PathBuf>,
 }
 
 impl SearchIndexBuilder {
+    /// Sets the root source file of the library, `src/lib.rs` by default.
+    ///
+    /// Item paths are computed relative to this file, and only items reachable from it are
+    /// considered part of the public API.
+    ///
+    pub fn set_lib_root<P: AsRef<Path>>(&mut self, file: P) {
+        self.lib_root = Some(normalize_path(file.as_ref()));
+    }
+
     /// Updates the search index with items parsed from a Rust source file.
     ///
+    /// If the file fails to parse, the error is recorded in the index and `false` is returned.
+    ///
     pub fn update<P: AsRef<Path>>(&mut self, file: P, content: &str) -> bool {
-        let mut visitor = IndexVisitor::new(&mut self.index, file);
-        if let Ok(ast) = syn::parse_file(content) {
-            syn::visit::visit_file(&mut visitor, &ast);
-            true
-        } else {
-            false
+        self.files.insert(file.as_ref().to_path_buf());
+        match syn::parse_file(content) {
+            Ok(ast) => {
+                let mut visitor = IndexVisitor::new(&mut self.index, file);
+                syn::visit::visit_file(&mut visitor, &ast);
+                true
+            }
+            Err(error) => {
+                let span = error.span();
+                let (start, end) = (span.start(), span.end());
+                self.index.parse_errors.push(ParseError {
+                    file: Arc::from(file.as_ref()),
+                    message: error.to_string(),
+                    line: NonZeroUsize::new(start.line).unwrap_or(NonZeroUsize::MIN),
+                    column: NonZeroUsize::MIN.saturating_add(start.column),
+                    end_line: NonZeroUsize::new(end.line).unwrap_or(NonZeroUsize::MIN),
+                    end_column: NonZeroUsize::MIN.saturating_add(end.column),
+                });
+                false
+            }
         }
     }
 
     /// Finalizes the construction of the `SearchIndex`.
     ///
-    pub fn finish(self) -> SearchIndex {
+    /// The module tree is resolved from the `mod` declarations: the `cfg` predicates of the
+    /// declarations are propagated to the items of the files implementing those modules, and
+    /// items and re-exports reachable from the library root get their full paths.
+    ///
+    pub fn finish(mut self) -> SearchIndex {
+        let lib_root = self
+            .lib_root
+            .take()
+            .unwrap_or_else(|| PathBuf::from("src/lib.rs"));
+        let file_modules = self.file_modules();
+
+        for item in self.index.items_mut() {
+            let Some(module) = file_modules.get(item.file.as_ref()) else {
+                // Items of files that are not part of any module tree, e.g. `build.rs`.
+                item.path = None;
+                item.exported = false;
+                continue;
+            };
+            if !module.cfgs.is_empty() {
+                item.cfg = Cfg::all(module.cfgs.iter().cloned().chain(item.cfg.take()));
+            }
+            if module.root != lib_root {
+                item.path = None;
+                item.exported = false;
+                continue;
+            }
+            item.path = Some(join_path(&module.path, item.path.as_deref()));
+            item.exported = match item.type_ {
+                // Impls have no visibility of their own.
+                ItemType::ImplType | ItemType::ImplTraitForType => true,
+                // Exported macros are always available at the crate root.
+                ItemType::Macro => item.exported,
+                _ => item.exported && module.public,
+            };
+        }
+
+        // The paths of the modules and items of the library, to resolve relative `use` paths.
+        let mut local_paths = self
+            .index
+            .items()
+            .filter_map(|item| item.path.clone())
+            .collect::<FnvHashSet<_>>();
+        for declaration in &self.index.modules {
+            if let Some(module) = file_modules.get(declaration.file.as_ref()) {
+                if module.root == lib_root {
+                    let parent = [module.path.as_slice(), &declaration.parents].concat();
+                    local_paths.insert(join_path(&parent, Some(&declaration.name)));
+                }
+            }
+        }
+
+        for reexport in &mut self.index.reexports {
+            let Some(module) = file_modules.get(reexport.file.as_ref()) else {
+                continue;
+            };
+            if !module.cfgs.is_empty() {
+                reexport.cfg = Cfg::all(module.cfgs.iter().cloned().chain(reexport.cfg.take()));
+            }
+            let mut module_path = module.path.clone();
+            module_path.append(&mut reexport.module);
+            reexport.source = resolve_use_path(&reexport.source, &module_path, |path| {
+                local_paths.contains(path)
+            });
+            reexport.path = (module.root == lib_root && module.public && reexport.path.is_some())
+                .then(|| join_path(&module_path, reexport.path.as_deref()));
+            reexport.module = module_path;
+        }
+
+        self.index.file_modules = file_modules;
+        self.index.lib_root = lib_root;
         self.index.freeze()
     }
+
+    /// Resolves the module each indexed file implements.
+    ///
+    /// Files not declared as a module by any other file are treated as roots of module trees.
+    ///
+    fn file_modules(&self) -> FnvHashMap<PathBuf, FileModule> {
+        // The file implementing each module declaration, with the declaration.
+        let mut declarations = FnvHashMap::<PathBuf, &ModuleDeclaration>::default();
+        for declaration in &self.index.modules {
+            if declaration.inline {
+                continue;
+            }
+            if let Some(target) = module_file(declaration, |path| self.files.contains(path)) {
+                declarations.insert(target, declaration);
+            }
+        }
+
+        let mut file_modules = FnvHashMap::default();
+        for file in &self.files {
+            let mut module = FileModule {
+                root: file.clone(),
+                path: Vec::new(),
+                public: true,
+                cfgs: Vec::new(),
+            };
+            let mut visited = FnvHashSet::default();
+            let mut current = file.as_path();
+            while let Some(declaration) = declarations.get(current) {
+                if !visited.insert(current) {
+                    break;
+                }
+                let mut segments = declaration.parents.clone();
+                segments.push(declaration.name.clone());
+                segments.append(&mut module.path);
+                module.path = segments;
+                module.public &= declaration.public;
+                module.cfgs.extend(declaration.cfg.iter().cloned());
+                module.root = declaration.file.to_path_buf();
+                current = &declaration.file;
+            }
+            file_modules.insert(file.clone(), module);
+        }
+        file_modules
+    }
+}
+
+/// The module implemented by a file.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileModule {
+    /// The root file of the module tree, e.g. `src/lib.rs` or `src/main.rs`.
+    pub root: PathBuf,
+    /// The path of the module relative to the root.
+    pub path: Vec<String>,
+    /// Whether the module and all of its parents are `pub`.
+    pub public: bool,
+    /// The `cfg` predicates of the declarations of the module and all of its parents.
+    pub cfgs: Vec<Cfg>,
+}
+
+/// Joins module path segments and an optional relative path into a path starting with `crate`.
+fn join_path(module: &[String], relative: Option<&str>) -> String {
+    let mut path = String::from("crate");
+    for segment in module
+        .iter()
+        .map(String::as_str)
+        .chain(relative.filter(|r| !r.is_empty()))
+    {
+        path.push_str("::");
+        path.push_str(segment);
+    }
+    path
+}
+
+/// Resolves `self`, `super` and `crate` prefixes of a `use` path within a module.
+///
+/// Paths starting with another name are relative to the module if `is_local` accepts the path of
+/// their first segment in the module. Otherwise they refer to an external crate and are kept as
+/// written.
+///
+fn resolve_use_path(source: &str, module: &[String], is_local: impl Fn(&str) -> bool) -> String {
+    let mut segments = source.split("::").collect::<Vec<_>>();
+    let mut base = module.to_vec();
+    match segments.first() {
+        Some(&"crate") => return source.to_string(),
+        Some(&"self") => {
+            segments.remove(0);
+        }
+        Some(&"super") => {
+            while segments.first() == Some(&"super") {
+                segments.remove(0);
+                base.pop();
+            }
+        }
+        Some(first) if is_local(&join_path(module, Some(first))) => {}
+        _ => return source.to_string(),
+    }
+    join_path(&base, Some(&segments.join("::")))
+}
+
+/// Returns the directory containing the files of the submodules declared in a file.
+///
+/// For crate roots and `mod.rs` files this is the directory of the file itself, otherwise it is a
+/// directory named after the file, e.g. `src/foo` for `src/foo.rs`.
+///
+pub fn module_directory(file: &Path) -> PathBuf {
+    let parent = file.parent().unwrap_or(Path::new(""));
+    let is_root = matches!(
+        file.file_name().and_then(|name| name.to_str()),
+        Some("lib.rs" | "main.rs" | "mod.rs" | "build.rs")
+    ) || ["src/bin", "examples", "tests", "benches"]
+        .iter()
+        .any(|dir| parent == Path::new(dir));
+    match file.file_stem() {
+        Some(stem) if !is_root => parent.join(stem),
+        _ => parent.to_path_buf(),
+    }
+}
+
+/// Resolves the file implementing a non-inline module declaration.
+///
+/// The `exists` function tells whether a file exists in the crate.
+///
+pub fn module_file(
+    declaration: &ModuleDeclaration,
+    exists: impl Fn(&Path) -> bool,
+) -> Option<PathBuf> {
+    let mut dir = module_directory(&declaration.file);
+    if let Some(path_attr) = &declaration.path_attr {
+        // Paths of non-inline modules declared at the top level of a file are relative to the
+        // directory of that file, otherwise to the directory of the enclosing inline modules.
+        let mut base = if declaration.parents.is_empty() {
+            declaration
+                .file
+                .parent()
+                .unwrap_or(Path::new(""))
+                .to_path_buf()
+        } else {
+            dir
+        };
+        base.extend(&declaration.parents);
+        let path = normalize_path(&base.join(path_attr));
+        return exists(&path).then_some(path);
+    }
+
+    dir.extend(&declaration.parents);
+    [
+        dir.join(format!("{}.rs", declaration.name)),
+        dir.join(&declaration.name).join("mod.rs"),
+    ]
+    .into_iter()
+    .find(|path| exists(path))
+}
+
+/// Removes `.` and `..` components from a relative path.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
 }
 
 /// A visitor struct for traversing and indexing Rust syntax trees.
@@ -135,6 +883,9 @@ impl SearchIndexBuilder {
 pub struct IndexVisitor<'i> {
     index: &'i mut SearchIndexMut,
     current_file: Arc<Path>,
+    modules: Vec<String>,
+    public: Vec<bool>,
+    cfgs: Vec<Option<Cfg>>,
 }
 
 impl<'i> IndexVisitor<'i> {
@@ -144,15 +895,37 @@ impl<'i> IndexVisitor<'i> {
         IndexVisitor {
             index,
             current_file: Arc::from(current_file.as_ref()),
+            modules: Vec::new(),
+            public: Vec::new(),
+            cfgs: Vec::new(),
         }
     }
 
+    /// Returns the `cfg` predicate of an item, including those of the enclosing inline modules.
+    fn cfg(&self, attrs: &[Attribute]) -> Option<Cfg> {
+        Cfg::all(
+            self.cfgs
+                .iter()
+                .flatten()
+                .cloned()
+                .chain(Cfg::from_attributes(attrs)),
+        )
+    }
+
+    /// Returns whether all enclosing inline modules are `pub`.
+    fn modules_public(&self) -> bool {
+        self.public.iter().all(|public| *public)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn create_item(
         &self,
         name: String,
         type_: ItemType,
         item_span: proc_macro2::Span,
         attrs: &[Attribute],
+        vis: Option<&Visibility>,
+        signature: String,
     ) -> Item {
         // 获取项的 span
         let mut start_line = item_span.start().line;
@@ -169,19 +942,148 @@ impl<'i> IndexVisitor<'i> {
         let start_line = NonZeroUsize::new(start_line).unwrap_or(NonZeroUsize::MIN);
         let end_line = NonZeroUsize::new(end_line).unwrap_or(NonZeroUsize::MAX);
 
+        let public = match vis {
+            Some(vis) => matches!(vis, Visibility::Public(_)),
+            None => match type_ {
+                ItemType::Macro => attrs
+                    .iter()
+                    .any(|attr| attr.path().is_ident("macro_export")),
+                _ => true,
+            },
+        };
+
+        // The path is relative to the file until the index is finished.
+        let mut path = self.modules.clone();
+        match type_ {
+            ItemType::ImplType | ItemType::ImplTraitForType => {}
+            ItemType::Macro if public => path.clear(),
+            _ => path.push(name.clone()),
+        }
+
         Item {
             name,
             type_,
             file: self.current_file.clone(),
             line_range: start_line..=end_line,
+            cfg: self.cfg(attrs),
+            path: Some(path.join("::")),
+            public,
+            exported: public && self.modules_public(),
+            signature: Some(signature),
+            impl_info: None,
+        }
+    }
+
+    /// Records the trait impls generated by the `#[derive(...)]` attributes of a struct or enum.
+    fn add_derives(&mut self, ident: &Ident, generics: &Generics, attrs: &[Attribute]) {
+        // Doc comments belong to the type, not to its derived impls.
+        let cfg_attrs = attrs
+            .iter()
+            .filter(|attr| !attr.path().is_ident("doc"))
+            .cloned()
+            .collect::<Vec<_>>();
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let self_type = tokens(quote::quote!(#ident #ty_generics));
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("derive")) {
+            let Ok(paths) =
+                attr.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+            else {
+                continue;
+            };
+            for path in paths {
+                let signature = tokens(
+                    quote::quote!(impl #impl_generics #path for #ident #ty_generics #where_clause),
+                );
+                let mut item = self.create_item(
+                    format!("impl {} for {}", tokens(&path), self_type),
+                    ItemType::ImplTraitForType,
+                    attr.span(),
+                    &cfg_attrs,
+                    None,
+                    format!("#[derive({})] {signature}", tokens(&path)),
+                );
+                item.impl_info = Some(ImplInfo {
+                    trait_path: Some(path_name(&path)),
+                    self_type: self_type.clone(),
+                    self_name: Some(ident.to_string()),
+                    blanket: false,
+                    bounds: Vec::new(),
+                    derived: true,
+                });
+                self.index
+                    .impl_trait_for_types
+                    .entry(item.name.to_lowercase())
+                    .or_default()
+                    .push(item);
+            }
         }
     }
 }
 
 impl<'i, 'ast> syn::visit::Visit<'ast> for IndexVisitor<'i> {
+    fn visit_item_mod(&mut self, i: &'ast ItemMod) {
+        let cfg = self.cfg(&i.attrs);
+        let public = matches!(i.vis, Visibility::Public(_));
+        self.index.modules.push(ModuleDeclaration {
+            name: i.ident.to_string(),
+            file: self.current_file.clone(),
+            parents: self.modules.clone(),
+            inline: i.content.is_some(),
+            path_attr: path_attribute(&i.attrs),
+            public: public && self.modules_public(),
+            cfg,
+        });
+
+        if i.content.is_some() {
+            self.modules.push(i.ident.to_string());
+            self.public.push(public);
+            self.cfgs.push(Cfg::from_attributes(&i.attrs));
+            syn::visit::visit_item_mod(self, i);
+            self.cfgs.pop();
+            self.public.pop();
+            self.modules.pop();
+        }
+    }
+
+    fn visit_item_use(&mut self, i: &'ast ItemUse) {
+        if !matches!(i.vis, Visibility::Public(_)) {
+            return;
+        }
+        let line = NonZeroUsize::new(i.span().start().line).unwrap_or(NonZeroUsize::MIN);
+        let cfg = self.cfg(&i.attrs);
+        let mut uses = Vec::new();
+        flatten_use_tree(&i.tree, String::new(), &mut uses);
+        for (source, name) in uses {
+            if name == "_" {
+                continue;
+            }
+            let mut path = self.modules.clone();
+            path.push(name);
+            self.index.reexports.push(ReExport {
+                path: self
+                    .modules_public()
+                    .then(|| path[self.modules.len()..].join("::")),
+                source,
+                file: self.current_file.clone(),
+                line,
+                cfg: cfg.clone(),
+                module: self.modules.clone(),
+            });
+        }
+    }
+
     fn visit_item_enum(&mut self, i: &'ast ItemEnum) {
         let name = i.ident.to_string();
-        let item = self.create_item(name, ItemType::Enum, i.span(), &i.attrs);
+        let signature = enum_signature(i);
+        let item = self.create_item(
+            name,
+            ItemType::Enum,
+            i.span(),
+            &i.attrs,
+            Some(&i.vis),
+            signature,
+        );
+        self.add_derives(&i.ident, &i.generics, &i.attrs);
         self.index
             .enums
             .entry(item.name.to_lowercase())
@@ -190,9 +1092,22 @@ impl<'i, 'ast> syn::visit::Visit<'ast> for IndexVisitor<'i> {
     }
 
     fn visit_item_fn(&mut self, i: &'ast ItemFn) {
+        let vis = &i.vis;
+        let sig = &i.sig;
         if is_attribute_macro(&i.attrs) {
             let name = i.sig.ident.to_string();
-            let item = self.create_item(name, ItemType::AttributeMacro, i.span(), &i.attrs);
+            let signature = format!(
+                "#[proc_macro_attribute] {}",
+                tokens(quote::quote!(#vis #sig))
+            );
+            let item = self.create_item(
+                name,
+                ItemType::AttributeMacro,
+                i.span(),
+                &i.attrs,
+                Some(vis),
+                signature,
+            );
             self.index
                 .attribute_macros
                 .entry(item.name.to_lowercase())
@@ -200,7 +1115,15 @@ impl<'i, 'ast> syn::visit::Visit<'ast> for IndexVisitor<'i> {
                 .push(item);
         } else {
             let name = i.sig.ident.to_string();
-            let item = self.create_item(name, ItemType::Function, i.span(), &i.attrs);
+            let signature = tokens(quote::quote!(#vis #sig));
+            let item = self.create_item(
+                name,
+                ItemType::Function,
+                i.span(),
+                &i.attrs,
+                Some(vis),
+                signature,
+            );
             self.index
                 .functions
                 .entry(item.name.to_lowercase())
@@ -211,6 +1134,7 @@ impl<'i, 'ast> syn::visit::Visit<'ast> for IndexVisitor<'i> {
 
     fn visit_item_impl(&mut self, i: &'ast ItemImpl) {
         let self_ty = &i.self_ty;
+        let signature = impl_signature(i);
 
         match &i.trait_ {
             Some((_, path, _)) => {
@@ -220,8 +1144,15 @@ impl<'i, 'ast> syn::visit::Visit<'ast> for IndexVisitor<'i> {
                     quote::quote! { #path },
                     quote::quote! { #self_ty }
                 );
-                let item =
-                    self.create_item(impl_name, ItemType::ImplTraitForType, i.span(), &i.attrs);
+                let mut item = self.create_item(
+                    impl_name,
+                    ItemType::ImplTraitForType,
+                    i.span(),
+                    &i.attrs,
+                    None,
+                    signature,
+                );
+                item.impl_info = Some(impl_info(i));
                 self.index
                     .impl_trait_for_types
                     .entry(item.name.to_lowercase())
@@ -231,7 +1162,15 @@ impl<'i, 'ast> syn::visit::Visit<'ast> for IndexVisitor<'i> {
             None => {
                 // impl Type
                 let impl_name = format!("impl {}", quote::quote! { #self_ty });
-                let item = self.create_item(impl_name, ItemType::ImplType, i.span(), &i.attrs);
+                let mut item = self.create_item(
+                    impl_name,
+                    ItemType::ImplType,
+                    i.span(),
+                    &i.attrs,
+                    None,
+                    signature,
+                );
+                item.impl_info = Some(impl_info(i));
                 self.index
                     .impl_types
                     .entry(item.name.to_lowercase())
@@ -244,7 +1183,8 @@ impl<'i, 'ast> syn::visit::Visit<'ast> for IndexVisitor<'i> {
     fn visit_item_macro(&mut self, i: &'ast ItemMacro) {
         if let Some(ident) = &i.ident {
             let name = ident.to_string();
-            let item = self.create_item(name, ItemType::Macro, i.span(), &i.attrs);
+            let signature = format!("macro_rules! {name}");
+            let item = self.create_item(name, ItemType::Macro, i.span(), &i.attrs, None, signature);
             self.index
                 .macros
                 .entry(item.name.to_lowercase())
@@ -255,7 +1195,16 @@ impl<'i, 'ast> syn::visit::Visit<'ast> for IndexVisitor<'i> {
 
     fn visit_item_struct(&mut self, i: &'ast ItemStruct) {
         let name = i.ident.to_string();
-        let item = self.create_item(name, ItemType::Struct, i.span(), &i.attrs);
+        let signature = struct_signature(i);
+        let item = self.create_item(
+            name,
+            ItemType::Struct,
+            i.span(),
+            &i.attrs,
+            Some(&i.vis),
+            signature,
+        );
+        self.add_derives(&i.ident, &i.generics, &i.attrs);
         self.index
             .structs
             .entry(item.name.to_lowercase())
@@ -265,7 +1214,15 @@ impl<'i, 'ast> syn::visit::Visit<'ast> for IndexVisitor<'i> {
 
     fn visit_item_trait(&mut self, i: &'ast ItemTrait) {
         let name = i.ident.to_string();
-        let item = self.create_item(name, ItemType::Trait, i.span(), &i.attrs);
+        let signature = trait_signature(i);
+        let item = self.create_item(
+            name,
+            ItemType::Trait,
+            i.span(),
+            &i.attrs,
+            Some(&i.vis),
+            signature,
+        );
         self.index
             .traits
             .entry(item.name.to_lowercase())
@@ -275,7 +1232,17 @@ impl<'i, 'ast> syn::visit::Visit<'ast> for IndexVisitor<'i> {
 
     fn visit_item_type(&mut self, i: &'ast syn::ItemType) {
         let name = i.ident.to_string();
-        let item = self.create_item(name, ItemType::TypeAlias, i.span(), &i.attrs);
+        let mut alias = i.clone();
+        alias.attrs.clear();
+        let signature = tokens(&alias);
+        let item = self.create_item(
+            name,
+            ItemType::TypeAlias,
+            i.span(),
+            &i.attrs,
+            Some(&i.vis),
+            signature,
+        );
         self.index
             .type_aliases
             .entry(item.name.to_lowercase())
@@ -284,6 +1251,361 @@ impl<'i, 'ast> syn::visit::Visit<'ast> for IndexVisitor<'i> {
     }
 }
 
+/// Flattens a `use` tree into pairs of source paths and the names they are imported as.
+///
+/// Glob imports are returned with the name `*`.
+pub(crate) fn flatten_use_tree(tree: &UseTree, prefix: String, uses: &mut Vec<(String, String)>) {
+    let join = |name: &str| {
+        if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{prefix}::{name}")
+        }
+    };
+    match tree {
+        UseTree::Path(path) => flatten_use_tree(&path.tree, join(&path.ident.to_string()), uses),
+        UseTree::Name(name) => {
+            let name = name.ident.to_string();
+            if name == "self" {
+                let last = prefix.rsplit("::").next().unwrap_or_default().to_string();
+                uses.push((prefix.clone(), last));
+            } else {
+                uses.push((join(&name), name));
+            }
+        }
+        UseTree::Rename(rename) => {
+            uses.push((join(&rename.ident.to_string()), rename.rename.to_string()))
+        }
+        UseTree::Glob(_) => uses.push((join("*"), "*".to_string())),
+        UseTree::Group(group) => {
+            for tree in &group.items {
+                flatten_use_tree(tree, prefix.clone(), uses);
+            }
+        }
+    }
+}
+
+/// Converts tokens to a compact, readable string.
+///
+/// `proc_macro2` separates all tokens with spaces; the spaces that rustfmt would not emit are removed.
+pub(crate) fn tokens(tokens: impl ToTokens) -> String {
+    let s = tokens.to_token_stream().to_string();
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == ' ' {
+            let prev = out.chars().next_back().unwrap_or(' ');
+            let next = chars.peek().copied().unwrap_or(' ');
+            let drop = matches!(next, ',' | ';' | ')' | ']' | '.' | ':' | '>')
+                || (next == '?' && !matches!(prev, ':' | '+'))
+                || matches!(prev, '(' | '[' | '<' | '&' | '!' | '#' | '*')
+                || (prev == '?' && out.ends_with(" ?"))
+                || (prev == ':' && out.ends_with("::"))
+                || (next == '<' && is_ident(prev))
+                || (next == '(' && (is_ident(prev) || (prev == '>' && !out.ends_with("->"))));
+            if drop {
+                continue;
+            }
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Returns the `#[non_exhaustive]` marker of an item, if any.
+fn non_exhaustive(attrs: &[Attribute]) -> &'static str {
+    if attrs
+        .iter()
+        .any(|attr| attr.path().is_ident("non_exhaustive"))
+    {
+        "#[non_exhaustive] "
+    } else {
+        ""
+    }
+}
+
+/// The signature of a struct: its header followed by one line per public field.
+///
+/// A line containing `..` indicates the struct has private fields.
+fn struct_signature(i: &ItemStruct) -> String {
+    let ItemStruct {
+        vis,
+        struct_token,
+        ident,
+        generics,
+        ..
+    } = i;
+    let where_clause = &generics.where_clause;
+    let mut lines = vec![format!(
+        "{}{}",
+        non_exhaustive(&i.attrs),
+        tokens(quote::quote!(#vis #struct_token #ident #generics #where_clause))
+    )];
+    let mut private = false;
+    let fields = match &i.fields {
+        Fields::Named(fields) => fields.named.iter().collect::<Vec<_>>(),
+        Fields::Unnamed(fields) => fields.unnamed.iter().collect(),
+        Fields::Unit => Vec::new(),
+    };
+    for (index, field) in fields.into_iter().enumerate() {
+        if !matches!(field.vis, Visibility::Public(_)) {
+            private = true;
+            continue;
+        }
+        let ty = &field.ty;
+        let name = field
+            .ident
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_else(|| index.to_string());
+        lines.push(format!("pub {name}: {}", tokens(ty)));
+    }
+    if private {
+        lines.push("..".to_string());
+    }
+    lines.join("\n")
+}
+
+/// The signature of an enum: its header followed by one line per variant.
+fn enum_signature(i: &ItemEnum) -> String {
+    let ItemEnum {
+        vis,
+        enum_token,
+        ident,
+        generics,
+        ..
+    } = i;
+    let where_clause = &generics.where_clause;
+    let mut lines = vec![format!(
+        "{}{}",
+        non_exhaustive(&i.attrs),
+        tokens(quote::quote!(#vis #enum_token #ident #generics #where_clause))
+    )];
+    for variant in &i.variants {
+        let mut variant = variant.clone();
+        variant.attrs.clear();
+        variant.discriminant = None;
+        lines.push(tokens(&variant));
+    }
+    lines.join("\n")
+}
+
+/// The signature of a trait: its header followed by one line per associated item.
+///
+/// Items with a default implementation end with `{ .. }`, required items end with `;`.
+fn trait_signature(i: &ItemTrait) -> String {
+    let ItemTrait {
+        vis,
+        unsafety,
+        auto_token,
+        trait_token,
+        ident,
+        generics,
+        colon_token,
+        supertraits,
+        ..
+    } = i;
+    let where_clause = &generics.where_clause;
+    let mut lines = vec![tokens(quote::quote!(
+        #vis #unsafety #auto_token #trait_token #ident #generics #colon_token #supertraits #where_clause
+    ))];
+    for item in &i.items {
+        let line = match item {
+            TraitItem::Fn(f) => {
+                let sig = &f.sig;
+                format!(
+                    "{}{}",
+                    tokens(sig),
+                    if f.default.is_some() { " { .. }" } else { ";" }
+                )
+            }
+            TraitItem::Type(t) => {
+                let mut t = t.clone();
+                t.attrs.clear();
+                let has_default = t.default.take().is_some();
+                let s = tokens(&t);
+                if has_default {
+                    format!("{} {{ .. }}", s.trim_end_matches(';'))
+                } else {
+                    s
+                }
+            }
+            TraitItem::Const(c) => {
+                let mut c = c.clone();
+                c.attrs.clear();
+                let has_default = c.default.take().is_some();
+                let s = tokens(&c);
+                if has_default {
+                    format!("{} {{ .. }}", s.trim_end_matches(';'))
+                } else {
+                    s
+                }
+            }
+            _ => continue,
+        };
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+/// The signature of an impl block: its header, followed by one line per public associated
+/// item for inherent impls.
+/// Returns the structure of an impl block.
+///
+/// The self type of a blanket impl is one of the impl's type parameters, possibly behind
+/// references, e.g. `impl<T: Read> Read for &mut T`.
+///
+fn impl_info(i: &ItemImpl) -> ImplInfo {
+    let mut self_ty = &*i.self_ty;
+    while let Type::Reference(reference) = self_ty {
+        self_ty = &reference.elem;
+    }
+    let type_param = match self_ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .get_ident()
+            .and_then(|ident| i.generics.type_params().find(|param| param.ident == *ident)),
+        _ => None,
+    };
+    let self_name = match self_ty {
+        Type::Path(path) if type_param.is_none() => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string()),
+        _ => None,
+    };
+    let bounds = type_param
+        .map(|param| {
+            let where_bounds = i
+                .generics
+                .where_clause
+                .iter()
+                .flat_map(|clause| clause.predicates.iter())
+                .filter_map(|predicate| match predicate {
+                    WherePredicate::Type(predicate) => Some(predicate),
+                    _ => None,
+                })
+                .filter(|predicate| match &predicate.bounded_ty {
+                    Type::Path(path) => path.path.is_ident(&param.ident),
+                    _ => false,
+                })
+                .flat_map(|predicate| predicate.bounds.iter());
+            param
+                .bounds
+                .iter()
+                .chain(where_bounds)
+                .filter_map(|bound| match bound {
+                    TypeParamBound::Trait(bound)
+                        if matches!(bound.modifier, TraitBoundModifier::None) =>
+                    {
+                        Some(path_name(&bound.path))
+                    }
+                    _ => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    ImplInfo {
+        trait_path: i.trait_.as_ref().map(|(_, path, _)| path_name(path)),
+        self_type: tokens(&i.self_ty),
+        self_name,
+        blanket: type_param.is_some(),
+        bounds,
+        derived: false,
+    }
+}
+
+/// Returns a path without its generic arguments, e.g. `io::Read` for `io::Read<'a>`.
+fn path_name(path: &syn::Path) -> String {
+    let segments = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>();
+    let prefix = if path.leading_colon.is_some() {
+        "::"
+    } else {
+        ""
+    };
+    format!("{prefix}{}", segments.join("::"))
+}
+
+/// Tells whether the path of a trait or type written in an impl matches a queried name or path.
+///
+/// Generic arguments are ignored, and paths are compared by their trailing segments, so that
+/// `Read` matches `std::io::Read` and `io::Read` does not match `fmt::Write`.
+///
+fn path_matches(written: &str, query: &str) -> bool {
+    let strip = |path: &str| -> Vec<String> {
+        let path = path.split('<').next().unwrap_or(path);
+        path.split("::")
+            .map(|segment| segment.trim().to_string())
+            .filter(|segment| !segment.is_empty())
+            .collect()
+    };
+    let (written, query) = (strip(written), strip(query));
+    !query.is_empty()
+        && written
+            .iter()
+            .rev()
+            .zip(query.iter().rev())
+            .all(|(a, b)| a == b)
+}
+
+fn impl_signature(i: &ItemImpl) -> String {
+    let ItemImpl {
+        defaultness,
+        unsafety,
+        impl_token,
+        generics,
+        trait_,
+        self_ty,
+        ..
+    } = i;
+    let where_clause = &generics.where_clause;
+    let trait_ = trait_
+        .as_ref()
+        .map(|(bang, path, for_token)| quote::quote!(#bang #path #for_token));
+    let mut lines = vec![tokens(quote::quote!(
+        #defaultness #unsafety #impl_token #generics #trait_ #self_ty #where_clause
+    ))];
+    if i.trait_.is_none() {
+        for item in &i.items {
+            let line = match item {
+                ImplItem::Fn(f) if matches!(f.vis, Visibility::Public(_)) => {
+                    let (vis, sig) = (&f.vis, &f.sig);
+                    tokens(quote::quote!(#vis #sig))
+                }
+                ImplItem::Const(c) if matches!(c.vis, Visibility::Public(_)) => {
+                    let (vis, ident, ty) = (&c.vis, &c.ident, &c.ty);
+                    tokens(quote::quote!(#vis const #ident: #ty))
+                }
+                _ => continue,
+            };
+            lines.push(line);
+        }
+    }
+    lines.join("\n")
+}
+
+/// Returns the value of a `#[path = "..."]` attribute.
+fn path_attribute(attrs: &[Attribute]) -> Option<String> {
+    attrs.iter().find_map(|attr| match &attr.meta {
+        Meta::NameValue(name_value) if name_value.path.is_ident("path") => {
+            match &name_value.value {
+                Expr::Lit(expr) => match &expr.lit {
+                    Lit::Str(value) => Some(value.value()),
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+        _ => None,
+    })
+}
+
 fn is_attribute_macro(attrs: &[Attribute]) -> bool {
     attrs.iter().any(|attr| {
         // check proc_macro_attribute
@@ -0,0 +1,177 @@
+//! The `vcs` module.
+//!
+//! Maps a published crate version to its source in the repository it was published from. When a
+//! crate is packaged from a git checkout, Cargo records the commit and the directory of the
+//! package in the repository in `.cargo_vcs_info.json`, and the manifest names the repository.
+//! Together they locate the published files in the repository, so that the published code can be
+//! compared with the code at that commit or at the head of the default branch.
+//!
+use crate::cache::Crate;
+use crate::github::Repository;
+use crate::FileLineRange;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::num::NonZeroUsize;
+
+#[cfg(feature = "utoipa")]
+use utoipa::ToSchema;
+
+/// The file in which Cargo records the commit a crate was packaged from.
+pub const VCS_INFO_FILE: &str = ".cargo_vcs_info.json";
+
+/// The source of a published crate version in its repository.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct CrateSource {
+    /// The `repository` URL of the manifest.
+    pub repository: Option<String>,
+    /// The GitHub repository, if `repository` points to GitHub.
+    pub github: Option<Repository>,
+    /// The SHA of the commit the version was packaged from.
+    pub commit_sha: Option<String>,
+    /// Whether the version was packaged with uncommitted changes, in which case its files may
+    /// differ from those of the commit.
+    pub dirty: bool,
+    /// The directory of the package in the repository, empty if it is the root.
+    pub path_in_vcs: String,
+}
+
+impl CrateSource {
+    /// Reads the source of a crate from its manifest and `.cargo_vcs_info.json`.
+    ///
+    /// Crates packaged outside of a git checkout, or before Cargo recorded it, have no commit.
+    ///
+    pub fn of(krate: &Crate) -> Self {
+        let repository = krate
+            .manifest()
+            .and_then(|manifest| manifest.package.repository.clone());
+        let vcs_info = krate
+            .get_file_desc(VCS_INFO_FILE)
+            .and_then(|desc| serde_json::from_slice::<VcsInfo>(&krate.file_data(desc)).ok())
+            .unwrap_or_default();
+        let (commit_sha, dirty) = vcs_info
+            .git
+            .map_or((None, false), |git| (Some(git.sha1), git.dirty));
+        Self {
+            github: repository.as_deref().and_then(parse_github_url),
+            repository,
+            commit_sha,
+            dirty,
+            path_in_vcs: vcs_info.path_in_vcs.trim_matches('/').to_string(),
+        }
+    }
+
+    /// Returns the path in the repository of a file of the crate.
+    ///
+    /// `Cargo.toml.orig`, the manifest as written before packaging, maps to `Cargo.toml`.
+    ///
+    pub fn repository_path(&self, path: &str) -> String {
+        let path = path.trim_start_matches('/');
+        let path = if path == "Cargo.toml.orig" {
+            "Cargo.toml"
+        } else {
+            path
+        };
+        match (self.path_in_vcs.as_str(), path) {
+            ("", path) => path.to_string(),
+            (dir, "") => dir.to_string(),
+            (dir, path) => format!("{dir}/{path}"),
+        }
+    }
+}
+
+/// Parses the owner and name of a GitHub repository from its URL.
+///
+/// Accepts the HTTPS, `git+https`, `ssh` and `git@github.com:` forms, with or without a `.git`
+/// suffix, and ignores any path after the repository name, e.g. `/tree/main/crates/foo`.
+///
+pub fn parse_github_url(url: &str) -> Option<Repository> {
+    let url = url.trim();
+    let path = match url.strip_prefix("git@github.com:") {
+        Some(path) => path,
+        None => {
+            let url = url.strip_prefix("git+").unwrap_or(url);
+            let (_, rest) = url.split_once("://")?;
+            let (authority, path) = rest.split_once('/')?;
+            let host = authority
+                .rsplit_once('@')
+                .map_or(authority, |(_, host)| host);
+            let host = host.strip_prefix("www.").unwrap_or(host);
+            if !host.eq_ignore_ascii_case("github.com") {
+                return None;
+            }
+            path
+        }
+    };
+    let mut segments = path
+        .split(['/', '?', '#'])
+        .filter(|segment| !segment.is_empty());
+    let owner = segments.next()?;
+    let repo = segments.next()?;
+    let repo = repo.strip_suffix(".git").unwrap_or(repo);
+    Some(Repository::from((owner, repo)))
+}
+
+/// The revision of the repository at which to read the files of a crate.
+///
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub enum SourceRevision {
+    /// The commit the version was packaged from.
+    #[default]
+    Commit,
+    /// The head of the default branch.
+    Head,
+}
+
+/// The revision and line range of a file of a crate read from its repository.
+///
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct SourceFileQuery {
+    /// The revision to read, `commit` by default.
+    #[serde(default)]
+    pub at: SourceRevision,
+    /// The start line number.
+    pub start: Option<NonZeroUsize>,
+    /// The end line number.
+    pub end: Option<NonZeroUsize>,
+}
+
+impl SourceFileQuery {
+    /// Returns the line range to read.
+    pub fn line_range(&self) -> FileLineRange {
+        FileLineRange {
+            start: self.start,
+            end: self.end,
+        }
+    }
+}
+
+/// The error of reading a file of a crate from a repository it cannot be mapped to.
+///
+#[derive(Debug)]
+pub struct SourceUnavailable(pub String);
+
+impl Display for SourceUnavailable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for SourceUnavailable {}
+
+#[derive(Deserialize, Default)]
+struct VcsInfo {
+    git: Option<GitInfo>,
+    #[serde(default)]
+    path_in_vcs: String,
+}
+
+#[derive(Deserialize)]
+struct GitInfo {
+    sha1: String,
+    #[serde(default)]
+    dirty: bool,
+}
@@ -0,0 +1,1322 @@
+//! The `xref` module.
+//!
+//! Cross-references between items and the code using them. Paths written in a source file are
+//! resolved to canonical paths through the module the file implements and the `use` declarations
+//! in scope, including renamed and glob imports. A canonical path starts either with `crate`, for
+//! items of the crate itself, or with the name of an external crate.
+//!
+//! The resolution is syntactic: it does not know the types of expressions, so method calls can
+//! only be matched by name, and names brought into scope by macros are not seen.
+//!
+use crate::cache::{Crate, CrateFileDataDesc};
+use crate::manifest::{Dependency, Manifest};
+use crate::search::{flatten_use_tree, tokens, PublicApi, SearchIndex};
+use crate::{CrateItem, Item, ItemType};
+use fnv::FnvHashMap;
+use proc_macro2::Span;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::sync::Arc;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+use syn::{
+    ExprMethodCall, Ident, ImplItem, ItemExternCrate, ItemImpl, ItemMod, ItemTrait, ItemUse, Token,
+    TraitItem, UseTree,
+};
+
+#[cfg(feature = "utoipa")]
+use utoipa::ToSchema;
+
+/// The crates every Rust crate can refer to without declaring them as dependencies.
+const BUILTIN_CRATES: &[&str] = &["std", "core", "alloc", "proc_macro", "test"];
+
+/// A reference to an item found in a source file.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct Usage {
+    /// The file containing the reference.
+    #[cfg_attr(feature = "utoipa", schema(value_type = String))]
+    pub file: Arc<Path>,
+    /// The line number of the reference.
+    #[cfg_attr(feature = "utoipa", schema(value_type = usize))]
+    pub line: NonZeroUsize,
+    /// The column number of the reference.
+    #[cfg_attr(feature = "utoipa", schema(value_type = usize))]
+    pub column: NonZeroUsize,
+    /// How the item is referred to.
+    pub kind: UsageKind,
+    /// The path as written in the source, e.g. `json::from_str`.
+    pub path: String,
+    /// The canonical path the written path resolves to, e.g. `serde_json::from_str`.
+    pub resolved: String,
+    /// The source line containing the reference, trimmed.
+    pub snippet: String,
+}
+
+/// The ways an item can be referred to.
+///
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub enum UsageKind {
+    /// A `use` declaration importing the item.
+    Import,
+    /// A path in an expression, a type, a pattern or a macro invocation.
+    Path,
+    /// A `#[derive(...)]` attribute.
+    Derive,
+    /// A method call with the name of the item. Method calls cannot be resolved without type
+    /// information, so these are only reported in files that also refer to the type.
+    MethodCall,
+}
+
+/// The names declared or imported in a module.
+#[derive(Debug, Default)]
+struct Scope {
+    /// The names of the items defined in the module.
+    items: BTreeSet<String>,
+    /// The imported names and the paths they refer to, as written.
+    imports: BTreeMap<String, Vec<String>>,
+    /// The paths of glob imports, as written and without the trailing `*`.
+    globs: Vec<Vec<String>>,
+}
+
+/// The module a piece of code is in: the root file of its module tree and its path from that root.
+///
+/// The path starts with `crate`.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ModulePath {
+    /// The root file of the module tree, e.g. `src/lib.rs`.
+    pub root: Arc<Path>,
+    /// The path of the module, starting with `crate`.
+    pub path: Vec<String>,
+}
+
+impl ModulePath {
+    /// Returns the path of a child module.
+    ///
+    pub fn child(&self, name: String) -> Self {
+        let mut path = self.path.clone();
+        path.push(name);
+        Self {
+            root: self.root.clone(),
+            path,
+        }
+    }
+}
+
+/// A parsed Rust source file.
+///
+pub struct ParsedFile {
+    /// The path of the file within the crate.
+    pub path: Arc<Path>,
+    /// The module implemented by the file.
+    pub module: ModulePath,
+    /// The syntax tree of the file.
+    pub ast: syn::File,
+    /// The source code of the file.
+    pub source: Arc<str>,
+}
+
+impl ParsedFile {
+    /// Parses a Rust source file of a crate, returning `None` if it is not valid Rust.
+    fn parse(index: &SearchIndex, path: &Path, source: &str) -> Option<Self> {
+        let ast = syn::parse_file(source).ok()?;
+        let module = match index.file_modules.get(path) {
+            Some(module) => ModulePath {
+                root: Arc::from(module.root.as_path()),
+                path: std::iter::once("crate".to_string())
+                    .chain(module.path.iter().cloned())
+                    .collect(),
+            },
+            None => ModulePath {
+                root: Arc::from(path),
+                path: vec!["crate".to_string()],
+            },
+        };
+        Some(Self {
+            path: Arc::from(path),
+            module,
+            ast,
+            source: Arc::from(source),
+        })
+    }
+}
+
+/// Returns the Rust source files of a crate, ordered by path.
+fn rust_files(krate: &Crate) -> Vec<(&Path, &CrateFileDataDesc)> {
+    let mut files = krate
+        .files()
+        .filter(|(path, _)| {
+            path.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("rs"))
+        })
+        .collect::<Vec<_>>();
+    files.sort_by_key(|(path, _)| *path);
+    files
+}
+
+/// The cross-reference index of a crate: the scopes of its modules and the functions defined in
+/// its impl blocks and traits.
+///
+/// Syntax trees cannot be shared between threads, so the index keeps only what is needed to
+/// resolve paths and is built once per crate, see [`Crate::xref_index`]. Requests then parse only
+/// the files they look into.
+///
+#[derive(Debug)]
+pub struct XrefIndex {
+    /// The resolver of the paths written in the files.
+    pub resolver: CrateResolver,
+    /// The root source file of the library.
+    pub lib_root: Arc<Path>,
+    /// Whether some files failed to parse.
+    pub incomplete: bool,
+    /// The functions defined in impl blocks and traits, by name.
+    functions: FnvHashMap<String, Vec<(Owner, Item)>>,
+}
+
+impl XrefIndex {
+    /// Parses every Rust source file of a crate to build its index.
+    ///
+    pub fn new(krate: &Crate) -> Self {
+        let index = krate.item_index();
+        let mut resolver = CrateResolver {
+            externs: extern_names(krate.manifest().map(Arc::as_ref)),
+            scopes: Default::default(),
+        };
+        let mut functions = FnvHashMap::default();
+        let mut incomplete = false;
+        for (path, desc) in rust_files(krate) {
+            let data = krate.file_data(desc);
+            let Some(file) = std::str::from_utf8(&data)
+                .ok()
+                .and_then(|source| ParsedFile::parse(index, path, source))
+            else {
+                incomplete = true;
+                continue;
+            };
+            let mut collector = ScopeCollector {
+                resolver: &mut resolver,
+                module: file.module.clone(),
+            };
+            collector.visit_file(&file.ast);
+            let mut collector = FunctionCollector {
+                file: file.path.clone(),
+                functions: &mut functions,
+            };
+            collector.visit_file(&file.ast);
+        }
+        Self {
+            resolver,
+            lib_root: Arc::from(index.lib_root.as_path()),
+            incomplete,
+            functions,
+        }
+    }
+
+    /// Returns the functions with a name defined in impl blocks and traits, with their owners.
+    fn functions(&self, name: &str) -> &[(Owner, Item)] {
+        self.functions.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the names the canonical paths may be written with in a source file: their last
+    /// segments, and the names they are imported under.
+    ///
+    fn names_of(&self, paths: &[Vec<String>]) -> BTreeSet<String> {
+        let mut names = paths
+            .iter()
+            .filter_map(|path| path.last().cloned())
+            .collect::<BTreeSet<_>>();
+        for (module, scope) in &self.resolver.scopes {
+            for (name, target) in &scope.imports {
+                let target = self.resolver.canonicalize(
+                    &module.root,
+                    self.resolver.resolve_use(module, target, 0),
+                    0,
+                );
+                if paths.iter().any(|path| path.starts_with(&target)) {
+                    names.insert(name.clone());
+                }
+            }
+        }
+        names
+    }
+}
+
+/// Rust source files of a crate, parsed, along with the cross-reference index of the crate.
+///
+pub struct ParsedCrate<'c> {
+    /// The files that were parsed, ordered by path.
+    pub files: Vec<ParsedFile>,
+    /// The cross-reference index of the crate.
+    pub xref: &'c XrefIndex,
+}
+
+impl<'c> ParsedCrate<'c> {
+    /// Parses every Rust source file of a crate.
+    ///
+    pub fn new(krate: &'c Crate) -> Self {
+        Self::with_filter(krate, |_, _| true)
+    }
+
+    /// Parses the Rust source files of a crate accepted by a filter of their paths and sources.
+    ///
+    pub fn with_filter(krate: &'c Crate, filter: impl Fn(&Path, &str) -> bool) -> Self {
+        let index = krate.item_index();
+        let files = rust_files(krate)
+            .into_iter()
+            .filter_map(|(path, desc)| {
+                let data = krate.file_data(desc);
+                let source = std::str::from_utf8(&data).ok()?;
+                filter(path, source)
+                    .then(|| ParsedFile::parse(index, path, source))
+                    .flatten()
+            })
+            .collect();
+        Self {
+            files,
+            xref: krate.xref_index(),
+        }
+    }
+
+    /// Whether some files of the crate failed to parse.
+    ///
+    pub fn incomplete(&self) -> bool {
+        self.xref.incomplete
+    }
+
+    /// Returns the path segment at a position in a file, with the candidate canonical paths of
+    /// the path up to that segment.
+    ///
+    /// Lines and columns are 1-based. Returns `None` if there is no path at the position.
+    ///
+    pub fn symbol_at(&self, file: &Path, line: usize, column: usize) -> Option<Symbol> {
+        let parsed = self.files.iter().find(|parsed| *parsed.path == *file)?;
+        let mut symbol = None;
+        let mut referenced = BTreeSet::new();
+        visit_references(parsed, &self.xref.resolver, |reference| {
+            if reference.kind != UsageKind::MethodCall {
+                referenced.extend(reference.written.split("::").map(String::from));
+            }
+            if symbol.is_some() {
+                return;
+            }
+            let Some(position) = reference.segment_spans.iter().position(|span| {
+                let (start, end) = (span.start(), span.end());
+                start.line == line
+                    && end.line == line
+                    && start.column < column
+                    && column <= end.column
+            }) else {
+                return;
+            };
+            let rest = reference.segment_spans.len() - position - 1;
+            let segments = reference.written.split("::").collect::<Vec<_>>();
+            symbol = Some(Symbol {
+                name: segments
+                    .get(position)
+                    .map_or_else(|| reference.written.clone(), |name| name.to_string()),
+                path: segments[..segments.len().saturating_sub(rest)].join("::"),
+                kind: reference.kind,
+                candidates: reference
+                    .candidates
+                    .into_iter()
+                    .filter(|candidate| candidate.len() > rest)
+                    .map(|mut candidate| {
+                        candidate.truncate(candidate.len() - rest);
+                        candidate
+                    })
+                    .collect(),
+                root: parsed.module.root.clone(),
+                file: parsed.path.clone(),
+                referenced: BTreeSet::new(),
+            });
+        });
+        symbol.map(|symbol| Symbol {
+            referenced,
+            ..symbol
+        })
+    }
+}
+
+/// A path segment found at a position in a source file.
+///
+#[derive(Debug, Clone)]
+pub struct Symbol {
+    /// The identifier at the position.
+    pub name: String,
+    /// The path up to and including the identifier, as written.
+    pub path: String,
+    /// How the path is used.
+    pub kind: UsageKind,
+    /// The candidate canonical paths of the identifier, see [`CrateResolver::resolve`]. For
+    /// method calls, this is the method name.
+    pub candidates: Vec<Vec<String>>,
+    /// The root file of the module tree containing the position.
+    pub root: Arc<Path>,
+    /// The file containing the position.
+    pub file: Arc<Path>,
+    /// The identifiers of the paths written in the file, used to rank the methods a method call
+    /// may refer to.
+    pub referenced: BTreeSet<String>,
+}
+
+/// Resolves the paths written in the source files of a crate to canonical paths.
+///
+#[derive(Debug)]
+pub struct CrateResolver {
+    externs: BTreeSet<String>,
+    scopes: FnvHashMap<ModulePath, Scope>,
+}
+
+impl CrateResolver {
+    /// Returns the names external crates are referred to by.
+    ///
+    pub fn externs(&self) -> &BTreeSet<String> {
+        &self.externs
+    }
+
+    /// Resolves a path written in a `use` declaration, or the target of an import, in a module.
+    ///
+    fn resolve_use(&self, module: &ModulePath, segments: &[String], depth: usize) -> Vec<String> {
+        let Some(first) = segments.first() else {
+            return module.path.clone();
+        };
+        match first.as_str() {
+            "crate" | "$crate" => std::iter::once("crate".to_string())
+                .chain(segments[1..].iter().cloned())
+                .collect(),
+            "self" => [&module.path, &segments[1..]].concat(),
+            "super" => {
+                let supers = segments.iter().take_while(|s| *s == "super").count();
+                let parent = module.path.len().saturating_sub(supers).max(1);
+                [&module.path[..parent], &segments[supers..]].concat()
+            }
+            first if self.externs.contains(first) => segments.to_vec(),
+            first => match self.scopes.get(module).and_then(|s| s.imports.get(first)) {
+                Some(target) if depth < 8 && target.as_slice() != &segments[..1] => {
+                    let target = self.resolve_use(module, target, depth + 1);
+                    [&target, &segments[1..]].concat()
+                }
+                _ => [&module.path, segments].concat(),
+            },
+        }
+    }
+
+    /// Follows the imports along a path starting with `crate`, so that items re-exported or
+    /// imported into other modules resolve to the path of their definition.
+    ///
+    fn canonicalize(&self, root: &Arc<Path>, path: Vec<String>, depth: usize) -> Vec<String> {
+        if depth >= 8 || path.first().map(String::as_str) != Some("crate") {
+            return path;
+        }
+        for i in 1..path.len() {
+            let module = ModulePath {
+                root: root.clone(),
+                path: path[..i].to_vec(),
+            };
+            let Some(scope) = self.scopes.get(&module) else {
+                continue;
+            };
+            if scope.items.contains(&path[i]) {
+                continue;
+            }
+            if let Some(target) = scope.imports.get(&path[i]) {
+                let target = self.resolve_use(&module, target, 0);
+                let resolved = [&target, &path[i + 1..]].concat();
+                return self.canonicalize(root, resolved, depth + 1);
+            }
+        }
+        path
+    }
+
+    /// Resolves a path written in an expression, a type, a pattern or an attribute in a module.
+    ///
+    /// Returns the candidate canonical paths in order of preference: a name that is neither
+    /// defined, imported nor an external crate may come from any of the glob imports in scope.
+    /// Returns no candidates for paths starting with `Self`.
+    ///
+    pub fn resolve(
+        &self,
+        module: &ModulePath,
+        segments: &[String],
+        leading_colon: bool,
+    ) -> Vec<Vec<String>> {
+        let Some(first) = segments.first() else {
+            return Vec::new();
+        };
+        if leading_colon || self.externs.contains(first) {
+            return vec![segments.to_vec()];
+        }
+        let scope = self.scopes.get(module);
+        let canonical = |path| self.canonicalize(&module.root, path, 0);
+        match first.as_str() {
+            "Self" => Vec::new(),
+            "crate" | "$crate" | "self" | "super" => {
+                vec![canonical(self.resolve_use(module, segments, 0))]
+            }
+            first
+                if scope.is_some_and(|scope| {
+                    scope.items.contains(first) || scope.imports.contains_key(first)
+                }) =>
+            {
+                vec![canonical(self.resolve_use(module, segments, 0))]
+            }
+            _ => {
+                let mut candidates = vec![canonical([&module.path, segments].concat())];
+                for glob in scope.iter().flat_map(|scope| scope.globs.iter()) {
+                    let glob = self.resolve_use(module, glob, 0);
+                    candidates.push(canonical([&glob, segments].concat()));
+                }
+                candidates
+            }
+        }
+    }
+
+    /// Resolves the source path of a `use` declaration in a module.
+    ///
+    pub fn resolve_import(&self, module: &ModulePath, source: &str) -> Vec<String> {
+        let segments = source
+            .split("::")
+            .filter(|s| !s.is_empty() && *s != "*")
+            .map(String::from)
+            .collect::<Vec<_>>();
+        let resolved = match segments.first().map(String::as_str) {
+            Some(first) if self.externs.contains(first) => segments,
+            Some("crate" | "$crate" | "self" | "super") => self.resolve_use(module, &segments, 0),
+            // A `use` path is relative to the module, unless its first segment is imported.
+            Some(first)
+                if self
+                    .scopes
+                    .get(module)
+                    .is_some_and(|scope| !scope.items.contains(first)) =>
+            {
+                self.resolve_use(module, &segments, 0)
+            }
+            _ => [module.path.as_slice(), &segments].concat(),
+        };
+        self.canonicalize(&module.root, resolved, 0)
+    }
+}
+
+/// Collects the items and `use` declarations of a file into the scopes of its modules.
+struct ScopeCollector<'r> {
+    resolver: &'r mut CrateResolver,
+    module: ModulePath,
+}
+
+impl ScopeCollector<'_> {
+    fn scope(&mut self) -> &mut Scope {
+        self.resolver.scopes.entry(self.module.clone()).or_default()
+    }
+}
+
+impl<'ast> Visit<'ast> for ScopeCollector<'_> {
+    fn visit_item(&mut self, i: &'ast syn::Item) {
+        let ident = match i {
+            syn::Item::Const(item) => Some(&item.ident),
+            syn::Item::Enum(item) => Some(&item.ident),
+            syn::Item::Fn(item) => Some(&item.sig.ident),
+            syn::Item::Macro(item) => item.ident.as_ref(),
+            syn::Item::Mod(item) => Some(&item.ident),
+            syn::Item::Static(item) => Some(&item.ident),
+            syn::Item::Struct(item) => Some(&item.ident),
+            syn::Item::Trait(item) => Some(&item.ident),
+            syn::Item::TraitAlias(item) => Some(&item.ident),
+            syn::Item::Type(item) => Some(&item.ident),
+            syn::Item::Union(item) => Some(&item.ident),
+            _ => None,
+        };
+        if let Some(ident) = ident {
+            self.scope().items.insert(ident.to_string());
+        }
+        syn::visit::visit_item(self, i);
+    }
+
+    fn visit_item_mod(&mut self, i: &'ast ItemMod) {
+        if i.content.is_some() {
+            let parent = self.module.clone();
+            self.module = parent.child(i.ident.to_string());
+            syn::visit::visit_item_mod(self, i);
+            self.module = parent;
+        }
+    }
+
+    fn visit_item_use(&mut self, i: &'ast ItemUse) {
+        let mut uses = Vec::new();
+        flatten_use_tree(&i.tree, String::new(), &mut uses);
+        let scope = self.scope();
+        for (source, name) in uses {
+            let segments = source
+                .split("::")
+                .filter(|s| *s != "*")
+                .map(String::from)
+                .collect::<Vec<_>>();
+            match name.as_str() {
+                "*" => scope.globs.push(segments),
+                "_" => {}
+                _ => {
+                    scope.imports.insert(name, segments);
+                }
+            }
+        }
+    }
+
+    fn visit_item_extern_crate(&mut self, i: &'ast ItemExternCrate) {
+        let name = i.ident.to_string();
+        if name == "self" {
+            return;
+        }
+        if let Some((_, rename)) = &i.rename {
+            self.scope()
+                .imports
+                .insert(rename.to_string(), vec![name.clone()]);
+        }
+        self.resolver.externs.insert(name);
+    }
+}
+
+/// A path found in a source file.
+///
+#[derive(Debug, Clone)]
+pub struct Reference {
+    /// How the path is used.
+    pub kind: UsageKind,
+    /// The path as written.
+    pub written: String,
+    /// The candidate canonical paths, see [`CrateResolver::resolve`].
+    pub candidates: Vec<Vec<String>>,
+    /// The span of the path.
+    pub span: Span,
+    /// The spans of the segments of the path, in order.
+    pub segment_spans: Vec<Span>,
+}
+
+/// Visits all paths in a parsed file, resolving them with the resolver of its crate.
+///
+/// Method calls are reported with the method name as the only candidate.
+///
+pub fn visit_references(
+    file: &ParsedFile,
+    resolver: &CrateResolver,
+    callback: impl FnMut(Reference),
+) {
+    let mut visitor = ReferenceVisitor {
+        resolver,
+        module: file.module.clone(),
+        callback,
+    };
+    visitor.visit_file(&file.ast);
+}
+
+struct ReferenceVisitor<'r, F> {
+    resolver: &'r CrateResolver,
+    module: ModulePath,
+    callback: F,
+}
+
+impl<F: FnMut(Reference)> ReferenceVisitor<'_, F> {
+    fn report_path(&mut self, path: &syn::Path, kind: UsageKind) {
+        let segments = path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect::<Vec<_>>();
+        let candidates =
+            self.resolver
+                .resolve(&self.module, &segments, path.leading_colon.is_some());
+        (self.callback)(Reference {
+            kind,
+            written: segments.join("::"),
+            candidates,
+            span: path.span(),
+            segment_spans: path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.span())
+                .collect(),
+        });
+    }
+}
+
+impl<'ast, F: FnMut(Reference)> Visit<'ast> for ReferenceVisitor<'_, F> {
+    fn visit_item_mod(&mut self, i: &'ast ItemMod) {
+        if i.content.is_some() {
+            let parent = self.module.clone();
+            self.module = parent.child(i.ident.to_string());
+            syn::visit::visit_item_mod(self, i);
+            self.module = parent;
+        }
+    }
+
+    fn visit_item_use(&mut self, i: &'ast ItemUse) {
+        let mut uses = Vec::new();
+        flatten_use_idents(&i.tree, &mut Vec::new(), &mut uses);
+        for idents in uses {
+            let source = idents
+                .iter()
+                .map(|ident| ident.to_string())
+                .collect::<Vec<_>>()
+                .join("::");
+            let resolved = self.resolver.resolve_import(&self.module, &source);
+            (self.callback)(Reference {
+                kind: UsageKind::Import,
+                written: source,
+                candidates: vec![resolved],
+                span: idents
+                    .first()
+                    .map_or_else(|| i.span(), |ident| ident.span()),
+                segment_spans: idents.iter().map(|ident| ident.span()).collect(),
+            });
+        }
+    }
+
+    fn visit_attribute(&mut self, i: &'ast syn::Attribute) {
+        if i.path().is_ident("derive") {
+            if let Ok(paths) =
+                i.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+            {
+                for path in &paths {
+                    self.report_path(path, UsageKind::Derive);
+                }
+            }
+        }
+    }
+
+    fn visit_path(&mut self, i: &'ast syn::Path) {
+        self.report_path(i, UsageKind::Path);
+        syn::visit::visit_path(self, i);
+    }
+
+    fn visit_expr_method_call(&mut self, i: &'ast ExprMethodCall) {
+        let method = i.method.to_string();
+        (self.callback)(Reference {
+            kind: UsageKind::MethodCall,
+            written: method.clone(),
+            candidates: vec![vec![method]],
+            span: i.method.span(),
+            segment_spans: vec![i.method.span()],
+        });
+        syn::visit::visit_expr_method_call(self, i);
+    }
+}
+
+/// Collects the paths imported by a `use` tree as identifiers, keeping their spans.
+///
+/// `self` in a group refers to the path of the group itself and globs to the path they are on.
+///
+fn flatten_use_idents(tree: &UseTree, prefix: &mut Vec<Ident>, uses: &mut Vec<Vec<Ident>>) {
+    match tree {
+        UseTree::Path(path) => {
+            prefix.push(path.ident.clone());
+            flatten_use_idents(&path.tree, prefix, uses);
+            prefix.pop();
+        }
+        UseTree::Name(name) if name.ident == "self" => uses.push(prefix.clone()),
+        UseTree::Name(name) => {
+            uses.push([prefix.as_slice(), std::slice::from_ref(&name.ident)].concat())
+        }
+        UseTree::Rename(rename) if rename.ident == "self" => uses.push(prefix.clone()),
+        UseTree::Rename(rename) => {
+            uses.push([prefix.as_slice(), std::slice::from_ref(&rename.ident)].concat())
+        }
+        UseTree::Glob(_) => uses.push(prefix.clone()),
+        UseTree::Group(group) => {
+            for tree in &group.items {
+                flatten_use_idents(tree, prefix, uses);
+            }
+        }
+    }
+}
+
+/// Returns the names the external crates of a crate are referred to by.
+///
+/// This includes the crate's own library, which its binaries, tests and examples refer to by name.
+///
+pub fn extern_names(manifest: Option<&Manifest>) -> BTreeSet<String> {
+    let mut names = BUILTIN_CRATES
+        .iter()
+        .map(|name| name.to_string())
+        .collect::<BTreeSet<_>>();
+    if let Some(manifest) = manifest {
+        names
+            .extend(all_dependencies(manifest).map(|dependency| dependency.name.replace('-', "_")));
+        names.insert(lib_ident(manifest));
+    }
+    names
+}
+
+/// Returns the normal, dev and build dependencies of a manifest, including target-specific ones.
+fn all_dependencies(manifest: &Manifest) -> impl Iterator<Item = &Dependency> {
+    let dependencies = &manifest.dependencies;
+    [&dependencies.normal, &dependencies.dev, &dependencies.build]
+        .into_iter()
+        .chain(
+            dependencies
+                .target
+                .iter()
+                .flat_map(|target| [&target.normal, &target.dev, &target.build]),
+        )
+        .flatten()
+}
+
+/// The item whose usages are searched for, with all the paths it can be referred to by.
+///
+#[derive(Debug, Clone)]
+pub struct UsageTarget {
+    /// The package name of the crate defining the item.
+    pub krate: String,
+    /// The paths of the item within its crate, starting with `crate`, including re-exports.
+    pub paths: BTreeSet<Vec<String>>,
+    /// The name of the associated item, if the target is a member of a type, e.g. `new` in `Bytes::new`.
+    pub member: Option<String>,
+}
+
+impl UsageTarget {
+    /// Resolves an item path within the index of the crate defining it.
+    ///
+    /// The path may start with `crate` or with the crate name, or be relative to the crate root.
+    /// Paths of associated items, like `Bytes::new`, are resolved to the paths of their type.
+    ///
+    pub fn new(index: &SearchIndex, krate: &str, path: &str) -> Self {
+        let crate_ident = krate.replace('-', "_");
+        let path = path.trim().trim_start_matches("::");
+        let relative = path
+            .strip_prefix("crate::")
+            .or_else(|| path.strip_prefix(&format!("{crate_ident}::")))
+            .unwrap_or(path);
+        let full = format!("crate::{relative}");
+
+        let api = PublicApi::new(index);
+        let split = |path: &str| path.split("::").map(String::from).collect::<Vec<_>>();
+        let items = api.lookup(&full);
+        if !items.is_empty() {
+            return Self {
+                krate: krate.to_string(),
+                paths: items
+                    .iter()
+                    .flat_map(|item| api.paths_of(item))
+                    .map(split)
+                    .collect(),
+                member: None,
+            };
+        }
+        if let Some((parent, member)) = full.rsplit_once("::") {
+            let items = api.lookup(parent);
+            if !items.is_empty() {
+                return Self {
+                    krate: krate.to_string(),
+                    paths: items
+                        .iter()
+                        .flat_map(|item| api.paths_of(item))
+                        .map(split)
+                        .collect(),
+                    member: Some(member.to_string()),
+                };
+            }
+        }
+        // Not a known public item, e.g. a module: search for the path as written.
+        Self {
+            krate: krate.to_string(),
+            paths: BTreeSet::from([split(&full)]),
+            member: None,
+        }
+    }
+}
+
+/// Finds the usages of an item of another crate in the Rust source files of a crate.
+///
+/// Returns the usages and whether some files could not be parsed. Fails if the crate does not
+/// depend on the crate defining the item.
+///
+pub fn find_usages(krate: &Crate, target: &UsageTarget) -> anyhow::Result<(Vec<Usage>, bool)> {
+    let target_ident = target.krate.replace('-', "_");
+    let names = match krate.manifest() {
+        Some(manifest) => all_dependencies(manifest)
+            .filter(|dependency| dependency.package_name() == target.krate)
+            .map(|dependency| dependency.name.replace('-', "_"))
+            .collect::<BTreeSet<_>>(),
+        None => BTreeSet::from([target_ident]),
+    };
+    if names.is_empty() {
+        anyhow::bail!("The crate does not depend on `{}`.", target.krate);
+    }
+
+    // The paths of the target as seen from the dependent crate.
+    let paths = names
+        .iter()
+        .flat_map(|name| {
+            target.paths.iter().map(move |path| {
+                std::iter::once(name.clone())
+                    .chain(path[1..].iter().cloned())
+                    .chain(target.member.clone())
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect::<Vec<_>>();
+    let parsed = parse_mentioning(krate, &paths, target.member.is_some());
+    let usages = collect_usages(&parsed, &paths, target.member.as_deref());
+    Ok((usages, parsed.incomplete()))
+}
+
+/// Finds the references to an item of a crate within the crate itself.
+///
+/// This covers the library as well as binaries, tests and examples referring to the library by
+/// name.
+///
+pub fn find_references(krate: &Crate, path: &str) -> References {
+    let index = krate.item_index();
+    let name = krate
+        .manifest()
+        .map(|manifest| manifest.package.name.clone())
+        .unwrap_or_default();
+    let target = UsageTarget::new(index, &name, path);
+    let lib_ident = krate
+        .manifest()
+        .map(|manifest| lib_ident(manifest))
+        .unwrap_or_else(|| name.replace('-', "_"));
+    let paths = target
+        .paths
+        .iter()
+        .flat_map(|path| {
+            let external = std::iter::once(lib_ident.clone()).chain(path[1..].iter().cloned());
+            [path.clone(), external.collect()]
+        })
+        .map(|mut path| {
+            path.extend(target.member.clone());
+            path
+        })
+        .collect::<Vec<_>>();
+
+    let parsed = parse_mentioning(krate, &paths, target.member.is_some());
+    References {
+        usages: collect_usages(&parsed, &paths, target.member.as_deref()),
+        incomplete: parsed.incomplete(),
+    }
+}
+
+/// Parses only the Rust source files of a crate mentioning a name the canonical paths may be
+/// written with. If the paths end with a member, the name of its type also counts, since method
+/// calls are reported in the files referring to the type.
+///
+fn parse_mentioning<'c>(krate: &'c Crate, paths: &[Vec<String>], member: bool) -> ParsedCrate<'c> {
+    let type_paths = paths
+        .iter()
+        .filter(|path| member && path.len() > 1)
+        .map(|path| path[..path.len() - 1].to_vec())
+        .collect::<Vec<_>>();
+    let names = krate
+        .xref_index()
+        .names_of(&[paths, type_paths.as_slice()].concat());
+    ParsedCrate::with_filter(krate, |_, source| {
+        names.iter().any(|name| source.contains(name.as_str()))
+    })
+}
+
+/// The references to an item within its crate.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct References {
+    /// The references found.
+    pub usages: Vec<Usage>,
+    /// Whether some files of the crate failed to parse, so references may be missing.
+    pub incomplete: bool,
+}
+
+/// Collects the references to any of the given canonical paths in a parsed crate.
+///
+/// Paths starting with `crate` only match in the library module tree. If `member` is set, the
+/// last segment of each path is the member, and method calls with its name are reported in the
+/// files referring to the type.
+///
+fn collect_usages(parsed: &ParsedCrate, paths: &[Vec<String>], member: Option<&str>) -> Vec<Usage> {
+    let type_paths = paths
+        .iter()
+        .filter(|_| member.is_some())
+        .map(|path| &path[..path.len() - 1])
+        .collect::<Vec<_>>();
+    let mut usages = Vec::new();
+    for file in &parsed.files {
+        let in_lib = *file.module.root == *parsed.xref.lib_root;
+        let matches = |candidate: &Vec<String>, path: &[String]| {
+            candidate.starts_with(path) && (in_lib || path[0] != "crate")
+        };
+        let mut type_referenced = false;
+        let mut method_calls = Vec::new();
+        visit_references(file, &parsed.xref.resolver, |reference| {
+            if reference.kind == UsageKind::MethodCall {
+                if member == reference.candidates[0].first().map(String::as_str) {
+                    method_calls.push(reference);
+                }
+                return;
+            }
+            let matched = reference
+                .candidates
+                .iter()
+                .find(|candidate| paths.iter().any(|path| matches(candidate, path)));
+            if reference
+                .candidates
+                .iter()
+                .any(|candidate| type_paths.iter().any(|path| matches(candidate, path)))
+            {
+                type_referenced = true;
+            }
+            if let Some(matched) = matched {
+                usages.push(usage(file, &reference, matched.join("::")));
+            }
+        });
+        if type_referenced {
+            for reference in method_calls {
+                let resolved = reference.written.clone();
+                usages.push(usage(file, &reference, resolved));
+            }
+        }
+    }
+    usages.sort_by(|a, b| (&a.file, a.line, a.column).cmp(&(&b.file, b.line, b.column)));
+    usages
+}
+
+/// Creates a `Usage` from a reference found in a file.
+fn usage(file: &ParsedFile, reference: &Reference, resolved: String) -> Usage {
+    let start = reference.span.start();
+    Usage {
+        file: file.path.clone(),
+        line: NonZeroUsize::new(start.line).unwrap_or(NonZeroUsize::MIN),
+        column: NonZeroUsize::MIN.saturating_add(start.column),
+        kind: reference.kind,
+        path: reference.written.clone(),
+        resolved,
+        snippet: file
+            .source
+            .lines()
+            .nth(start.line.saturating_sub(1))
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+    }
+}
+
+/// The definitions of the symbol at a position in a source file.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct Definitions {
+    /// The identifier at the position.
+    pub symbol: String,
+    /// The path up to and including the identifier, as written.
+    pub path: String,
+    /// How the path is used.
+    pub kind: UsageKind,
+    /// The canonical path of the definitions, starting with the name of the crate defining them.
+    pub resolved: Option<String>,
+    /// The definitions found, in the crate itself or in its dependencies.
+    pub definitions: Vec<CrateItem>,
+}
+
+/// Where a path leads to.
+///
+#[derive(Debug, Clone)]
+pub enum Resolution {
+    /// The path is defined in the crate, at the given canonical path.
+    Local(Vec<String>, Vec<Item>),
+    /// The path leaves the crate through a dependency, at the given path within the dependency,
+    /// starting with `crate`.
+    Dependency(Dependency, Vec<String>),
+    /// The path could not be resolved, e.g. a local variable or an item of the standard library.
+    Unresolved,
+}
+
+/// Resolves the symbol at a position to its definitions in the crate, or to the dependency it
+/// leaves the crate through.
+///
+pub fn resolve_symbol(krate: &Crate, symbol: &Symbol) -> Resolution {
+    if symbol.kind == UsageKind::MethodCall {
+        // Without types, a method call may refer to any method with its name. Those of the types
+        // referenced or defined in the file come first.
+        let mut methods = krate
+            .xref_index()
+            .functions(&symbol.name)
+            .iter()
+            .collect::<Vec<_>>();
+        methods.sort_by_key(|(owner, item)| {
+            !(symbol.referenced.contains(owner.name()) || item.file == symbol.file)
+        });
+        let methods = methods
+            .into_iter()
+            .map(|(_, item)| item.clone())
+            .collect::<Vec<_>>();
+        return match methods.is_empty() {
+            true => Resolution::Unresolved,
+            false => Resolution::Local(vec![symbol.name.clone()], methods),
+        };
+    }
+    let lib_root = &krate.item_index().lib_root;
+    let lib_ident = krate.manifest().map(|manifest| lib_ident(manifest));
+    for candidate in &symbol.candidates {
+        let resolution = match candidate.first().map(String::as_str) {
+            Some("crate") => resolve_path(krate, candidate.clone(), &symbol.root),
+            Some(first) if Some(first) == lib_ident.as_deref() => {
+                let path = std::iter::once("crate".to_string())
+                    .chain(candidate[1..].iter().cloned())
+                    .collect();
+                resolve_path(krate, path, lib_root)
+            }
+            Some(first) => match dependency(krate, first) {
+                Some(dependency) => {
+                    let path = std::iter::once("crate".to_string())
+                        .chain(candidate[1..].iter().cloned())
+                        .collect();
+                    Resolution::Dependency(dependency.clone(), path)
+                }
+                None => Resolution::Unresolved,
+            },
+            None => Resolution::Unresolved,
+        };
+        if !matches!(resolution, Resolution::Unresolved) {
+            return resolution;
+        }
+    }
+    Resolution::Unresolved
+}
+
+/// Resolves a canonical path starting with `crate` within a crate, following re-exports of
+/// other crates.
+///
+pub fn resolve_path(krate: &Crate, path: Vec<String>, root: &Path) -> Resolution {
+    let items = find_definitions(krate, &path, root);
+    if !items.is_empty() {
+        return Resolution::Local(path, items);
+    }
+    if root != krate.item_index().lib_root {
+        return Resolution::Unresolved;
+    }
+    // A re-export of an item of another crate, e.g. `pub use bytes::Bytes;`.
+    let api = PublicApi::new(krate.item_index());
+    for end in (2..=path.len()).rev() {
+        let Some(reexport) = api.reexports.get(&path[..end].join("::")) else {
+            continue;
+        };
+        let source = reexport.source.split("::").collect::<Vec<_>>();
+        if let Some(dependency) = dependency(krate, source[0]) {
+            let path = std::iter::once("crate")
+                .chain(source[1..].iter().copied())
+                .map(String::from)
+                .chain(path[end..].iter().cloned())
+                .collect();
+            return Resolution::Dependency(dependency.clone(), path);
+        }
+    }
+    Resolution::Unresolved
+}
+
+/// Returns the dependency of a crate referred to by a name.
+///
+pub fn dependency<'c>(krate: &'c Crate, name: &str) -> Option<&'c Dependency> {
+    all_dependencies(krate.manifest()?).find(|dependency| dependency.name.replace('-', "_") == name)
+}
+
+/// Returns the name the library of a crate is referred to by.
+fn lib_ident(manifest: &Manifest) -> String {
+    manifest
+        .lib
+        .as_ref()
+        .map(|lib| lib.name.as_str())
+        .filter(|name| !name.is_empty())
+        .unwrap_or(manifest.package.name.as_str())
+        .replace('-', "_")
+}
+
+/// Finds the definitions of a canonical path starting with `crate` within a crate.
+///
+/// The `root` is the root file of the module tree the path belongs to. Paths of the library are
+/// also looked up among the re-exports of its public API. Members of types and traits, like
+/// `crate::Bytes::new`, resolve to the functions defined in their impl blocks, or to the type
+/// itself for other members such as enum variants.
+///
+pub fn find_definitions(krate: &Crate, path: &[String], root: &Path) -> Vec<Item> {
+    let index = krate.item_index();
+    let Some((name, module)) = path.split_last() else {
+        return Vec::new();
+    };
+    let is_impl =
+        |item: &Item| matches!(item.type_, ItemType::ImplType | ItemType::ImplTraitForType);
+    let joined = path.join("::");
+    let mut items = if root == index.lib_root {
+        let items = index
+            .items()
+            .filter(|item| !is_impl(item) && item.path.as_deref() == Some(joined.as_str()))
+            .cloned()
+            .collect::<Vec<_>>();
+        if items.is_empty() {
+            PublicApi::new(index)
+                .lookup(&joined)
+                .into_iter()
+                .cloned()
+                .collect()
+        } else {
+            items
+        }
+    } else {
+        // Items outside the library have no path, match them by module and name instead.
+        index
+            .items()
+            .filter(|item| {
+                !is_impl(item)
+                    && item.name == *name
+                    && index
+                        .file_modules
+                        .get(item.file.as_ref())
+                        .is_some_and(|file| {
+                            file.root == root
+                                && module.get(1..).is_some_and(|m| m.starts_with(&file.path))
+                        })
+            })
+            .cloned()
+            .collect()
+    };
+    if items.is_empty() && path.len() > 2 {
+        let parents = find_definitions(krate, module, root);
+        items = parents
+            .iter()
+            .flat_map(|parent| find_members(krate, parent, name))
+            .collect();
+        if items.is_empty() {
+            items = parents;
+        }
+    }
+    items
+}
+
+/// Finds the methods with a name defined in any impl block or trait of a crate.
+///
+pub fn find_methods(krate: &Crate, name: &str) -> Vec<Item> {
+    find_functions(krate, name, |_| true)
+}
+
+/// Finds the functions named `member` in the impl blocks of a type, or in the definition of a trait.
+fn find_members(krate: &Crate, parent: &Item, member: &str) -> Vec<Item> {
+    let path = parent.path.as_deref();
+    let mut members = find_functions(krate, member, |owner| match (owner, parent.type_) {
+        (Owner::Trait(ident), ItemType::Trait) => *ident == parent.name,
+        (Owner::Impl(ident), ItemType::Struct | ItemType::Enum | ItemType::TypeAlias) => {
+            *ident == parent.name
+        }
+        _ => false,
+    });
+    for item in &mut members {
+        item.path = path.map(|path| format!("{path}::{member}"));
+    }
+    members
+}
+
+/// The item containing a function.
+#[derive(Debug, Clone)]
+enum Owner {
+    /// An impl block of a type, by the name of the type.
+    Impl(String),
+    /// A trait definition, by the name of the trait.
+    Trait(String),
+}
+
+impl Owner {
+    /// Returns the name of the type or trait.
+    fn name(&self) -> &str {
+        match self {
+            Owner::Impl(name) | Owner::Trait(name) => name,
+        }
+    }
+}
+
+/// Finds the functions with a name defined in the impl blocks and traits accepted by a filter.
+fn find_functions(krate: &Crate, name: &str, filter: impl Fn(&Owner) -> bool) -> Vec<Item> {
+    krate
+        .xref_index()
+        .functions(name)
+        .iter()
+        .filter(|(owner, _)| filter(owner))
+        .map(|(_, item)| item.clone())
+        .collect()
+}
+
+/// Collects the functions defined in the impl blocks and traits of a file.
+struct FunctionCollector<'a> {
+    file: Arc<Path>,
+    functions: &'a mut FnvHashMap<String, Vec<(Owner, Item)>>,
+}
+
+impl FunctionCollector<'_> {
+    fn push(
+        &mut self,
+        owner: Owner,
+        attrs: &[syn::Attribute],
+        sig: &syn::Signature,
+        span: Span,
+        public: bool,
+    ) {
+        let start = attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("doc"))
+            .map(|attr| attr.span().start().line)
+            .fold(span.start().line, usize::min);
+        let name = sig.ident.to_string();
+        let item = Item {
+            name: name.clone(),
+            type_: ItemType::Function,
+            file: self.file.clone(),
+            line_range: NonZeroUsize::new(start).unwrap_or(NonZeroUsize::MIN)
+                ..=NonZeroUsize::new(span.end().line).unwrap_or(NonZeroUsize::MAX),
+            cfg: None,
+            path: None,
+            public,
+            exported: false,
+            signature: Some(tokens(sig)),
+            impl_info: None,
+        };
+        self.functions.entry(name).or_default().push((owner, item));
+    }
+}
+
+impl<'ast> Visit<'ast> for FunctionCollector<'_> {
+    fn visit_item_impl(&mut self, i: &'ast ItemImpl) {
+        let syn::Type::Path(self_ty) = &*i.self_ty else {
+            return;
+        };
+        let Some(ident) = self_ty.path.segments.last().map(|segment| &segment.ident) else {
+            return;
+        };
+        for item in &i.items {
+            if let ImplItem::Fn(function) = item {
+                let public =
+                    i.trait_.is_some() || matches!(function.vis, syn::Visibility::Public(_));
+                self.push(
+                    Owner::Impl(ident.to_string()),
+                    &function.attrs,
+                    &function.sig,
+                    function.span(),
+                    public,
+                );
+            }
+        }
+    }
+
+    fn visit_item_trait(&mut self, i: &'ast ItemTrait) {
+        for item in &i.items {
+            if let TraitItem::Fn(function) = item {
+                let public = matches!(i.vis, syn::Visibility::Public(_));
+                self.push(
+                    Owner::Trait(i.ident.to_string()),
+                    &function.attrs,
+                    &function.sig,
+                    function.span(),
+                    public,
+                );
+            }
+        }
+    }
+}